@@ -10,6 +10,7 @@ pub mod parse_bpf_loader;
 #[allow(deprecated)]
 pub mod parse_config;
 pub mod parse_nonce;
+pub mod parse_sonic_fee_settlement;
 pub mod parse_stake;
 pub mod parse_sysvar;
 pub mod parse_token;
@@ -2,14 +2,15 @@ use {
     crate::{
         parse_address_lookup_table::parse_address_lookup_table,
         parse_bpf_loader::parse_bpf_upgradeable_loader, parse_config::parse_config,
-        parse_nonce::parse_nonce, parse_stake::parse_stake, parse_sysvar::parse_sysvar,
-        parse_token::parse_token, parse_vote::parse_vote,
+        parse_nonce::parse_nonce, parse_sonic_fee_settlement::parse_sonic_fee_settlement,
+        parse_stake::parse_stake, parse_sysvar::parse_sysvar, parse_token::parse_token,
+        parse_vote::parse_vote,
     },
     inflector::Inflector,
     serde_json::Value,
     solana_sdk::{
-        address_lookup_table, instruction::InstructionError, pubkey::Pubkey, stake, system_program,
-        sysvar, vote,
+        address_lookup_table, instruction::InstructionError, pubkey::Pubkey, sonic_fee_settlement,
+        stake, system_program, sysvar, vote,
     },
     std::collections::HashMap,
     thiserror::Error,
@@ -19,6 +20,7 @@ lazy_static! {
     static ref ADDRESS_LOOKUP_PROGRAM_ID: Pubkey = address_lookup_table::program::id();
     static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
     static ref CONFIG_PROGRAM_ID: Pubkey = solana_config_program::id();
+    static ref SONIC_FEE_SETTLEMENT_PROGRAM_ID: Pubkey = sonic_fee_settlement::program::id();
     static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
     static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
     static ref SYSVAR_PROGRAM_ID: Pubkey = sysvar::id();
@@ -37,6 +39,10 @@ lazy_static! {
         m.insert(*SYSTEM_PROGRAM_ID, ParsableAccount::Nonce);
         m.insert(spl_token::id(), ParsableAccount::SplToken);
         m.insert(spl_token_2022::id(), ParsableAccount::SplToken2022);
+        m.insert(
+            *SONIC_FEE_SETTLEMENT_PROGRAM_ID,
+            ParsableAccount::SonicFeeSettlement,
+        );
         m.insert(*STAKE_PROGRAM_ID, ParsableAccount::Stake);
         m.insert(*SYSVAR_PROGRAM_ID, ParsableAccount::Sysvar);
         m.insert(*VOTE_PROGRAM_ID, ParsableAccount::Vote);
@@ -77,6 +83,7 @@ pub enum ParsableAccount {
     BpfUpgradeableLoader,
     Config,
     Nonce,
+    SonicFeeSettlement,
     SplToken,
     SplToken2022,
     Stake,
@@ -108,6 +115,9 @@ pub fn parse_account_data(
         }
         ParsableAccount::Config => serde_json::to_value(parse_config(data, pubkey)?)?,
         ParsableAccount::Nonce => serde_json::to_value(parse_nonce(data)?)?,
+        ParsableAccount::SonicFeeSettlement => {
+            serde_json::to_value(parse_sonic_fee_settlement(data)?)?
+        }
         ParsableAccount::SplToken | ParsableAccount::SplToken2022 => {
             serde_json::to_value(parse_token(data, additional_data.spl_token_decimals)?)?
         }
@@ -0,0 +1,253 @@
+use {
+    crate::{
+        parse_account_data::{ParsableAccount, ParseAccountError},
+        StringAmount,
+    },
+    bincode::deserialize,
+    solana_sdk::{
+        clock::Slot,
+        sonic_fee_settlement::state::{
+            DynamicFeeMultiplier, FeeMultiplierConfig, SettledRange, SettlementAccount,
+            SettlementAccountType, SettlementCursor, SettlementState,
+        },
+    },
+};
+
+pub fn parse_sonic_fee_settlement(
+    data: &[u8],
+) -> Result<SonicFeeSettlementAccountType, ParseAccountError> {
+    let settlement_state: SettlementState = deserialize(data)
+        .map_err(|_| ParseAccountError::AccountNotParsable(ParsableAccount::SonicFeeSettlement))?;
+    let parsed_account = match settlement_state {
+        SettlementState::Uninitialized => SonicFeeSettlementAccountType::Uninitialized,
+        SettlementState::FeeBillSettled(settlement_account) => {
+            SonicFeeSettlementAccountType::SettlementAccount(settlement_account.into())
+        }
+        SettlementState::SettlementCursor(cursor) => {
+            SonicFeeSettlementAccountType::SettlementCursor(cursor.into())
+        }
+        SettlementState::FeeMultiplier(config) => {
+            SonicFeeSettlementAccountType::FeeMultiplier(config.into())
+        }
+    };
+    Ok(parsed_account)
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "type", content = "info")]
+pub enum SonicFeeSettlementAccountType {
+    Uninitialized,
+    SettlementAccount(UiSettlementAccount),
+    SettlementCursor(UiSettlementCursor),
+    FeeMultiplier(UiFeeMultiplierConfig),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSettlementAccount {
+    pub owner: String,
+    pub account_type: String,
+    pub amount: StringAmount,
+    pub withdrawable: StringAmount,
+    pub withdrawn: StringAmount,
+}
+
+impl From<SettlementAccount> for UiSettlementAccount {
+    fn from(settlement_account: SettlementAccount) -> Self {
+        let account_type = match settlement_account.account_type {
+            SettlementAccountType::BurnAccount => "burn",
+            SettlementAccountType::HSSNAccount => "hssn",
+            SettlementAccountType::SonicGridAccount => "sonicGrid",
+            SettlementAccountType::GridAccount => "grid",
+        };
+        Self {
+            owner: settlement_account.owner.to_string(),
+            account_type: account_type.to_string(),
+            amount: settlement_account.amount.to_string(),
+            withdrawable: settlement_account.withdrawable.to_string(),
+            withdrawn: settlement_account.withdrawed.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSettlementCursor {
+    pub settler: String,
+    pub next_bill_id: StringAmount,
+    pub settled_bills: StringAmount,
+    pub settled_amount: StringAmount,
+    pub ranges: Vec<UiSettledRange>,
+}
+
+impl From<SettlementCursor> for UiSettlementCursor {
+    fn from(cursor: SettlementCursor) -> Self {
+        Self {
+            settler: cursor.settler.to_string(),
+            next_bill_id: cursor.next_bill_id.to_string(),
+            settled_bills: cursor.settled_bills.to_string(),
+            settled_amount: cursor.settled_amount.to_string(),
+            ranges: cursor.ranges.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSettledRange {
+    pub from_id: StringAmount,
+    pub end_id: StringAmount,
+    pub bill_count: StringAmount,
+    pub amount: StringAmount,
+    pub slot: Slot,
+}
+
+impl From<SettledRange> for UiSettledRange {
+    fn from(range: SettledRange) -> Self {
+        Self {
+            from_id: range.from_id.to_string(),
+            end_id: range.end_id.to_string(),
+            bill_count: range.bill_count.to_string(),
+            amount: range.amount.to_string(),
+            slot: range.slot,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiFeeMultiplierConfig {
+    pub authority: String,
+    pub multiplier: u32,
+    pub active_multiplier: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic: Option<UiDynamicFeeMultiplier>,
+}
+
+impl From<FeeMultiplierConfig> for UiFeeMultiplierConfig {
+    fn from(config: FeeMultiplierConfig) -> Self {
+        Self {
+            authority: config.authority.to_string(),
+            multiplier: config.multiplier,
+            active_multiplier: config.active_multiplier,
+            dynamic: config.dynamic.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiDynamicFeeMultiplier {
+    pub min_multiplier: u32,
+    pub max_multiplier: u32,
+    pub target_utilization_percent: u8,
+}
+
+impl From<DynamicFeeMultiplier> for UiDynamicFeeMultiplier {
+    fn from(dynamic: DynamicFeeMultiplier) -> Self {
+        Self {
+            min_multiplier: dynamic.min_multiplier,
+            max_multiplier: dynamic.max_multiplier,
+            target_utilization_percent: dynamic.target_utilization_percent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        bincode::serialize,
+        solana_sdk::{
+            pubkey::Pubkey,
+            sonic_fee_settlement::state::{SETTLEMENT_ACCOUNT_SIZE, SETTLEMENT_CURSOR_SIZE},
+        },
+    };
+
+    #[test]
+    fn test_parse_sonic_fee_settlement() {
+        let mut data = vec![0; SETTLEMENT_ACCOUNT_SIZE];
+        assert_eq!(
+            parse_sonic_fee_settlement(&data).unwrap(),
+            SonicFeeSettlementAccountType::Uninitialized
+        );
+
+        let owner = Pubkey::new_unique();
+        let state = SettlementState::FeeBillSettled(SettlementAccount {
+            owner,
+            account_type: SettlementAccountType::HSSNAccount,
+            amount: 42,
+            withdrawable: 40,
+            withdrawed: 2,
+        });
+        bincode::serialize_into(&mut data[..], &state).unwrap();
+        assert_eq!(
+            parse_sonic_fee_settlement(&data).unwrap(),
+            SonicFeeSettlementAccountType::SettlementAccount(UiSettlementAccount {
+                owner: owner.to_string(),
+                account_type: "hssn".to_string(),
+                amount: "42".to_string(),
+                withdrawable: "40".to_string(),
+                withdrawn: "2".to_string(),
+            })
+        );
+
+        let settler = Pubkey::new_unique();
+        let mut cursor = SettlementCursor::new(settler);
+        cursor
+            .record(SettledRange {
+                from_id: 0,
+                end_id: 9,
+                bill_count: 10,
+                amount: 1_000,
+                slot: 7,
+            })
+            .unwrap();
+        let mut data = vec![0; SETTLEMENT_CURSOR_SIZE];
+        bincode::serialize_into(&mut data[..], &SettlementState::SettlementCursor(cursor)).unwrap();
+        assert_eq!(
+            parse_sonic_fee_settlement(&data).unwrap(),
+            SonicFeeSettlementAccountType::SettlementCursor(UiSettlementCursor {
+                settler: settler.to_string(),
+                next_bill_id: "10".to_string(),
+                settled_bills: "10".to_string(),
+                settled_amount: "1000".to_string(),
+                ranges: vec![UiSettledRange {
+                    from_id: "0".to_string(),
+                    end_id: "9".to_string(),
+                    bill_count: "10".to_string(),
+                    amount: "1000".to_string(),
+                    slot: 7,
+                }],
+            })
+        );
+
+        let authority = Pubkey::new_unique();
+        let config = FeeMultiplierConfig::new(
+            authority,
+            20_000,
+            Some(DynamicFeeMultiplier {
+                min_multiplier: 10_000,
+                max_multiplier: 30_000,
+                target_utilization_percent: 50,
+            }),
+        );
+        let data = serialize(&SettlementState::FeeMultiplier(config)).unwrap();
+        assert_eq!(
+            parse_sonic_fee_settlement(&data).unwrap(),
+            SonicFeeSettlementAccountType::FeeMultiplier(UiFeeMultiplierConfig {
+                authority: authority.to_string(),
+                multiplier: 20_000,
+                active_multiplier: 20_000,
+                dynamic: Some(UiDynamicFeeMultiplier {
+                    min_multiplier: 10_000,
+                    max_multiplier: 30_000,
+                    target_utilization_percent: 50,
+                }),
+            })
+        );
+
+        assert!(parse_sonic_fee_settlement(&[4, 0, 0, 0]).is_err());
+        assert!(parse_sonic_fee_settlement(&[]).is_err());
+    }
+}
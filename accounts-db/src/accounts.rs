@@ -862,6 +862,7 @@ mod tests {
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                remote_accounts: vec![],
             },
             programs_modified_by_tx: Box::<LoadedProgramsForTxBatch>::default(),
         }
@@ -1,15 +1,16 @@
 use {
-    crate::{accounts_db::AccountsDb, accounts_hash::AccountHash, inline_spl_token, inline_spl_token_2022}, dashmap::DashMap, seqlock::SeqLock, solana_sdk::{
-        account::{Account, AccountSharedData, ReadableAccount},
+    crate::{accounts_db::AccountsDb, accounts_hash::AccountHash, synthetic_accounts::SyntheticAccounts}, dashmap::DashMap, log::*, seqlock::SeqLock, solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
         clock::Slot,
         pubkey::Pubkey,
-    }, sonic_hypergrid::remote_loader::RemoteAccountLoader, std::{
+    }, sonic_hypergrid::{error::RemoteLoadError, remote_loader::RemoteAccountLoader}, std::{
         collections::BTreeSet,
         ops::Deref,
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
             Arc, RwLock,
         },
+        time::Instant,
     }
 };
 
@@ -150,7 +151,7 @@ impl CachedAccountInner {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct AccountsCache {
     cache: DashMap<Slot, SlotCache>,
     // Queue of potentially unflushed roots. Random eviction + cache too large
@@ -159,6 +160,26 @@ pub struct AccountsCache {
     max_flushed_root: AtomicU64,
     total_size: Arc<AtomicU64>,
     pub remote_loader: RemoteAccountLoader, //Sonic: using RemoteAccountLoader
+    pub synthetic_accounts: SyntheticAccounts, //Sonic: accounts served with pinned content
+}
+
+impl Default for AccountsCache {
+    fn default() -> Self {
+        let remote_loader = RemoteAccountLoader::default();
+        let synthetic_accounts = SyntheticAccounts::new_from_config(remote_loader.config())
+            .unwrap_or_else(|err| {
+                error!("AccountsCache: {err}, serving the native mints");
+                SyntheticAccounts::native_mints()
+            });
+        Self {
+            cache: DashMap::default(),
+            maybe_unflushed_roots: RwLock::default(),
+            max_flushed_root: AtomicU64::default(),
+            total_size: Arc::default(),
+            remote_loader,
+            synthetic_accounts,
+        }
+    }
 }
 
 impl AccountsCache {
@@ -225,14 +246,19 @@ impl AccountsCache {
         //     .and_then(|slot_cache| slot_cache.get_cloned(pubkey))
         match self.slot_cache(slot)
             .and_then(|slot_cache| slot_cache.get_cloned(pubkey)) {
+            //Sonic: remote copies are served at the dummy slot 0, where the copy read before a
+            //refresh, or before a deactivation and a new migration, gives way to the current one
+            Some(account) if slot == 0 => match self.remote_loader.get_account(pubkey) {
+                Some(remote) if remote != account.account => Some(self.store(slot, pubkey, remote)),
+                _ => Some(account),
+            },
             Some(account) => {
                 Some(account)
             },
             None => {
-                if inline_spl_token::native_mint::id() == *pubkey || 
-                   inline_spl_token_2022::native_mint::id() == *pubkey {
-                    //Sonic: load native mint
-                    return Some(self.store(slot, pubkey, self.native_mint_account(pubkey)));
+                //Sonic: load synthetic account
+                if let Some(account) = self.synthetic_accounts.get(pubkey) {
+                    return Some(self.store(slot, pubkey, account));
                 }
                 //Sonic: load from remote
                 let account = self.remote_loader.get_account(pubkey);
@@ -248,39 +274,31 @@ impl AccountsCache {
         }
     }
 
-    fn native_mint_account(&self, pubkey: &Pubkey) -> AccountSharedData {
-        // Sonic: Add the native mint
-        let owner = if inline_spl_token::native_mint::id() == *pubkey {
-            inline_spl_token::id()
-        } else {
-            inline_spl_token_2022::id()
-        };
-        let mut native_mint_account: AccountSharedData = solana_sdk::account::AccountSharedData::from(Account {
-            owner, 
-            data: inline_spl_token::native_mint::ACCOUNT_DATA.to_vec(),
-            lamports: 1_461_600, //1_000_000_000,
-            executable: false,
-            rent_epoch: 18446744073709551615,
-        });
-        native_mint_account.remote = true;
+    //Sonic: check if account is served with pinned content
+    pub fn is_synthetic_account(&self, pubkey: &Pubkey) -> bool {
+        self.synthetic_accounts.contains(pubkey)
+    }
 
-        return native_mint_account;
+    //Sonic: keep the remote copy of the account read by the bank at `slot` until the slot is
+    //rooted
+    pub fn pin_remote_account(&self, pubkey: &Pubkey, slot: Slot) {
+        self.remote_loader.pin_account(pubkey, slot);
     }
 
     //Sonic: check if account exists in remote
     pub fn has_account_from_remote(&self, pubkey: &Pubkey) -> bool {
-        inline_spl_token::native_mint::id() == *pubkey || //native mint
-        inline_spl_token_2022::native_mint::id() == *pubkey || //native mint 2022
+        self.synthetic_accounts.is_remote(pubkey) ||
         self.remote_loader.has_account(pubkey)
     }
 
-    //Sonic: load accounts from remote
-    pub fn load_accounts_from_remote(&self, pubkeys: Vec<Pubkey>, source: Option<Pubkey>, refresh: bool) {
+    //Sonic: load accounts from remote, stops at the first account failing to load. Requests
+    //that cannot start before `deadline` fail.
+    pub fn load_accounts_from_remote(&self, pubkeys: Vec<Pubkey>, source: Option<Pubkey>, refresh: bool, deadline: Instant) -> Result<(), RemoteLoadError> {
         // println!("AccountsCache::load_accounts_from_remote, {:?}", pubkeys);
-        pubkeys.iter().for_each(|pubkey| {
+        pubkeys.iter().try_for_each(|pubkey| {
             //Sonic: load from remote
-            self.remote_loader.load_account(pubkey, source, refresh);
-        });
+            self.remote_loader.load_account_with_deadline(pubkey, source, refresh, deadline).map(|_| ())
+        })
     }
 
     //Sonic: load accounts from remote
@@ -301,6 +319,8 @@ impl AccountsCache {
     }
 
     pub fn add_root(&self, root: Slot) {
+        //Sonic: the remote accounts read by the rooted slots may now be evicted
+        self.remote_loader.unpin_accounts(root);
         let max_flushed_root = self.fetch_max_flush_root();
         if root > max_flushed_root || (root == max_flushed_root && root == 0) {
             self.maybe_unflushed_roots.write().unwrap().insert(root);
@@ -20,7 +20,6 @@
 
 #[cfg(feature = "dev-context-only-utils")]
 use qualifier_attr::qualifiers;
-use crate::inline_spl_token;
 
 use {
     crate::{
@@ -51,7 +50,7 @@ use {
         },
         accounts_index_storage::Startup,
         accounts_partition::RentPayingAccountsByPartition,
-        accounts_update_notifier_interface::AccountsUpdateNotifier,
+        accounts_update_notifier_interface::{AccountsUpdateNotifier, RemoteAccountUpdateNotifier},
         active_stats::{ActiveStatItem, ActiveStats},
         ancestors::Ancestors,
         ancient_append_vecs::{
@@ -2572,6 +2571,14 @@ impl AccountsDb {
             new.paths = paths;
             new.temp_paths = Some(temp_dirs);
         };
+        // Sonic: notify of the changes of the remote accounts as well
+        if let Some(accounts_update_notifier) = &new.accounts_update_notifier {
+            new.accounts_cache
+                .remote_loader
+                .set_notifier(Arc::new(RemoteAccountUpdateNotifier(
+                    accounts_update_notifier.clone(),
+                )));
+        }
 
         new.start_background_hasher();
         {
@@ -5068,8 +5075,15 @@ impl AccountsDb {
             // we bail out pretty early for missing.
             AccountIndexGetResult::NotFound => {
                 // Sonic: check if the pubkey is from remote in cache.
-                if ancestors.len() > 1 && self.accounts_cache.has_account_from_remote(pubkey) {
+                if ancestors.len() > 1
+                    && (self.accounts_cache.has_account_from_remote(pubkey)
+                        || self.accounts_cache.is_synthetic_account(pubkey))
+                {
                     // println!("******AccountsDb.read_index_for_accessor_or_load_slow: {:?} {}", std::thread::current().id(), pubkey.to_string());
+                    //Sonic: the remote copy is served at the dummy slot, so it is pinned by the
+                    //slot of the reading bank
+                    self.accounts_cache
+                        .pin_remote_account(pubkey, ancestors.max_slot());
                     return Some((0, StorageLocation::Cached, None)); //Sonic: return a dummy slot number
                 }
                 return None;
@@ -7722,14 +7736,11 @@ impl AccountsDb {
                 .map(|d| d.as_ref().unwrap().get_cache_hash_data())
                 .collect::<Vec<_>>();
 
-            //Sonic: calculate the total lamports of remote accounts
+            //Sonic: calculate the total lamports of remote accounts, the accounts the remote
+            //loader or the synthetic accounts serve as remote, and the ones missing from the index
             let mut lamports: u64 = 0;
             for chis in cache_hash_intermediates.clone() {
                 for item in chis {
-                    if item.pubkey.to_string().contains("11111111111111111") && 
-                       item.pubkey != inline_spl_token::native_mint::id() {
-                        continue;
-                    }
                     if self.accounts_cache.has_account_from_remote(&item.pubkey){
                         // println!("_calculate_accounts_hash_from_storages, remote key: {:?}", item);
                         lamports += item.lamports;
@@ -11217,6 +11228,50 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_remote_account_pinned_by_unrooted_reader() {
+        use sonic_hypergrid::{
+            config::Config, remote_loader::RemoteAccountLoader, remote_source::StaticRemoteSource,
+        };
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let remote_source = StaticRemoteSource::new();
+        remote_source.add_account(a, AccountSharedData::new(42, 0, &Pubkey::default()), None);
+        remote_source.add_account(b, AccountSharedData::new(43, 0, &Pubkey::default()), None);
+        let mut config = Config::default();
+        config.cache.max_accounts = 1;
+        let mut db = AccountsDb::new_single_for_tests();
+        db.accounts_cache.remote_loader = RemoteAccountLoader::new_with_config(config);
+        db.accounts_cache
+            .remote_loader
+            .set_remote_source(Arc::new(remote_source));
+        let migrate = |pubkey: Pubkey| {
+            let deadline = Instant::now() + Duration::from_secs(1);
+            db.accounts_cache
+                .load_accounts_from_remote(vec![pubkey], None, false, deadline)
+                .unwrap();
+        };
+
+        // `a` is read by the unrooted bank at slot 2, so migrating `b` does not evict it
+        let ancestors = linear_ancestors(3);
+        let lamports = |pubkey| {
+            db.load_with_fixed_root(&ancestors, pubkey)
+                .map(|(account, _)| account.lamports())
+        };
+        migrate(a);
+        assert_eq!(lamports(&a), Some(42));
+        migrate(b);
+        assert!(db.accounts_cache.has_account_from_remote(&a));
+        assert_eq!(lamports(&a), Some(42));
+
+        // Once the slot is rooted `a` is evicted, and reads find no account until it is
+        // migrated again
+        db.add_root(2);
+        assert!(!db.accounts_cache.has_account_from_remote(&a));
+        assert_eq!(lamports(&a), None);
+        migrate(a);
+        assert_eq!(lamports(&a), Some(42));
+    }
+
     #[test]
     fn test_accountsdb_latest_ancestor() {
         solana_logger::setup();
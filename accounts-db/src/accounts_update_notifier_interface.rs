@@ -1,8 +1,10 @@
+pub use sonic_hypergrid::remote_loader::{RemoteAccountEvent, RemoteAccountEventKind};
 use {
     crate::account_storage::meta::StoredAccountMeta,
     solana_sdk::{
         account::AccountSharedData, clock::Slot, pubkey::Pubkey, transaction::SanitizedTransaction,
     },
+    sonic_hypergrid::remote_loader::RemoteAccountNotifier,
     std::sync::Arc,
 };
 
@@ -23,6 +25,20 @@ pub trait AccountsUpdateNotifierInterface: std::fmt::Debug {
 
     /// Notified when all accounts have been notified when restoring from a snapshot.
     fn notify_end_of_restore_from_snapshot(&self);
+
+    /// Sonic: notified when a remote account is loaded into, refreshed in or
+    /// evicted from the remote account cache.
+    fn notify_remote_account(&self, _event: &RemoteAccountEvent) {}
 }
 
 pub type AccountsUpdateNotifier = Arc<dyn AccountsUpdateNotifierInterface + Sync + Send>;
+
+/// Sonic: relays the events of the remote account loader to an accounts update notifier.
+#[derive(Debug)]
+pub(crate) struct RemoteAccountUpdateNotifier(pub AccountsUpdateNotifier);
+
+impl RemoteAccountNotifier for RemoteAccountUpdateNotifier {
+    fn notify_remote_account(&self, event: &RemoteAccountEvent) {
+        self.0.notify_remote_account(event);
+    }
+}
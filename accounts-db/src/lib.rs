@@ -43,6 +43,7 @@ pub mod shared_buffer_reader;
 pub mod sorted_storages;
 pub mod stake_rewards;
 pub mod storable_accounts;
+pub mod synthetic_accounts;
 pub mod tiered_storage;
 pub mod transaction_error_metrics;
 pub mod transaction_results;
@@ -0,0 +1,180 @@
+//! Sonic: synthetic accounts, well-known accounts served with pinned content.
+//!
+//! The set is declared in the hypergrid configuration and defaults to the native mints of
+//! SPL Token and Token-2022. Genesis creates the synthetic accounts, and the accounts cache
+//! serves them to the chains whose genesis did not.
+
+use {
+    crate::{inline_spl_token, inline_spl_token_2022},
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_sdk::{
+        account::{AccountSharedData, WritableAccount},
+        native_token::sol_to_lamports,
+        pubkey::Pubkey,
+    },
+    sonic_hypergrid::config::{Config, SyntheticAccountConfig, SyntheticAccountOrigin},
+    std::{collections::HashMap, str::FromStr},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticAccount {
+    pub account: AccountSharedData,
+    pub origin: SyntheticAccountOrigin,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyntheticAccounts {
+    accounts: HashMap<Pubkey, SyntheticAccount>,
+}
+
+impl SyntheticAccounts {
+    /// The default set: the native mints of SPL Token and Token-2022, copies of the base layer
+    /// ones.
+    pub fn native_mints() -> Self {
+        let mut synthetic_accounts = Self::default();
+        for (mint, owner) in [
+            (inline_spl_token::native_mint::id(), inline_spl_token::id()),
+            (
+                inline_spl_token_2022::native_mint::id(),
+                inline_spl_token_2022::id(),
+            ),
+        ] {
+            synthetic_accounts.add(
+                mint,
+                AccountSharedData::create(
+                    sol_to_lamports(1.),
+                    inline_spl_token::native_mint::ACCOUNT_DATA.to_vec(),
+                    owner,
+                    false,
+                    u64::MAX,
+                ),
+                SyntheticAccountOrigin::Remote,
+            );
+        }
+        synthetic_accounts
+    }
+
+    /// The set declared in `config`, the native mints if it declares none.
+    pub fn new_from_config(config: &Config) -> Result<Self, String> {
+        match &config.synthetic_accounts {
+            Some(accounts) => accounts
+                .iter()
+                .try_fold(Self::default(), |mut set, config| {
+                    let (pubkey, account) = Self::account_from_config(config)?;
+                    set.add(pubkey, account, config.origin);
+                    Ok(set)
+                }),
+            None => Ok(Self::native_mints()),
+        }
+    }
+
+    /// The set declared in the hypergrid configuration file at its default path.
+    pub fn new_from_default_config() -> Result<Self, String> {
+        let config_path = Config::default_path();
+        match Config::load(&config_path) {
+            Ok(config) => Self::new_from_config(&config),
+            Err(_) => Ok(Self::native_mints()),
+        }
+    }
+
+    fn account_from_config(
+        config: &SyntheticAccountConfig,
+    ) -> Result<(Pubkey, AccountSharedData), String> {
+        let pubkey = Pubkey::from_str(&config.pubkey)
+            .map_err(|err| format!("invalid synthetic account {}: {err}", config.pubkey))?;
+        let owner = Pubkey::from_str(&config.owner)
+            .map_err(|err| format!("invalid owner of synthetic account {pubkey}: {err}"))?;
+        let data = BASE64_STANDARD
+            .decode(&config.data)
+            .map_err(|err| format!("invalid data of synthetic account {pubkey}: {err}"))?;
+        let account = AccountSharedData::create(
+            config.lamports,
+            data,
+            owner,
+            config.executable,
+            config.rent_epoch,
+        );
+        Ok((pubkey, account))
+    }
+
+    /// Serve `account` at `pubkey`, replacing any account declared there.
+    pub fn add(
+        &mut self,
+        pubkey: Pubkey,
+        account: AccountSharedData,
+        origin: SyntheticAccountOrigin,
+    ) {
+        self.accounts
+            .insert(pubkey, SyntheticAccount { account, origin });
+    }
+
+    /// The account served at `pubkey`, flagged as remote if it is declared so.
+    pub fn get(&self, pubkey: &Pubkey) -> Option<AccountSharedData> {
+        self.accounts.get(pubkey).map(|synthetic| {
+            let mut account = synthetic.account.clone();
+            account.remote = synthetic.origin == SyntheticAccountOrigin::Remote;
+            account
+        })
+    }
+
+    pub fn contains(&self, pubkey: &Pubkey) -> bool {
+        self.accounts.contains_key(pubkey)
+    }
+
+    /// Whether `pubkey` is a synthetic copy of a base layer account.
+    pub fn is_remote(&self, pubkey: &Pubkey) -> bool {
+        self.accounts
+            .get(pubkey)
+            .is_some_and(|synthetic| synthetic.origin == SyntheticAccountOrigin::Remote)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Pubkey, &SyntheticAccount)> {
+        self.accounts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::ReadableAccount};
+
+    #[test]
+    fn test_synthetic_accounts_from_config() {
+        let native_mint = inline_spl_token::native_mint::id();
+        let config = Config::default();
+        let synthetic_accounts = SyntheticAccounts::new_from_config(&config).unwrap();
+        assert_eq!(synthetic_accounts, SyntheticAccounts::native_mints());
+        assert!(synthetic_accounts.is_remote(&native_mint));
+        let account = synthetic_accounts.get(&native_mint).unwrap();
+        assert!(account.remote);
+        assert_eq!(account.owner(), &inline_spl_token::id());
+
+        // A declared set replaces the native mints
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let config = Config {
+            synthetic_accounts: Some(vec![SyntheticAccountConfig {
+                pubkey: pubkey.to_string(),
+                owner: owner.to_string(),
+                lamports: 42,
+                data: BASE64_STANDARD.encode([1, 2, 3]),
+                executable: false,
+                rent_epoch: u64::MAX,
+                origin: SyntheticAccountOrigin::Local,
+            }]),
+            ..Config::default()
+        };
+        let synthetic_accounts = SyntheticAccounts::new_from_config(&config).unwrap();
+        assert!(!synthetic_accounts.contains(&native_mint));
+        assert!(synthetic_accounts.contains(&pubkey));
+        assert!(!synthetic_accounts.is_remote(&pubkey));
+        let account = synthetic_accounts.get(&pubkey).unwrap();
+        assert!(!account.remote);
+        assert_eq!(account.lamports(), 42);
+        assert_eq!(account.data(), &[1, 2, 3]);
+        assert_eq!(account.owner(), &owner);
+
+        let mut config = config;
+        config.synthetic_accounts.as_mut().unwrap()[0].data = "not base64".to_string();
+        assert!(SyntheticAccounts::new_from_config(&config).is_err());
+    }
+}
@@ -12,9 +12,11 @@ use {
     solana_program_runtime::loaded_programs::LoadedProgramsForTxBatch,
     solana_sdk::{
         instruction::{CompiledInstruction, TRANSACTION_LEVEL_STACK_HEIGHT},
+        pubkey::Pubkey,
         transaction::{self, TransactionError},
         transaction_context::{TransactionContext, TransactionReturnData},
     },
+    sonic_hypergrid::remote_loader::RemoteOrigin,
 };
 
 pub type TransactionCheckResult = (transaction::Result<()>, Option<NoncePartial>);
@@ -85,6 +87,9 @@ pub struct TransactionExecutionDetails {
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
+    /// Sonic: the accounts of the transaction served from the remote cache, and where they
+    /// were loaded from.
+    pub remote_accounts: Vec<(Pubkey, RemoteOrigin)>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,4 +1,5 @@
 use {
+    crate::remote_accounts::RemoteAccountsConfig,
     clap::{crate_description, crate_name, App, Arg, ArgMatches},
     solana_clap_utils::{
         hidden_unless_forced,
@@ -80,6 +81,7 @@ pub struct Config {
     pub num_conflict_groups: Option<usize>,
     pub bind_address: IpAddr,
     pub client_node_id: Option<Keypair>,
+    pub remote_accounts_config: Option<RemoteAccountsConfig>,
 }
 
 impl Eq for Config {}
@@ -115,6 +117,7 @@ impl Default for Config {
             num_conflict_groups: None,
             bind_address: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
             client_node_id: None,
+            remote_accounts_config: None,
         }
     }
 }
@@ -396,6 +399,58 @@ pub fn build_args<'a>(version: &'_ str) -> App<'a, '_> {
                 .validator(is_keypair)
                 .help("File containing the node identity (keypair) of a validator with active stake. This allows communicating with network using staked connection"),
         )
+        .arg(
+            Arg::with_name("remote_accounts_source_url")
+                .long("remote-accounts-source-url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .conflicts_with_all(&["use_durable_nonce", "instruction_padding_data_size"])
+                .help("Benchmark transactions using remote accounts instead of transfers. \
+                       The accounts are created on the cluster at this URL, which the grid must migrate them from"),
+        )
+        .arg(
+            Arg::with_name("remote_accounts_source_node")
+                .long("remote-accounts-source-node")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .requires("remote_accounts_source_url")
+                .help("Hypergrid node the remote accounts are migrated from [default: identity of the source cluster RPC node]"),
+        )
+        .arg(
+            Arg::with_name("num_remote_accounts")
+                .long("num-remote-accounts")
+                .value_name("NUM")
+                .takes_value(true)
+                .requires("remote_accounts_source_url")
+                .validator(|arg| is_within_range(arg, 1..))
+                .help("Number of accounts created on the source cluster [default: 64]"),
+        )
+        .arg(
+            Arg::with_name("remote_account_data_size")
+                .long("remote-account-data-size")
+                .value_name("BYTES")
+                .takes_value(true)
+                .requires("remote_accounts_source_url")
+                .help("Data size of the accounts created on the source cluster [default: 128]"),
+        )
+        .arg(
+            Arg::with_name("remote_accounts_target_tps")
+                .long("remote-accounts-target-tps")
+                .value_name("TPS")
+                .takes_value(true)
+                .requires("remote_accounts_source_url")
+                .validator(|arg| is_within_range(arg, 1..))
+                .help("Rate remote account transactions are sent to the grid at [default: 1000]"),
+        )
+        .arg(
+            Arg::with_name("remote_accounts_mix")
+                .long("remote-accounts-mix")
+                .value_name("MIGRATE:REFRESH:READ_ONLY")
+                .takes_value(true)
+                .requires("remote_accounts_source_url")
+                .help("Relative weights of the migrate, refresh and read-only transactions sent [default: 1:1:8]"),
+        )
 }
 
 /// Parses a clap `ArgMatches` structure into a `Config`
@@ -577,6 +632,39 @@ pub fn parse_args(matches: &ArgMatches) -> Result<Config, &'static str> {
         args.client_node_id = Some(client_node_id);
     }
 
+    if let Some(source_rpc_url) = matches.value_of("remote_accounts_source_url") {
+        let mut remote_accounts_config = RemoteAccountsConfig {
+            source_rpc_url: source_rpc_url.to_string(),
+            ..RemoteAccountsConfig::default()
+        };
+        if let Some(source_node) = matches.value_of("remote_accounts_source_node") {
+            remote_accounts_config.source_node = Some(
+                source_node
+                    .parse()
+                    .map_err(|_| "can't parse remote-accounts-source-node")?,
+            );
+        }
+        if let Some(num_accounts) = matches.value_of("num_remote_accounts") {
+            remote_accounts_config.num_accounts = num_accounts
+                .parse()
+                .map_err(|_| "can't parse num-remote-accounts")?;
+        }
+        if let Some(data_size) = matches.value_of("remote_account_data_size") {
+            remote_accounts_config.account_data_size = data_size
+                .parse()
+                .map_err(|_| "can't parse remote-account-data-size")?;
+        }
+        if let Some(target_tps) = matches.value_of("remote_accounts_target_tps") {
+            remote_accounts_config.target_tps = target_tps
+                .parse()
+                .map_err(|_| "can't parse remote-accounts-target-tps")?;
+        }
+        if let Some(mix) = matches.value_of("remote_accounts_mix") {
+            remote_accounts_config.mix = mix.parse()?;
+        }
+        args.remote_accounts_config = Some(remote_accounts_config);
+    }
+
     Ok(args)
 }
 
@@ -584,6 +672,7 @@ pub fn parse_args(matches: &ArgMatches) -> Result<Config, &'static str> {
 mod tests {
     use {
         super::*,
+        crate::remote_accounts::RemoteTransactionMix,
         solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
         std::{
             net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -714,5 +803,44 @@ mod tests {
                 ..Config::default()
             }
         );
+
+        // with remote accounts
+        let keypair = read_keypair_file(&keypair_file_name).unwrap();
+        let matches = build_args("1.0.0").get_matches_from(vec![
+            "solana-bench-tps",
+            "--identity",
+            &keypair_file_name,
+            "-u",
+            "http://192.0.0.1:8899",
+            "--remote-accounts-source-url",
+            "http://192.0.0.2:8899",
+            "--num-remote-accounts",
+            "16",
+            "--remote-accounts-target-tps",
+            "200",
+            "--remote-accounts-mix",
+            "1:0:4",
+        ]);
+        let actual = parse_args(&matches).unwrap();
+        assert_eq!(
+            actual,
+            Config {
+                json_rpc_url: "http://192.0.0.1:8899".to_string(),
+                websocket_url: "ws://192.0.0.1:8900/".to_string(),
+                id: keypair,
+                remote_accounts_config: Some(RemoteAccountsConfig {
+                    source_rpc_url: "http://192.0.0.2:8899".to_string(),
+                    num_accounts: 16,
+                    target_tps: 200,
+                    mix: RemoteTransactionMix {
+                        migrate: 1,
+                        refresh: 0,
+                        read_only: 4,
+                    },
+                    ..RemoteAccountsConfig::default()
+                }),
+                ..Config::default()
+            }
+        );
     }
 }
@@ -4,4 +4,5 @@ pub mod bench_tps_client;
 pub mod cli;
 pub mod keypairs;
 mod perf_utils;
+pub mod remote_accounts;
 pub mod send_batch;
@@ -8,6 +8,7 @@ use {
         bench_tps_client::BenchTpsClient,
         cli::{self, ExternalClientType},
         keypairs::get_keypairs,
+        remote_accounts::do_bench_remote_accounts,
         send_batch::{generate_durable_nonce_accounts, generate_keypairs},
     },
     solana_client::{
@@ -257,6 +258,7 @@ fn main() {
         instruction_padding_config,
         bind_address,
         client_node_id,
+        remote_accounts_config,
         ..
     } = &cli_config;
 
@@ -330,6 +332,32 @@ fn main() {
         *target_node,
         connection_cache,
     );
+    if let Some(remote_accounts_config) = remote_accounts_config {
+        let payers = get_keypairs(
+            client.clone(),
+            id,
+            *tx_count,
+            *num_lamports_per_account,
+            client_ids_and_stake_file,
+            *read_from_client_file,
+            false,
+        );
+        let rpc_client = Arc::new(RpcClient::new_with_commitment(
+            json_rpc_url.to_string(),
+            CommitmentConfig::confirmed(),
+        ));
+        if let Err(err) = do_bench_remote_accounts(
+            client,
+            rpc_client,
+            &cli_config,
+            remote_accounts_config,
+            &payers,
+        ) {
+            eprintln!("Remote accounts benchmark failed: {err}");
+            exit(1);
+        }
+        return;
+    }
     if let Some(instruction_padding_config) = instruction_padding_config {
         info!(
             "Checking for existence of instruction padding program: {}",
@@ -0,0 +1,616 @@
+//! Sonic: benchmark of grid transactions depending on remote accounts.
+//!
+//! Accounts are first created on a source cluster, acting as the base layer. Migrate, refresh
+//! and read-only transactions using them are then sent to the grid at a target rate, and the
+//! TPS, confirmation latency and number of remote accounts used by each kind of transaction
+//! are reported. The remote accounts used are the ones recorded in the status meta, whether the
+//! grid fetched them or served them from its cache.
+
+use {
+    crate::{
+        bench_tps_client::{BenchTpsClient, BenchTpsError, Result},
+        cli::Config,
+    },
+    log::*,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcBlockConfig,
+    solana_sdk::{
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        hash::Hash,
+        instruction::AccountMeta,
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        sonic_account_migrater::instruction::migrate_source_accounts,
+        system_instruction, system_program,
+        transaction::Transaction,
+    },
+    solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
+    std::{
+        collections::{HashMap, HashSet},
+        fmt,
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::{sleep, Builder},
+        time::{Duration, Instant},
+    },
+};
+
+/// Number of accounts created on the source cluster per transaction
+const CREATE_ACCOUNTS_CHUNK_SIZE: usize = 4;
+/// Lamports kept on the source cluster for the fees of the account creations
+const SOURCE_FEES_LAMPORTS: u64 = LAMPORTS_PER_SOL / 100;
+/// Maximum number of signatures queried per `getSignatureStatuses` request
+const MAX_SIGNATURE_STATUSES: usize = 256;
+/// Transactions are sent in batches this often
+const SEND_BATCH_PERIOD: Duration = Duration::from_millis(100);
+/// Period the blockhash of the sent transactions is refreshed at
+const BLOCKHASH_REFRESH_PERIOD: Duration = Duration::from_secs(1);
+/// Period the statuses of the sent transactions are polled at
+const CONFIRMATION_POLL_PERIOD: Duration = Duration::from_millis(200);
+/// Time left to the transactions to be confirmed once all of them are sent
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RemoteTransactionKind {
+    /// Migrate a remote account from the source
+    Migrate,
+    /// Reload a migrated remote account from the source
+    Refresh,
+    /// Use a migrated remote account as a read-only account of a transfer
+    ReadOnly,
+}
+
+impl RemoteTransactionKind {
+    const ALL: [Self; 3] = [Self::Migrate, Self::Refresh, Self::ReadOnly];
+}
+
+impl fmt::Display for RemoteTransactionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Migrate => "migrate",
+            Self::Refresh => "refresh",
+            Self::ReadOnly => "read-only",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Relative weights of the kinds of transactions sent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RemoteTransactionMix {
+    pub migrate: u64,
+    pub refresh: u64,
+    pub read_only: u64,
+}
+
+impl Default for RemoteTransactionMix {
+    fn default() -> Self {
+        Self {
+            migrate: 1,
+            refresh: 1,
+            read_only: 8,
+        }
+    }
+}
+
+impl FromStr for RemoteTransactionMix {
+    type Err = &'static str;
+
+    /// Parse `MIGRATE:REFRESH:READ_ONLY` weights
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let weights = s
+            .split(':')
+            .map(|weight| weight.parse::<u64>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| "can't parse remote-accounts-mix")?;
+        let &[migrate, refresh, read_only] = weights.as_slice() else {
+            return Err("remote-accounts-mix must have 3 weights");
+        };
+        if migrate == 0 {
+            return Err("remote-accounts-mix must migrate accounts");
+        }
+        Ok(Self {
+            migrate,
+            refresh,
+            read_only,
+        })
+    }
+}
+
+impl RemoteTransactionMix {
+    /// The kind of the `index`th transaction sent, interleaving the kinds in their proportions
+    fn kind(&self, index: u64) -> RemoteTransactionKind {
+        let position = index % (self.migrate + self.refresh + self.read_only);
+        if position < self.migrate {
+            RemoteTransactionKind::Migrate
+        } else if position < self.migrate + self.refresh {
+            RemoteTransactionKind::Refresh
+        } else {
+            RemoteTransactionKind::ReadOnly
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RemoteAccountsConfig {
+    /// RPC URL of the cluster the remote accounts are created on
+    pub source_rpc_url: String,
+    /// Hypergrid node the accounts are migrated from, the identity of the source cluster RPC
+    /// node if not set
+    pub source_node: Option<Pubkey>,
+    pub num_accounts: usize,
+    pub account_data_size: usize,
+    /// Transactions sent to the grid per second
+    pub target_tps: u64,
+    pub mix: RemoteTransactionMix,
+}
+
+impl Default for RemoteAccountsConfig {
+    fn default() -> Self {
+        Self {
+            source_rpc_url: String::new(),
+            source_node: None,
+            num_accounts: 64,
+            account_data_size: 128,
+            target_tps: 1_000,
+            mix: RemoteTransactionMix::default(),
+        }
+    }
+}
+
+/// Results of the transactions of a kind
+#[derive(Debug, Default, Clone)]
+pub struct RemoteTransactionStats {
+    pub sent: usize,
+    pub confirmed: usize,
+    pub failed: usize,
+    /// Confirmation latencies of the confirmed transactions
+    pub latencies: Vec<Duration>,
+    /// Remote accounts used by the confirmed transactions, as recorded in their status meta
+    pub remote_accounts_used: usize,
+}
+
+impl RemoteTransactionStats {
+    fn latency_percentile(&self, percentile: usize) -> Duration {
+        let mut latencies = self.latencies.clone();
+        latencies.sort_unstable();
+        latencies
+            .get((latencies.len() * percentile / 100).min(latencies.len().saturating_sub(1)))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RemoteAccountsStats {
+    pub kinds: HashMap<RemoteTransactionKind, RemoteTransactionStats>,
+    pub elapsed: Duration,
+}
+
+impl RemoteAccountsStats {
+    pub fn confirmed(&self) -> usize {
+        self.kinds.values().map(|stats| stats.confirmed).sum()
+    }
+
+    pub fn remote_accounts_used(&self) -> usize {
+        self.kinds
+            .values()
+            .map(|stats| stats.remote_accounts_used)
+            .sum()
+    }
+
+    pub fn tps(&self) -> f64 {
+        self.confirmed() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// A transaction waiting to be confirmed
+struct PendingTransaction {
+    kind: RemoteTransactionKind,
+    sent: Instant,
+}
+
+/// Create `num_accounts` system accounts with `data_size` bytes of data on the source cluster,
+/// funded by `funding_key`
+pub fn create_source_accounts(
+    source_client: &RpcClient,
+    funding_key: &Keypair,
+    num_accounts: usize,
+    data_size: usize,
+) -> Result<Vec<Pubkey>> {
+    let lamports = source_client.get_minimum_balance_for_rent_exemption(data_size)?;
+    let total = lamports * num_accounts as u64 + SOURCE_FEES_LAMPORTS;
+    let balance = source_client.get_balance(&funding_key.pubkey())?;
+    if balance < total {
+        info!("Requesting an airdrop on the source cluster...");
+        let signature = source_client
+            .request_airdrop(&funding_key.pubkey(), total - balance)
+            .map_err(|_| BenchTpsError::AirdropFailure)?;
+        source_client
+            .poll_for_signature(&signature)
+            .map_err(|_| BenchTpsError::AirdropFailure)?;
+    }
+
+    info!(
+        "Creating {} accounts on the source cluster...",
+        num_accounts
+    );
+    let keypairs: Vec<Keypair> = (0..num_accounts).map(|_| Keypair::new()).collect();
+    for chunk in keypairs.chunks(CREATE_ACCOUNTS_CHUNK_SIZE) {
+        let instructions: Vec<_> = chunk
+            .iter()
+            .map(|keypair| {
+                system_instruction::create_account(
+                    &funding_key.pubkey(),
+                    &keypair.pubkey(),
+                    lamports,
+                    data_size as u64,
+                    &system_program::id(),
+                )
+            })
+            .collect();
+        let mut signers: Vec<&Keypair> = vec![funding_key];
+        signers.extend(chunk);
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&funding_key.pubkey()),
+            &signers,
+            source_client.get_latest_blockhash()?,
+        );
+        source_client.send_and_confirm_transaction(&transaction)?;
+    }
+    Ok(keypairs.iter().map(|keypair| keypair.pubkey()).collect())
+}
+
+fn remote_account_transaction(
+    kind: RemoteTransactionKind,
+    payer: &Keypair,
+    source_node: Pubkey,
+    account: Pubkey,
+    blockhash: Hash,
+) -> Transaction {
+    let instruction = match kind {
+        RemoteTransactionKind::Migrate => {
+            migrate_source_accounts(payer.pubkey(), source_node, false, vec![account])
+        }
+        RemoteTransactionKind::Refresh => {
+            migrate_source_accounts(payer.pubkey(), source_node, true, vec![account])
+        }
+        RemoteTransactionKind::ReadOnly => {
+            // The system program ignores the accounts following the ones of the transfer
+            let mut instruction = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+            instruction
+                .accounts
+                .push(AccountMeta::new_readonly(account, false));
+            instruction
+        }
+    };
+    Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[payer], blockhash)
+}
+
+/// Poll the statuses of the pending transactions until `exit` is set and none are left, or
+/// the confirmation timeout elapsed
+fn confirm_transactions(
+    rpc_client: &RpcClient,
+    pending: &Mutex<HashMap<Signature, PendingTransaction>>,
+    stats: &Mutex<RemoteAccountsStats>,
+    confirmed_slots: &Mutex<HashMap<Slot, Vec<(Signature, RemoteTransactionKind)>>>,
+    exit: &AtomicBool,
+) {
+    let mut deadline = None;
+    loop {
+        if exit.load(Ordering::Relaxed) {
+            let deadline = *deadline.get_or_insert_with(|| Instant::now() + CONFIRMATION_TIMEOUT);
+            if pending.lock().unwrap().is_empty() || Instant::now() > deadline {
+                break;
+            }
+        }
+        sleep(CONFIRMATION_POLL_PERIOD);
+
+        let signatures: Vec<Signature> = pending.lock().unwrap().keys().copied().collect();
+        for signatures in signatures.chunks(MAX_SIGNATURE_STATUSES) {
+            let statuses = match rpc_client.get_signature_statuses(signatures) {
+                Ok(statuses) => statuses.value,
+                Err(err) => {
+                    info!("Couldn't get signature statuses: {:?}", err);
+                    continue;
+                }
+            };
+            let now = Instant::now();
+            let mut pending = pending.lock().unwrap();
+            let mut stats = stats.lock().unwrap();
+            for (signature, status) in signatures.iter().zip(statuses) {
+                let Some(status) = status else {
+                    continue;
+                };
+                if status.err.is_none()
+                    && !status.satisfies_commitment(CommitmentConfig::confirmed())
+                {
+                    continue;
+                }
+                let Some(transaction) = pending.remove(signature) else {
+                    continue;
+                };
+                let kind_stats = stats.kinds.entry(transaction.kind).or_default();
+                if let Some(err) = status.err {
+                    debug!(
+                        "{} transaction {} failed: {}",
+                        transaction.kind, signature, err
+                    );
+                    kind_stats.failed += 1;
+                } else {
+                    kind_stats.confirmed += 1;
+                    kind_stats
+                        .latencies
+                        .push(now.duration_since(transaction.sent));
+                    confirmed_slots
+                        .lock()
+                        .unwrap()
+                        .entry(status.slot)
+                        .or_default()
+                        .push((*signature, transaction.kind));
+                }
+            }
+        }
+    }
+}
+
+/// Count the remote accounts used by the confirmed transactions, as recorded in their status
+/// meta
+fn count_remote_accounts_used(
+    rpc_client: &RpcClient,
+    confirmed_slots: HashMap<Slot, Vec<(Signature, RemoteTransactionKind)>>,
+    stats: &mut RemoteAccountsStats,
+) {
+    for (slot, transactions) in confirmed_slots {
+        let block = match rpc_client.get_block_with_config(
+            slot,
+            RpcBlockConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                transaction_details: Some(TransactionDetails::Full),
+                rewards: Some(false),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        ) {
+            Ok(block) => block,
+            Err(err) => {
+                info!("Couldn't get block {}: {:?}", slot, err);
+                continue;
+            }
+        };
+        let kinds: HashMap<String, RemoteTransactionKind> = transactions
+            .into_iter()
+            .map(|(signature, kind)| (signature.to_string(), kind))
+            .collect();
+        for transaction in block.transactions.unwrap_or_default() {
+            let Some(signature) = transaction
+                .transaction
+                .decode()
+                .and_then(|transaction| transaction.signatures.first().copied())
+            else {
+                continue;
+            };
+            let Some(kind) = kinds.get(&signature.to_string()) else {
+                continue;
+            };
+            let remote_accounts = transaction
+                .meta
+                .and_then(|meta| Option::<Vec<_>>::from(meta.remote_accounts))
+                .map(|remote_accounts| remote_accounts.len())
+                .unwrap_or_default();
+            stats.kinds.entry(*kind).or_default().remote_accounts_used += remote_accounts;
+        }
+    }
+}
+
+/// Send the mix of remote account transactions of `remote_config` to the grid for the
+/// duration of `config`, paid by `payers`, and report their results
+pub fn do_bench_remote_accounts<T>(
+    client: Arc<T>,
+    rpc_client: Arc<RpcClient>,
+    config: &Config,
+    remote_config: &RemoteAccountsConfig,
+    payers: &[Keypair],
+) -> Result<RemoteAccountsStats>
+where
+    T: 'static + BenchTpsClient + Send + Sync + ?Sized,
+{
+    assert!(!payers.is_empty());
+    let source_client = RpcClient::new_with_commitment(
+        remote_config.source_rpc_url.clone(),
+        CommitmentConfig::confirmed(),
+    );
+    let source_node = match remote_config.source_node {
+        Some(source_node) => source_node,
+        None => source_client.get_identity()?,
+    };
+    info!("Migrating remote accounts from {}", source_node);
+    let accounts = create_source_accounts(
+        &source_client,
+        &config.id,
+        remote_config.num_accounts,
+        remote_config.account_data_size,
+    )?;
+
+    let pending = Arc::new(Mutex::new(HashMap::new()));
+    let stats = Arc::new(Mutex::new(RemoteAccountsStats::default()));
+    let confirmed_slots = Arc::new(Mutex::new(HashMap::new()));
+    let exit = Arc::new(AtomicBool::new(false));
+    let confirmation_thread = {
+        let rpc_client = rpc_client.clone();
+        let pending = pending.clone();
+        let stats = stats.clone();
+        let confirmed_slots = confirmed_slots.clone();
+        let exit = exit.clone();
+        Builder::new()
+            .name("solBenchRemConf".to_string())
+            .spawn(move || {
+                confirm_transactions(&rpc_client, &pending, &stats, &confirmed_slots, &exit)
+            })
+            .unwrap()
+    };
+
+    let batch_size = (remote_config.target_tps as f64 * SEND_BATCH_PERIOD.as_secs_f64())
+        .ceil()
+        .max(1.0) as u64;
+    // Read-only transactions only use the accounts a migrate transaction was sent for
+    let mut migrated = HashSet::new();
+    let mut sent_count = 0u64;
+    let mut blockhash = client.get_latest_blockhash()?;
+    let mut blockhash_time = Instant::now();
+    let start = Instant::now();
+    while start.elapsed() < config.duration {
+        let batch_start = Instant::now();
+        if blockhash_time.elapsed() > BLOCKHASH_REFRESH_PERIOD {
+            match client.get_latest_blockhash() {
+                Ok(new_blockhash) => {
+                    blockhash = new_blockhash;
+                    blockhash_time = Instant::now();
+                }
+                Err(err) => info!("Couldn't get new blockhash: {:?}", err),
+            }
+        }
+
+        let mut transactions = Vec::with_capacity(batch_size as usize);
+        let mut kinds = Vec::with_capacity(batch_size as usize);
+        for index in sent_count..sent_count + batch_size {
+            // Spread the payers over the accounts so that no transaction repeats for a blockhash
+            let payer = &payers[index as usize % payers.len()];
+            let account = accounts[(index as usize / payers.len()) % accounts.len()];
+            let kind = match remote_config.mix.kind(index) {
+                RemoteTransactionKind::Migrate => {
+                    migrated.insert(account);
+                    RemoteTransactionKind::Migrate
+                }
+                kind if migrated.contains(&account) => kind,
+                _ => {
+                    migrated.insert(account);
+                    RemoteTransactionKind::Migrate
+                }
+            };
+            transactions.push(remote_account_transaction(
+                kind,
+                payer,
+                source_node,
+                account,
+                blockhash,
+            ));
+            kinds.push(kind);
+        }
+        sent_count += batch_size;
+
+        {
+            let now = Instant::now();
+            let mut pending = pending.lock().unwrap();
+            let mut stats = stats.lock().unwrap();
+            for (transaction, kind) in transactions.iter().zip(kinds) {
+                pending.insert(
+                    transaction.signatures[0],
+                    PendingTransaction { kind, sent: now },
+                );
+                stats.kinds.entry(kind).or_default().sent += 1;
+            }
+        }
+        if let Err(err) = client.send_batch(transactions) {
+            warn!("Couldn't send transactions: {:?}", err);
+        }
+
+        if let Some(remaining) = SEND_BATCH_PERIOD.checked_sub(batch_start.elapsed()) {
+            sleep(remaining);
+        }
+    }
+    let elapsed = start.elapsed();
+
+    info!("Waiting for the transactions to be confirmed...");
+    exit.store(true, Ordering::Relaxed);
+    confirmation_thread.join().unwrap();
+
+    let mut stats = Arc::try_unwrap(stats).unwrap().into_inner().unwrap();
+    stats.elapsed = elapsed;
+    let confirmed_slots = Arc::try_unwrap(confirmed_slots)
+        .unwrap()
+        .into_inner()
+        .unwrap();
+    count_remote_accounts_used(&rpc_client, confirmed_slots, &mut stats);
+    report_stats(&stats);
+    Ok(stats)
+}
+
+fn report_stats(stats: &RemoteAccountsStats) {
+    info!(
+        " Transactions |   Sent | Confirmed | Failed | Latency p50/p90/max (ms) | Remote accounts used"
+    );
+    info!(
+        "--------------+--------+-----------+--------+--------------------------+---------------------"
+    );
+    for kind in RemoteTransactionKind::ALL {
+        let Some(kind_stats) = stats.kinds.get(&kind) else {
+            continue;
+        };
+        info!(
+            " {:12} | {:6} | {:9} | {:6} | {:>24} | {}",
+            kind.to_string(),
+            kind_stats.sent,
+            kind_stats.confirmed,
+            kind_stats.failed,
+            format!(
+                "{}/{}/{}",
+                kind_stats.latency_percentile(50).as_millis(),
+                kind_stats.latency_percentile(90).as_millis(),
+                kind_stats.latency_percentile(100).as_millis(),
+            ),
+            kind_stats.remote_accounts_used,
+        );
+    }
+    info!(
+        "\nConfirmed TPS: {:.2} over {:.2}s, remote accounts used: {}",
+        stats.tps(),
+        stats.elapsed.as_secs_f64(),
+        stats.remote_accounts_used(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_transaction_mix() {
+        assert_eq!(
+            "1:2:3".parse::<RemoteTransactionMix>(),
+            Ok(RemoteTransactionMix {
+                migrate: 1,
+                refresh: 2,
+                read_only: 3,
+            })
+        );
+        assert!("1:2".parse::<RemoteTransactionMix>().is_err());
+        assert!("0:1:1".parse::<RemoteTransactionMix>().is_err());
+        assert!("1:a:1".parse::<RemoteTransactionMix>().is_err());
+
+        let mix = RemoteTransactionMix {
+            migrate: 1,
+            refresh: 2,
+            read_only: 3,
+        };
+        let kinds: Vec<_> = (0..7).map(|index| mix.kind(index)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RemoteTransactionKind::Migrate,
+                RemoteTransactionKind::Refresh,
+                RemoteTransactionKind::Refresh,
+                RemoteTransactionKind::ReadOnly,
+                RemoteTransactionKind::ReadOnly,
+                RemoteTransactionKind::ReadOnly,
+                RemoteTransactionKind::Migrate,
+            ]
+        );
+    }
+}
@@ -5,6 +5,7 @@ use {
     solana_bench_tps::{
         bench::{do_bench_tps, generate_and_fund_keypairs},
         cli::{Config, InstructionPaddingConfig},
+        remote_accounts::{do_bench_remote_accounts, RemoteAccountsConfig, RemoteTransactionKind},
         send_batch::generate_durable_nonce_accounts,
     },
     solana_client::{
@@ -28,6 +29,7 @@ use {
     },
     solana_streamer::socket::SocketAddrSpace,
     solana_test_validator::TestValidatorGenesis,
+    sonic_hypergrid::remote_source::RpcRemoteSource,
     std::{sync::Arc, time::Duration},
 };
 
@@ -214,3 +216,72 @@ fn test_bench_tps_tpu_client_with_padding() {
         ..Config::default()
     });
 }
+
+#[test]
+#[serial]
+fn test_bench_tps_remote_accounts() {
+    solana_logger::setup();
+
+    let start_validator = |genesis: &mut TestValidatorGenesis| {
+        let mint_keypair = Keypair::new();
+        let mint_pubkey = mint_keypair.pubkey();
+        let faucet_addr = run_local_faucet(mint_keypair, None);
+        genesis
+            .fee_rate_governor(FeeRateGovernor::new(0, 0))
+            .faucet_addr(Some(faucet_addr))
+            .start_with_mint_address(mint_pubkey, SocketAddrSpace::Unspecified)
+            .expect("validator start failed")
+    };
+
+    // The source cluster acts as the base layer the grid migrates the accounts from
+    let source_validator = start_validator(&mut TestValidatorGenesis::default());
+    let grid_validator = start_validator(
+        TestValidatorGenesis::default()
+            .remote_source(Arc::new(RpcRemoteSource::new(source_validator.rpc_url()))),
+    );
+
+    let rpc_client = Arc::new(RpcClient::new_with_commitment(
+        grid_validator.rpc_url(),
+        CommitmentConfig::processed(),
+    ));
+    let client = Arc::new(
+        TpuClient::new(
+            rpc_client,
+            &grid_validator.rpc_pubsub_url(),
+            TpuClientConfig::default(),
+        )
+        .unwrap(),
+    );
+
+    let config = Config {
+        tx_count: 16,
+        duration: Duration::from_secs(10),
+        ..Config::default()
+    };
+    let remote_accounts_config = RemoteAccountsConfig {
+        source_rpc_url: source_validator.rpc_url(),
+        num_accounts: 8,
+        target_tps: 50,
+        ..RemoteAccountsConfig::default()
+    };
+    let payers =
+        generate_and_fund_keypairs(client.clone(), &config.id, config.tx_count, 1000, false)
+            .unwrap();
+
+    let stats = do_bench_remote_accounts(
+        client,
+        Arc::new(RpcClient::new_with_commitment(
+            grid_validator.rpc_url(),
+            CommitmentConfig::confirmed(),
+        )),
+        &config,
+        &remote_accounts_config,
+        &payers,
+    )
+    .unwrap();
+
+    assert!(stats.confirmed() > 0);
+    let migrate_stats = &stats.kinds[&RemoteTransactionKind::Migrate];
+    assert!(migrate_stats.confirmed > 0);
+    assert!(migrate_stats.remote_accounts_used > 0);
+}
@@ -2474,6 +2474,158 @@ impl fmt::Display for CliAddressLookupTableCreated {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHypergridNode {
+    pub pubkey: String,
+    pub name: String,
+    pub rpc: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliHypergridNodes {
+    pub nodes: Vec<CliHypergridNode>,
+}
+impl QuietDisplay for CliHypergridNodes {}
+impl VerboseDisplay for CliHypergridNodes {}
+impl fmt::Display for CliHypergridNodes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.nodes.is_empty() {
+            return writeln!(f, "No hypergrid nodes registered");
+        }
+        writeln!(
+            f,
+            "{}",
+            style(format!(
+                "{:<44}  {:<10}  {:<16}  {}",
+                "Node", "Role", "Name", "RPC"
+            ))
+            .bold()
+        )?;
+        for node in &self.nodes {
+            writeln!(
+                f,
+                "{:<44}  {:<10}  {:<16}  {}",
+                node.pubkey, node.role, node.name, node.rpc
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliRemoteAccountStatus {
+    pub address: String,
+    /// Whether the account exists on the grid
+    pub exists: bool,
+    /// Whether the grid account was migrated from a remote source
+    pub remote: bool,
+    pub lamports: Option<u64>,
+    pub owner: Option<String>,
+    /// Whether HSSN holds a record of the account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hssn_recorded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hssn_lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hssn_owner: Option<String>,
+}
+impl QuietDisplay for CliRemoteAccountStatus {}
+impl VerboseDisplay for CliRemoteAccountStatus {}
+impl fmt::Display for CliRemoteAccountStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Address:", &self.address)?;
+        if self.exists {
+            let origin = if self.remote { "Remote" } else { "Local" };
+            writeln_name_value(f, "Origin:", origin)?;
+        } else {
+            writeln_name_value(f, "Origin:", "None (not on this grid)")?;
+        }
+        if let Some(lamports) = self.lamports {
+            writeln_name_value(f, "Balance:", &build_balance_message(lamports, false, true))?;
+        }
+        if let Some(owner) = &self.owner {
+            writeln_name_value(f, "Owner:", owner)?;
+        }
+        if let Some(hssn_recorded) = self.hssn_recorded {
+            writeln_name_value(
+                f,
+                "HSSN Record:",
+                if hssn_recorded { "Yes" } else { "None" },
+            )?;
+        }
+        if let Some(hssn_lamports) = self.hssn_lamports {
+            writeln_name_value(
+                f,
+                "HSSN Balance:",
+                &build_balance_message(hssn_lamports, false, true),
+            )?;
+        }
+        if let Some(hssn_owner) = &self.hssn_owner {
+            writeln_name_value(f, "HSSN Owner:", hssn_owner)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSettlementAccount {
+    pub address: String,
+    pub owner: String,
+    pub account_type: String,
+    pub amount: u64,
+    pub withdrawable: u64,
+    pub withdrawn: u64,
+}
+impl QuietDisplay for CliSettlementAccount {}
+impl VerboseDisplay for CliSettlementAccount {}
+impl fmt::Display for CliSettlementAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Settlement Account:", &self.address)?;
+        writeln_name_value(f, "Owner:", &self.owner)?;
+        writeln_name_value(f, "Account Type:", &self.account_type)?;
+        writeln_name_value(
+            f,
+            "Settled:",
+            &build_balance_message(self.amount, false, true),
+        )?;
+        writeln_name_value(
+            f,
+            "Withdrawable:",
+            &build_balance_message(self.withdrawable, false, true),
+        )?;
+        writeln_name_value(
+            f,
+            "Withdrawn:",
+            &build_balance_message(self.withdrawn, false, true),
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSettlementAccountCreated {
+    pub settlement_address: String,
+    pub signature: String,
+}
+impl QuietDisplay for CliSettlementAccountCreated {}
+impl VerboseDisplay for CliSettlementAccountCreated {}
+impl fmt::Display for CliSettlementAccountCreated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Signature:", &self.signature)?;
+        writeln_name_value(f, "Settlement Account:", &self.settlement_address)?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct ReturnSignersConfig {
     pub dump_transaction_message: bool,
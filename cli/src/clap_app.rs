@@ -1,8 +1,8 @@
 use {
     crate::{
         address_lookup_table::AddressLookupTableSubCommands, cli::*, cluster_query::*, feature::*,
-        inflation::*, nonce::*, program::*, program_v4::ProgramV4SubCommands, stake::*,
-        validator_info::*, vote::*, wallet::*,
+        hypergrid::HypergridSubCommands, inflation::*, nonce::*, program::*,
+        program_v4::ProgramV4SubCommands, stake::*, validator_info::*, vote::*, wallet::*,
     },
     clap::{App, AppSettings, Arg, ArgGroup, SubCommand},
     solana_clap_utils::{self, hidden_unless_forced, input_validators::*, keypair::*},
@@ -149,6 +149,7 @@ pub fn get_clap_app<'ab, 'v>(name: &str, about: &'ab str, version: &'v str) -> A
         .program_subcommands()
         .program_v4_subcommands()
         .address_lookup_table_subcommands()
+        .hypergrid_subcommands()
         .stake_subcommands()
         .validator_info_subcommands()
         .vote_subcommands()
@@ -1,7 +1,8 @@
 use {
     crate::{
-        address_lookup_table::*, clap_app::*, cluster_query::*, feature::*, inflation::*, nonce::*,
-        program::*, program_v4::*, spend_utils::*, stake::*, validator_info::*, vote::*, wallet::*,
+        address_lookup_table::*, clap_app::*, cluster_query::*, feature::*, hypergrid::*,
+        inflation::*, nonce::*, program::*, program_v4::*, spend_utils::*, stake::*,
+        validator_info::*, vote::*, wallet::*,
     },
     clap::{crate_description, crate_name, value_t_or_exit, ArgMatches, Shell},
     log::*,
@@ -445,6 +446,8 @@ pub enum CliCommand {
     },
     // Address lookup table commands
     AddressLookupTable(AddressLookupTableCliCommand),
+    // Hypergrid Commands
+    Hypergrid(HypergridCliCommand),
     SignOffchainMessage {
         message: OffchainMessage,
     },
@@ -697,6 +700,9 @@ pub fn parse_command(
         ("address-lookup-table", Some(matches)) => {
             parse_address_lookup_table_subcommand(matches, default_signer, wallet_manager)
         }
+        ("hypergrid", Some(matches)) => {
+            parse_hypergrid_subcommand(matches, default_signer, wallet_manager)
+        }
         ("wait-for-max-stake", Some(matches)) => {
             let max_stake_percent = value_t_or_exit!(matches, "max_percent", f32);
             Ok(CliCommandInfo {
@@ -1654,6 +1660,11 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         CliCommand::AddressLookupTable(subcommand) => {
             process_address_lookup_table_subcommand(rpc_client, config, subcommand)
         }
+
+        // Hypergrid Commands
+        CliCommand::Hypergrid(subcommand) => {
+            process_hypergrid_subcommand(rpc_client, config, subcommand)
+        }
         CliCommand::SignOffchainMessage { message } => {
             process_sign_offchain_message(config, message)
         }
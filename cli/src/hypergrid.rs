@@ -0,0 +1,771 @@
+use {
+    crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
+    clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
+    serde_json::json,
+    solana_account_decoder::UiAccount,
+    solana_clap_utils::{self, input_parsers::*, input_validators::*, keypair::*},
+    solana_cli_output::{
+        CliHypergridNode, CliHypergridNodes, CliRemoteAccountStatus, CliSettlementAccount,
+        CliSettlementAccountCreated, CliSignature,
+    },
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        config::{RpcAccountInfoConfig, RpcSendTransactionConfig},
+        request::RpcRequest,
+        response::Response,
+    },
+    solana_sdk::{
+        account::ReadableAccount,
+        instruction::Instruction,
+        message::Message,
+        native_token::lamports_to_sol,
+        pubkey::Pubkey,
+        signer::Signer,
+        sonic_account_migrater::instruction::{
+            deactivate_remote_accounts, migrate_remote_accounts, migrate_source_accounts,
+        },
+        sonic_fee_settlement::{
+            self,
+            instruction::{initialize_account, withdraw_fee_bill},
+            state::{SettlementAccountType, SettlementState, SETTLEMENT_ACCOUNT_SIZE},
+        },
+        system_instruction,
+        transaction::Transaction,
+    },
+    sonic_hypergrid::{config::Config as HypergridConfig, remote_loader::RemoteAccountLoader},
+    std::{rc::Rc, sync::Arc},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum HypergridCliCommand {
+    MigrateAccounts {
+        addresses: Vec<Pubkey>,
+        source_node: Option<Pubkey>,
+        refresh: bool,
+    },
+    DeactivateAccounts {
+        addresses: Vec<Pubkey>,
+    },
+    ShowNodes {
+        hssn_url: Option<String>,
+    },
+    ShowAccountStatus {
+        address: Pubkey,
+        hssn_url: Option<String>,
+    },
+    InitializeSettlementAccount {
+        settlement_account_signer_index: SignerIndex,
+        owner: Pubkey,
+        account_type: SettlementAccountType,
+    },
+    ShowSettlementAccount {
+        settlement_address: Pubkey,
+    },
+    WithdrawSettlementAccount {
+        settlement_address: Pubkey,
+        owner_signer_index: SignerIndex,
+        lamports: u64,
+    },
+}
+
+pub trait HypergridSubCommands {
+    fn hypergrid_subcommands(self) -> Self;
+}
+
+fn hssn_url_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("hssn_url")
+        .long("hssn-url")
+        .value_name("URL")
+        .takes_value(true)
+        .validator(is_url)
+        .help("HSSN REST endpoint [default: hssn_rpc_url of the hypergrid config file]")
+}
+
+fn addresses_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("addresses")
+        .index(1)
+        .value_name("ADDRESS")
+        .takes_value(true)
+        .multiple(true)
+        .required(true)
+        .validator(is_pubkey)
+        .help("Addresses of the accounts")
+}
+
+impl HypergridSubCommands for App<'_, '_> {
+    fn hypergrid_subcommands(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("hypergrid")
+                .about("Hypergrid remote account and fee settlement management")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("migrate")
+                        .about("Migrate remote accounts into this grid")
+                        .arg(addresses_arg())
+                        .arg(
+                            Arg::with_name("source_node")
+                                .long("source-node")
+                                .value_name("NODE_PUBKEY")
+                                .takes_value(true)
+                                .validator(is_pubkey)
+                                .help(
+                                    "Hypergrid node to migrate the accounts from \
+                                     [default: the base layer]",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("refresh")
+                                .long("refresh")
+                                .takes_value(false)
+                                .requires("source_node")
+                                .help("Reload the accounts from the source node"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("deactivate")
+                        .about("Deactivate remote accounts migrated into this grid")
+                        .arg(addresses_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("nodes")
+                        .about("List the hypergrid nodes registered in HSSN")
+                        .arg(hssn_url_arg()),
+                )
+                .subcommand(
+                    SubCommand::with_name("status")
+                        .about("Display the remote status of an account")
+                        .arg(
+                            Arg::with_name("address")
+                                .index(1)
+                                .value_name("ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_pubkey)
+                                .help("Address of the account"),
+                        )
+                        .arg(hssn_url_arg())
+                        .arg(
+                            Arg::with_name("skip_hssn")
+                                .long("skip-hssn")
+                                .takes_value(false)
+                                .conflicts_with("hssn_url")
+                                .help("Do not query the HSSN record of the account"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("fee-settlement")
+                        .about("Fee settlement account management")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(
+                            SubCommand::with_name("initialize")
+                                .about("Create and initialize a settlement account")
+                                .arg(
+                                    Arg::with_name("settlement_account")
+                                        .index(1)
+                                        .value_name("ACCOUNT_KEYPAIR")
+                                        .takes_value(true)
+                                        .required(true)
+                                        .validator(is_valid_signer)
+                                        .help("Keypair of the settlement account to create"),
+                                )
+                                .arg(
+                                    Arg::with_name("owner")
+                                        .long("owner")
+                                        .value_name("OWNER_PUBKEY")
+                                        .takes_value(true)
+                                        .validator(is_valid_pubkey)
+                                        .help(
+                                            "Owner allowed to withdraw from the account \
+                                             [default: the default configured keypair]",
+                                        ),
+                                )
+                                .arg(
+                                    Arg::with_name("account_type")
+                                        .long("account-type")
+                                        .value_name("TYPE")
+                                        .takes_value(true)
+                                        .possible_values(&["burn", "hssn", "sonic-grid", "grid"])
+                                        .default_value("grid")
+                                        .help("Party the settlement account belongs to"),
+                                ),
+                        )
+                        .subcommand(
+                            SubCommand::with_name("show")
+                                .about("Display information about a settlement account")
+                                .arg(
+                                    Arg::with_name("settlement_address")
+                                        .index(1)
+                                        .value_name("ADDRESS")
+                                        .takes_value(true)
+                                        .required(true)
+                                        .validator(is_pubkey)
+                                        .help("Address of the settlement account"),
+                                ),
+                        )
+                        .subcommand(
+                            SubCommand::with_name("withdraw")
+                                .about(
+                                    "Withdraw settled fees from a settlement account to its owner",
+                                )
+                                .arg(
+                                    Arg::with_name("settlement_address")
+                                        .index(1)
+                                        .value_name("ADDRESS")
+                                        .takes_value(true)
+                                        .required(true)
+                                        .validator(is_pubkey)
+                                        .help("Address of the settlement account"),
+                                )
+                                .arg(
+                                    Arg::with_name("amount")
+                                        .index(2)
+                                        .value_name("AMOUNT")
+                                        .takes_value(true)
+                                        .required(true)
+                                        .validator(is_amount)
+                                        .help("The amount to withdraw, in SOL"),
+                                )
+                                .arg(
+                                    Arg::with_name("owner")
+                                        .long("owner")
+                                        .value_name("OWNER_SIGNER")
+                                        .takes_value(true)
+                                        .validator(is_valid_signer)
+                                        .help(
+                                            "Owner of the settlement account \
+                                             [default: the default configured keypair]",
+                                        ),
+                                ),
+                        ),
+                ),
+        )
+    }
+}
+
+fn parse_settlement_account_type(account_type: &str) -> SettlementAccountType {
+    match account_type {
+        "burn" => SettlementAccountType::BurnAccount,
+        "hssn" => SettlementAccountType::HSSNAccount,
+        "sonic-grid" => SettlementAccountType::SonicGridAccount,
+        _ => SettlementAccountType::GridAccount,
+    }
+}
+
+pub fn parse_hypergrid_subcommand(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let (subcommand, sub_matches) = matches.subcommand();
+
+    let response = match (subcommand, sub_matches) {
+        ("migrate", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Hypergrid(HypergridCliCommand::MigrateAccounts {
+                addresses: values_of(matches, "addresses").unwrap(),
+                source_node: pubkey_of(matches, "source_node"),
+                refresh: matches.is_present("refresh"),
+            }),
+            signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+        },
+        ("deactivate", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Hypergrid(HypergridCliCommand::DeactivateAccounts {
+                addresses: values_of(matches, "addresses").unwrap(),
+            }),
+            signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+        },
+        ("nodes", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Hypergrid(HypergridCliCommand::ShowNodes {
+                hssn_url: value_of(matches, "hssn_url"),
+            }),
+            signers: vec![],
+        },
+        ("status", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Hypergrid(HypergridCliCommand::ShowAccountStatus {
+                address: pubkey_of(matches, "address").unwrap(),
+                hssn_url: if matches.is_present("skip_hssn") {
+                    None
+                } else {
+                    Some(value_of(matches, "hssn_url").unwrap_or_else(|| {
+                        HypergridConfig::load(&HypergridConfig::default_path())
+                            .unwrap_or_default()
+                            .hssn_rpc_url
+                    }))
+                },
+            }),
+            signers: vec![],
+        },
+        ("fee-settlement", Some(matches)) => {
+            parse_fee_settlement_subcommand(matches, default_signer, wallet_manager)?
+        }
+        _ => unreachable!(),
+    };
+    Ok(response)
+}
+
+fn parse_fee_settlement_subcommand(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let (subcommand, sub_matches) = matches.subcommand();
+
+    let response = match (subcommand, sub_matches) {
+        ("initialize", Some(matches)) => {
+            let mut bulk_signers = vec![Some(
+                default_signer.signer_from_path(matches, wallet_manager)?,
+            )];
+
+            let (settlement_account_signer, settlement_pubkey) =
+                signer_of(matches, "settlement_account", wallet_manager)?;
+            bulk_signers.push(settlement_account_signer);
+
+            let owner = if let Some(owner) = pubkey_of_signer(matches, "owner", wallet_manager)? {
+                owner
+            } else {
+                default_signer
+                    .signer_from_path(matches, wallet_manager)?
+                    .pubkey()
+            };
+
+            let signer_info =
+                default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
+
+            CliCommandInfo {
+                command: CliCommand::Hypergrid(HypergridCliCommand::InitializeSettlementAccount {
+                    settlement_account_signer_index: signer_info
+                        .index_of(settlement_pubkey)
+                        .unwrap(),
+                    owner,
+                    account_type: parse_settlement_account_type(
+                        matches.value_of("account_type").unwrap(),
+                    ),
+                }),
+                signers: signer_info.signers,
+            }
+        }
+        ("show", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Hypergrid(HypergridCliCommand::ShowSettlementAccount {
+                settlement_address: pubkey_of(matches, "settlement_address").unwrap(),
+            }),
+            signers: vec![],
+        },
+        ("withdraw", Some(matches)) => {
+            let (owner_signer, owner_pubkey) = signer_of(matches, "owner", wallet_manager)?;
+            let bulk_signers = vec![
+                Some(default_signer.signer_from_path(matches, wallet_manager)?),
+                owner_signer,
+            ];
+            let signer_info =
+                default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
+
+            CliCommandInfo {
+                command: CliCommand::Hypergrid(HypergridCliCommand::WithdrawSettlementAccount {
+                    settlement_address: pubkey_of(matches, "settlement_address").unwrap(),
+                    owner_signer_index: signer_info.index_of(owner_pubkey).unwrap(),
+                    lamports: lamports_of_sol(matches, "amount").unwrap(),
+                }),
+                signers: signer_info.signers,
+            }
+        }
+        _ => unreachable!(),
+    };
+    Ok(response)
+}
+
+pub fn process_hypergrid_subcommand(
+    rpc_client: Arc<RpcClient>,
+    config: &CliConfig,
+    subcommand: &HypergridCliCommand,
+) -> ProcessResult {
+    match subcommand {
+        HypergridCliCommand::MigrateAccounts {
+            addresses,
+            source_node,
+            refresh,
+        } => process_migrate_accounts(&rpc_client, config, addresses, *source_node, *refresh),
+        HypergridCliCommand::DeactivateAccounts { addresses } => {
+            process_deactivate_accounts(&rpc_client, config, addresses)
+        }
+        HypergridCliCommand::ShowNodes { hssn_url } => process_show_nodes(config, hssn_url),
+        HypergridCliCommand::ShowAccountStatus { address, hssn_url } => {
+            process_show_account_status(&rpc_client, config, *address, hssn_url.as_deref())
+        }
+        HypergridCliCommand::InitializeSettlementAccount {
+            settlement_account_signer_index,
+            owner,
+            account_type,
+        } => process_initialize_settlement_account(
+            &rpc_client,
+            config,
+            *settlement_account_signer_index,
+            *owner,
+            account_type.clone(),
+        ),
+        HypergridCliCommand::ShowSettlementAccount { settlement_address } => {
+            process_show_settlement_account(&rpc_client, config, *settlement_address)
+        }
+        HypergridCliCommand::WithdrawSettlementAccount {
+            settlement_address,
+            owner_signer_index,
+            lamports,
+        } => process_withdraw_settlement_account(
+            &rpc_client,
+            config,
+            *settlement_address,
+            *owner_signer_index,
+            *lamports,
+        ),
+    }
+}
+
+/// Sign `instructions` with `signers`, paid by the default signer, and send them.
+fn send_instructions(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let mut tx = Transaction::new_unsigned(Message::new(
+        instructions,
+        Some(&config.signers[0].pubkey()),
+    ));
+
+    tx.try_sign(signers, blockhash)?;
+    let signature = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+        &tx,
+        config.commitment,
+        RpcSendTransactionConfig {
+            skip_preflight: false,
+            preflight_commitment: Some(config.commitment.commitment),
+            ..RpcSendTransactionConfig::default()
+        },
+    )?;
+    Ok(signature.to_string())
+}
+
+fn hssn_loader(hssn_url: &Option<String>) -> RemoteAccountLoader {
+    let mut hypergrid_config =
+        HypergridConfig::load(&HypergridConfig::default_path()).unwrap_or_default();
+    if let Some(hssn_url) = hssn_url {
+        hypergrid_config.hssn_rpc_url = hssn_url.clone();
+    }
+    RemoteAccountLoader::new_with_config(hypergrid_config)
+}
+
+fn process_migrate_accounts(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    addresses: &[Pubkey],
+    source_node: Option<Pubkey>,
+    refresh: bool,
+) -> ProcessResult {
+    let payer_address = config.signers[0].pubkey();
+    let instruction = match source_node {
+        Some(node_id) => {
+            migrate_source_accounts(payer_address, node_id, refresh, addresses.to_vec())
+        }
+        None => migrate_remote_accounts(payer_address, addresses.to_vec()),
+    };
+
+    match send_instructions(rpc_client, config, &[instruction], &[config.signers[0]]) {
+        Err(err) => Err(format!("Migrate failed: {err}").into()),
+        Ok(signature) => Ok(config
+            .output_format
+            .formatted_string(&CliSignature { signature })),
+    }
+}
+
+fn process_deactivate_accounts(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    addresses: &[Pubkey],
+) -> ProcessResult {
+    let instruction = deactivate_remote_accounts(config.signers[0].pubkey(), addresses.to_vec());
+
+    match send_instructions(rpc_client, config, &[instruction], &[config.signers[0]]) {
+        Err(err) => Err(format!("Deactivate failed: {err}").into()),
+        Ok(signature) => Ok(config
+            .output_format
+            .formatted_string(&CliSignature { signature })),
+    }
+}
+
+fn process_show_nodes(config: &CliConfig, hssn_url: &Option<String>) -> ProcessResult {
+    let nodes = hssn_loader(hssn_url)
+        .get_hypergrid_nodes()
+        .map_err(|err| format!("Failed to load hypergrid nodes: {err}"))?;
+    let mut nodes: Vec<_> = nodes
+        .into_iter()
+        .map(|node| CliHypergridNode {
+            pubkey: node.pubkey.to_string(),
+            name: node.name.clone(),
+            rpc: node.rpc.clone(),
+            role: node.role_name().to_string(),
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.role.cmp(&b.role).then(a.name.cmp(&b.name)));
+    Ok(config
+        .output_format
+        .formatted_string(&CliHypergridNodes { nodes }))
+}
+
+fn process_show_account_status(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    address: Pubkey,
+    hssn_url: Option<&str>,
+) -> ProcessResult {
+    // The `remote` flag is only carried by the RPC representation of the account.
+    let account = rpc_client
+        .send::<Response<Option<UiAccount>>>(
+            RpcRequest::GetAccountInfo,
+            json!([
+                address.to_string(),
+                RpcAccountInfoConfig {
+                    commitment: Some(config.commitment),
+                    data_slice: Some(solana_account_decoder::UiDataSliceConfig {
+                        offset: 0,
+                        length: 0,
+                    }),
+                    ..RpcAccountInfoConfig::default()
+                }
+            ]),
+        )?
+        .value;
+
+    let mut status = CliRemoteAccountStatus {
+        address: address.to_string(),
+        exists: account.is_some(),
+        remote: account
+            .as_ref()
+            .map(|account| account.remote)
+            .unwrap_or(false),
+        lamports: account.as_ref().map(|account| account.lamports),
+        owner: account.map(|account| account.owner),
+        hssn_recorded: None,
+        hssn_lamports: None,
+        hssn_owner: None,
+    };
+
+    if let Some(hssn_url) = hssn_url {
+        let hssn_account = hssn_loader(&Some(hssn_url.to_string()))
+            .get_hssn_account(&address)
+            .map_err(|err| format!("Failed to query HSSN: {err}"))?;
+        status.hssn_recorded = Some(hssn_account.is_some());
+        status.hssn_lamports = hssn_account.as_ref().map(|account| account.lamports());
+        status.hssn_owner = hssn_account.map(|account| account.owner().to_string());
+    }
+
+    Ok(config.output_format.formatted_string(&status))
+}
+
+fn process_initialize_settlement_account(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    settlement_account_signer_index: SignerIndex,
+    owner: Pubkey,
+    account_type: SettlementAccountType,
+) -> ProcessResult {
+    let settlement_account_signer = config.signers[settlement_account_signer_index];
+    let settlement_address = settlement_account_signer.pubkey();
+
+    if rpc_client
+        .get_account_with_commitment(&settlement_address, config.commitment)?
+        .value
+        .is_some()
+    {
+        return Err(
+            CliError::BadParameter(format!("Account {settlement_address} already exists")).into(),
+        );
+    }
+
+    let lamports = rpc_client.get_minimum_balance_for_rent_exemption(SETTLEMENT_ACCOUNT_SIZE)?;
+    let instructions = [
+        system_instruction::create_account(
+            &config.signers[0].pubkey(),
+            &settlement_address,
+            lamports,
+            SETTLEMENT_ACCOUNT_SIZE as u64,
+            &sonic_fee_settlement::program::id(),
+        ),
+        initialize_account(settlement_address, owner, account_type),
+    ];
+
+    match send_instructions(
+        rpc_client,
+        config,
+        &instructions,
+        &[config.signers[0], settlement_account_signer],
+    ) {
+        Err(err) => Err(format!("Initialize failed: {err}").into()),
+        Ok(signature) => Ok(config
+            .output_format
+            .formatted_string(&CliSettlementAccountCreated {
+                settlement_address: settlement_address.to_string(),
+                signature,
+            })),
+    }
+}
+
+fn get_settlement_account(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    settlement_address: &Pubkey,
+) -> Result<sonic_fee_settlement::state::SettlementAccount, Box<dyn std::error::Error>> {
+    let account = rpc_client
+        .get_account_with_commitment(settlement_address, config.commitment)?
+        .value
+        .ok_or_else(|| format!("Settlement account {settlement_address} not found"))?;
+    if !sonic_fee_settlement::program::check_id(&account.owner) {
+        return Err(format!(
+            "Account {settlement_address} is not owned by the fee settlement program"
+        )
+        .into());
+    }
+    match bincode::deserialize::<SettlementState>(&account.data) {
+        Ok(SettlementState::FeeBillSettled(settlement_account)) => Ok(settlement_account),
+        _ => Err(format!("Account {settlement_address} is not a settlement account").into()),
+    }
+}
+
+fn process_show_settlement_account(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    settlement_address: Pubkey,
+) -> ProcessResult {
+    let settlement_account = get_settlement_account(rpc_client, config, &settlement_address)?;
+    let account_type = match settlement_account.account_type {
+        SettlementAccountType::BurnAccount => "Burn",
+        SettlementAccountType::HSSNAccount => "HSSN",
+        SettlementAccountType::SonicGridAccount => "Sonic Grid",
+        SettlementAccountType::GridAccount => "Grid",
+    };
+    Ok(config
+        .output_format
+        .formatted_string(&CliSettlementAccount {
+            address: settlement_address.to_string(),
+            owner: settlement_account.owner.to_string(),
+            account_type: account_type.to_string(),
+            amount: settlement_account.amount,
+            withdrawable: settlement_account.withdrawable,
+            withdrawn: settlement_account.withdrawed,
+        }))
+}
+
+fn process_withdraw_settlement_account(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    settlement_address: Pubkey,
+    owner_signer_index: SignerIndex,
+    lamports: u64,
+) -> ProcessResult {
+    let owner_signer = config.signers[owner_signer_index];
+    let settlement_account = get_settlement_account(rpc_client, config, &settlement_address)?;
+    if settlement_account.owner != owner_signer.pubkey() {
+        return Err(CliError::BadParameter(format!(
+            "{} is not the owner of settlement account {settlement_address}",
+            owner_signer.pubkey()
+        ))
+        .into());
+    }
+    if lamports > settlement_account.withdrawable {
+        return Err(CliError::BadParameter(format!(
+            "Settlement account {settlement_address} has only {} SOL withdrawable",
+            lamports_to_sol(settlement_account.withdrawable)
+        ))
+        .into());
+    }
+
+    let instruction = withdraw_fee_bill(settlement_address, owner_signer.pubkey(), lamports);
+    match send_instructions(
+        rpc_client,
+        config,
+        &[instruction],
+        &[config.signers[0], owner_signer],
+    ) {
+        Err(err) => Err(format!("Withdraw failed: {err}").into()),
+        Ok(signature) => Ok(config
+            .output_format
+            .formatted_string(&CliSignature { signature })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{clap_app::get_clap_app, cli::parse_command},
+        solana_sdk::signature::{read_keypair_file, write_keypair, Keypair},
+        tempfile::NamedTempFile,
+    };
+
+    fn make_tmp_file() -> (String, NamedTempFile) {
+        let tmp_file = NamedTempFile::new().unwrap();
+        (String::from(tmp_file.path().to_str().unwrap()), tmp_file)
+    }
+
+    #[test]
+    fn test_parse_hypergrid_migrate() {
+        let test_commands = get_clap_app("test", "desc", "version");
+
+        let default_keypair = Keypair::new();
+        let (default_keypair_file, mut tmp_file) = make_tmp_file();
+        write_keypair(&default_keypair, tmp_file.as_file_mut()).unwrap();
+        let default_signer = DefaultSigner::new("", &default_keypair_file);
+
+        let address = Pubkey::new_unique();
+        let node_id = Pubkey::new_unique();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "hypergrid",
+            "migrate",
+            &address.to_string(),
+            "--source-node",
+            &node_id.to_string(),
+            "--refresh",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Hypergrid(HypergridCliCommand::MigrateAccounts {
+                    addresses: vec![address],
+                    source_node: Some(node_id),
+                    refresh: true,
+                }),
+                signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
+            }
+        );
+
+        // --refresh requires a source node
+        let test_command = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "hypergrid",
+            "migrate",
+            &address.to_string(),
+            "--refresh",
+        ]);
+        assert!(test_command.is_err());
+
+        let test_command = test_commands.get_matches_from(vec![
+            "test",
+            "hypergrid",
+            "fee-settlement",
+            "withdraw",
+            &address.to_string(),
+            "1.5",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Hypergrid(HypergridCliCommand::WithdrawSettlementAccount {
+                    settlement_address: address,
+                    owner_signer_index: 0,
+                    lamports: 1_500_000_000,
+                }),
+                signers: vec![read_keypair_file(&default_keypair_file).unwrap().into()],
+            }
+        );
+    }
+}
@@ -30,6 +30,7 @@ pub mod cli;
 pub mod cluster_query;
 pub mod compute_unit_price;
 pub mod feature;
+pub mod hypergrid;
 pub mod inflation;
 pub mod memo;
 pub mod nonce;
@@ -142,8 +142,13 @@ impl AggregateCommitmentService {
         let (block_commitment, rooted_stake) =
             Self::aggregate_commitment(&ancestors, &aggregation_data.bank);
 
-        let highest_super_majority_root =
-            get_highest_super_majority_root(rooted_stake, aggregation_data.total_stake);
+        // Sonic: the blocks of a sequencer grid are rooted without votes
+        let sequencer_mode = aggregation_data.bank.is_sequencer_mode();
+        let highest_super_majority_root = if sequencer_mode {
+            aggregation_data.root
+        } else {
+            get_highest_super_majority_root(rooted_stake, aggregation_data.total_stake)
+        };
 
         let mut new_block_commitment = BlockCommitmentCache::new(
             block_commitment,
@@ -155,7 +160,11 @@ impl AggregateCommitmentService {
                 highest_super_majority_root,
             },
         );
-        let highest_confirmed_slot = new_block_commitment.calculate_highest_confirmed_slot();
+        let highest_confirmed_slot = if sequencer_mode {
+            aggregation_data.root
+        } else {
+            new_block_commitment.calculate_highest_confirmed_slot()
+        };
         new_block_commitment.set_highest_confirmed_slot(highest_confirmed_slot);
 
         let mut w_block_commitment_cache = block_commitment_cache.write().unwrap();
@@ -253,7 +262,10 @@ mod tests {
         solana_runtime::{
             accounts_background_service::AbsRequestSender,
             bank_forks::BankForks,
-            genesis_utils::{create_genesis_config_with_vote_accounts, ValidatorVoteKeypairs},
+            genesis_utils::{
+                activate_sequencer_mode, create_genesis_config_with_vote_accounts,
+                ValidatorVoteKeypairs,
+            },
         },
         solana_sdk::{account::Account, pubkey::Pubkey, signature::Signer},
         solana_stake_program::stake_state,
@@ -684,4 +696,29 @@ mod tests {
             bank_forks.read().unwrap().get(highest_super_majority_root);
         assert!(highest_super_majority_root_bank.is_some());
     }
+
+    #[test]
+    fn test_update_commitment_cache_sequencer_mode() {
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(10_000);
+        activate_sequencer_mode(&mut genesis_config, Pubkey::new_unique());
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank3 = Arc::new(Bank::new_from_parent(bank0, &Pubkey::default(), 3));
+        let ancestors = bank3.status_cache_ancestors();
+        let block_commitment_cache = RwLock::new(BlockCommitmentCache::default());
+
+        // No vote roots the blocks of the sequencer, the root of the node is final
+        let commitment_slots = AggregateCommitmentService::update_commitment_cache(
+            &block_commitment_cache,
+            CommitmentAggregationData {
+                bank: bank3,
+                root: 3,
+                total_stake: 100,
+            },
+            ancestors,
+        );
+        assert_eq!(commitment_slots.highest_confirmed_slot, 3);
+        assert_eq!(commitment_slots.highest_super_majority_root, 3);
+    }
 }
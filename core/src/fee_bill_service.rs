@@ -0,0 +1,402 @@
+//! Sonic: the `fee_bill_service` turns the transaction fees collected by rooted
+//! banks into fee bills, and settles them through the fee settlement program
+//! and HSSN.
+
+use {
+    crossbeam_channel::{Receiver, RecvTimeoutError},
+    solana_runtime::{
+        bank::{CollectedFeeDetails, SlotFeeDetails},
+        bank_forks::BankForks,
+    },
+    solana_sdk::{clock::Slot, pubkey::Pubkey},
+    sonic_hypergrid::{
+        config::{FeeBillConfig, FeeBillKey},
+        fee_bill::{FeeBillLedger, FeeBillSubmitter},
+    },
+    std::{
+        collections::HashMap,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+const MIN_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+pub type FeeDetailsReceiver = Receiver<SlotFeeDetails>;
+
+pub struct FeeBillService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl FeeBillService {
+    pub fn new(
+        fee_details_receiver: FeeDetailsReceiver,
+        bank_forks: Arc<RwLock<BankForks>>,
+        ledger_path: &Path,
+        config: FeeBillConfig,
+        hssn_tx_url: Option<&str>,
+        exit: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        let mut ledger = FeeBillLedger::open(ledger_path)
+            .map_err(|err| format!("failed to open fee bill ledger: {err}"))?;
+        let submitter = FeeBillSubmitter::new(&config, hssn_tx_url)?;
+
+        let thread_hdl = Builder::new()
+            .name("solFeeBill".to_string())
+            .spawn(move || {
+                let mut state = FeeBillState::new(config, &ledger);
+                let mut retry = RetryBackoff::default();
+                loop {
+                    if exit.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match fee_details_receiver.recv_timeout(RECV_TIMEOUT) {
+                        Ok(slot_fee_details) => state.add_frozen(slot_fee_details, &ledger),
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+
+                    let root = bank_forks.read().unwrap().root();
+                    let pending_batches = ledger.pending.len();
+                    if state.receive_and_root(&fee_details_receiver, root, &mut ledger) {
+                        if let Err(err) = ledger.save() {
+                            error!("failed to save fee bill ledger: {err}");
+                        }
+                        if ledger.pending.len() > pending_batches {
+                            retry.reset();
+                        }
+                    }
+
+                    if !ledger.pending.is_empty() && retry.is_due() {
+                        match Self::submit_pending(&submitter, &mut ledger) {
+                            Ok(()) => retry.reset(),
+                            Err(err) => {
+                                warn!("fee bill submission failed: {err}");
+                                datapoint_warn!(
+                                    "fee_bill_service-submission_failed",
+                                    ("pending_batches", ledger.pending.len(), i64),
+                                    ("error", err, String),
+                                );
+                                retry.backoff();
+                            }
+                        }
+                    }
+                }
+            })
+            .unwrap();
+        Ok(Self { thread_hdl })
+    }
+
+    /// Submit the pending batches in id order, stopping at the first failure.
+    fn submit_pending(
+        submitter: &FeeBillSubmitter,
+        ledger: &mut FeeBillLedger,
+    ) -> Result<(), String> {
+        let mut result = Ok(());
+        for batch in ledger.pending.iter_mut() {
+            result = submitter
+                .settle(batch)
+                .and_then(|()| submitter.record_in_hssn(batch));
+            if result.is_err() {
+                break;
+            }
+        }
+        ledger.prune();
+        if let Err(err) = ledger.save() {
+            error!("failed to save fee bill ledger: {err}");
+        }
+        result
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+/// Tracks frozen banks until they are rooted or pruned, and aggregates the
+/// fees of rooted slots until enough slots are rooted to issue bills. The
+/// running aggregate is mirrored in the ledger, so a restart, which doesn't
+/// replay the rooted slots, resumes it instead of losing their fees.
+struct FeeBillState {
+    config: FeeBillConfig,
+    frozen: HashMap<Slot, SlotFeeDetails>,
+    root: Option<Slot>,
+    start_slot: Option<Slot>,
+    fees: HashMap<Pubkey, u64>,
+}
+
+impl FeeBillState {
+    fn new(config: FeeBillConfig, ledger: &FeeBillLedger) -> Self {
+        let unbilled = ledger.unbilled.as_ref();
+        Self {
+            config,
+            frozen: HashMap::default(),
+            root: ledger.last_rooted_slot,
+            start_slot: unbilled.map(|unbilled| unbilled.start_slot),
+            fees: unbilled
+                .map(|unbilled| {
+                    unbilled
+                        .fees
+                        .iter()
+                        .map(|bill| (bill.key, bill.amount))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn add_frozen(&mut self, slot_fee_details: SlotFeeDetails, ledger: &FeeBillLedger) {
+        if !ledger.is_aggregated(slot_fee_details.slot) {
+            self.frozen.insert(slot_fee_details.slot, slot_fee_details);
+        }
+    }
+
+    /// Add the fee details queued in `receiver`, then aggregate the fees of the slots
+    /// rooted by `root`. Banks send their fee details when frozen, before they can be
+    /// rooted, so draining the channel after reading `root` sees every rooted slot.
+    fn receive_and_root(
+        &mut self,
+        receiver: &FeeDetailsReceiver,
+        root: Slot,
+        ledger: &mut FeeBillLedger,
+    ) -> bool {
+        for slot_fee_details in receiver.try_iter() {
+            self.add_frozen(slot_fee_details, ledger);
+        }
+        self.set_root(root, ledger)
+    }
+
+    /// Aggregate the fees of the slots rooted by `root`, returns true if the ledger changed.
+    fn set_root(&mut self, root: Slot, ledger: &mut FeeBillLedger) -> bool {
+        if self
+            .root
+            .map(|last_root| root <= last_root)
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        let rooted = rooted_slots(&self.frozen, root, self.root);
+        self.root = Some(root);
+        let any_rooted = !rooted.is_empty();
+        for slot in rooted.into_iter().rev() {
+            let slot_fee_details = self.frozen.remove(&slot).unwrap();
+            self.start_slot.get_or_insert(slot);
+            self.add_fees(slot_fee_details.details);
+        }
+        // Whatever else is at or below the root is on a dead fork.
+        self.frozen.retain(|slot, _| *slot > root);
+        if !any_rooted {
+            return false;
+        }
+
+        // `rooted` isn't empty, so neither is `start_slot`
+        let start_slot = self.start_slot.unwrap();
+        if root.saturating_sub(start_slot) + 1 >= self.config.slots_per_bill {
+            ledger.add_bills(
+                start_slot,
+                root,
+                std::mem::take(&mut self.fees),
+                self.config.max_bills_per_batch,
+            );
+            self.start_slot = None;
+        } else {
+            ledger.set_unbilled(start_slot, root, &self.fees);
+        }
+        true
+    }
+
+    fn add_fees(&mut self, details: CollectedFeeDetails) {
+        let fees = match self.config.aggregate_by {
+            FeeBillKey::FeePayer => details.by_fee_payer,
+            FeeBillKey::Program => details.by_program,
+        };
+        for (key, fee) in fees {
+            let entry = self.fees.entry(key).or_default();
+            *entry = entry.saturating_add(fee);
+        }
+    }
+}
+
+/// Walk the fork from `root` back to `last_root`, returning the rooted slots
+/// that were frozen since, newest first.
+fn rooted_slots(
+    frozen: &HashMap<Slot, SlotFeeDetails>,
+    root: Slot,
+    last_root: Option<Slot>,
+) -> Vec<Slot> {
+    let mut rooted = vec![];
+    let mut slot = root;
+    while let Some(slot_fee_details) = frozen.get(&slot) {
+        if last_root
+            .map(|last_root| slot <= last_root)
+            .unwrap_or(false)
+        {
+            break;
+        }
+        rooted.push(slot);
+        if slot_fee_details.parent_slot == slot {
+            break;
+        }
+        slot = slot_fee_details.parent_slot;
+    }
+    rooted
+}
+
+struct RetryBackoff {
+    next_attempt: Instant,
+    backoff: Duration,
+}
+
+impl Default for RetryBackoff {
+    fn default() -> Self {
+        Self {
+            next_attempt: Instant::now(),
+            backoff: MIN_RETRY_BACKOFF,
+        }
+    }
+}
+
+impl RetryBackoff {
+    fn is_due(&self) -> bool {
+        Instant::now() >= self.next_attempt
+    }
+
+    fn backoff(&mut self) {
+        self.next_attempt = Instant::now() + self.backoff;
+        self.backoff = (self.backoff * 2).min(MAX_RETRY_BACKOFF);
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot_fee_details(
+        slot: Slot,
+        parent_slot: Slot,
+        fee_payer: Pubkey,
+        fee: u64,
+    ) -> SlotFeeDetails {
+        let mut details = CollectedFeeDetails::default();
+        details.by_fee_payer.insert(fee_payer, fee);
+        SlotFeeDetails {
+            slot,
+            parent_slot,
+            details,
+        }
+    }
+
+    #[test]
+    fn test_fee_bill_state_skips_dead_forks() {
+        let dir = std::env::temp_dir().join(format!("fee_bill_service_{}", Pubkey::new_unique()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut ledger = FeeBillLedger::open(&dir).unwrap();
+        let mut state = FeeBillState::new(
+            FeeBillConfig {
+                slots_per_bill: 4,
+                ..FeeBillConfig::default()
+            },
+            &ledger,
+        );
+        let payer = Pubkey::new_unique();
+
+        //   0 - 1 - 2 - 4
+        //        \
+        //         3
+        state.add_frozen(slot_fee_details(0, 0, payer, 1), &ledger);
+        state.add_frozen(slot_fee_details(1, 0, payer, 2), &ledger);
+        state.add_frozen(slot_fee_details(2, 1, payer, 4), &ledger);
+        state.add_frozen(slot_fee_details(3, 1, payer, 8), &ledger);
+        state.add_frozen(slot_fee_details(4, 2, payer, 16), &ledger);
+
+        assert!(state.set_root(2, &mut ledger));
+        assert!(!state.frozen.contains_key(&3));
+        assert!(ledger.pending.is_empty());
+        assert_eq!(ledger.unbilled.as_ref().unwrap().fees[0].amount, 1 + 2 + 4);
+        assert!(state.set_root(4, &mut ledger));
+        assert!(ledger.unbilled.is_none());
+
+        assert_eq!(ledger.pending.len(), 1);
+        let batch = &ledger.pending[0];
+        assert_eq!((batch.start_slot, batch.end_slot), (0, 4));
+        assert_eq!(batch.bills.len(), 1);
+        assert_eq!(batch.bills[0].amount, 1 + 2 + 4 + 16);
+
+        // Replayed slots that were already billed are ignored
+        state.add_frozen(slot_fee_details(4, 2, payer, 16), &ledger);
+        assert!(state.frozen.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fee_bill_state_root_ahead_of_fee_details() {
+        let dir = std::env::temp_dir().join(format!("fee_bill_service_{}", Pubkey::new_unique()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut ledger = FeeBillLedger::open(&dir).unwrap();
+        let mut state = FeeBillState::new(
+            FeeBillConfig {
+                slots_per_bill: 3,
+                ..FeeBillConfig::default()
+            },
+            &ledger,
+        );
+        let payer = Pubkey::new_unique();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+
+        // The root moved to 2 while the fee details of slots 0..=2 were still queued
+        sender.send(slot_fee_details(0, 0, payer, 1)).unwrap();
+        sender.send(slot_fee_details(1, 0, payer, 2)).unwrap();
+        sender.send(slot_fee_details(2, 1, payer, 4)).unwrap();
+        assert!(state.receive_and_root(&receiver, 2, &mut ledger));
+
+        assert_eq!(ledger.pending.len(), 1);
+        let batch = &ledger.pending[0];
+        assert_eq!((batch.start_slot, batch.end_slot), (0, 2));
+        assert_eq!(batch.bills[0].amount, 1 + 2 + 4);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fee_bill_state_resumes_unbilled_fees_after_restart() {
+        let dir = std::env::temp_dir().join(format!("fee_bill_service_{}", Pubkey::new_unique()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = FeeBillConfig {
+            slots_per_bill: 4,
+            ..FeeBillConfig::default()
+        };
+        let payer = Pubkey::new_unique();
+
+        let mut ledger = FeeBillLedger::open(&dir).unwrap();
+        let mut state = FeeBillState::new(config.clone(), &ledger);
+        state.add_frozen(slot_fee_details(0, 0, payer, 1), &ledger);
+        state.add_frozen(slot_fee_details(1, 0, payer, 2), &ledger);
+        assert!(state.set_root(1, &mut ledger));
+        ledger.save().unwrap();
+
+        // Slots 0 and 1 are rooted, so they aren't replayed after the restart
+        let mut ledger = FeeBillLedger::open(&dir).unwrap();
+        let mut state = FeeBillState::new(config, &ledger);
+        state.add_frozen(slot_fee_details(1, 0, payer, 2), &ledger);
+        assert!(state.frozen.is_empty());
+        state.add_frozen(slot_fee_details(2, 1, payer, 4), &ledger);
+        state.add_frozen(slot_fee_details(3, 2, payer, 8), &ledger);
+        assert!(state.set_root(3, &mut ledger));
+
+        assert_eq!(ledger.pending.len(), 1);
+        let batch = &ledger.pending[0];
+        assert_eq!((batch.start_slot, batch.end_slot), (0, 3));
+        assert_eq!(batch.bills[0].amount, 1 + 2 + 4 + 8);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
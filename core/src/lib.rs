@@ -20,6 +20,7 @@ pub mod completed_data_sets_service;
 pub mod consensus;
 pub mod cost_update_service;
 pub mod drop_bank_service;
+pub mod fee_bill_service;
 pub mod fetch_stage;
 pub mod gen_keys;
 pub mod next_leader;
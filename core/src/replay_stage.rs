@@ -852,16 +852,20 @@ impl ReplayStage {
                     vote_bank,
                     reset_bank,
                     heaviest_fork_failures,
-                } = Self::select_vote_and_reset_forks(
-                    &heaviest_bank,
-                    heaviest_bank_on_same_voted_fork.as_ref(),
-                    &ancestors,
-                    &descendants,
-                    &progress,
-                    &mut tower,
-                    &latest_validator_votes_for_frozen_banks,
-                    &heaviest_subtree_fork_choice,
-                );
+                } = if heaviest_bank.is_sequencer_mode() {
+                    Self::select_sequencer_forks(&heaviest_bank, forks_root)
+                } else {
+                    Self::select_vote_and_reset_forks(
+                        &heaviest_bank,
+                        heaviest_bank_on_same_voted_fork.as_ref(),
+                        &ancestors,
+                        &descendants,
+                        &progress,
+                        &mut tower,
+                        &latest_validator_votes_for_frozen_banks,
+                        &heaviest_subtree_fork_choice,
+                    )
+                };
                 select_vote_and_reset_forks_time.stop();
 
                 if vote_bank.is_none() {
@@ -950,6 +954,7 @@ impl ReplayStage {
                         &mut epoch_slots_frozen_slots,
                         &drop_bank_sender,
                         wait_to_vote_slot,
+                        tower_storage.as_ref(),
                     );
                 }
                 voting_time.stop();
@@ -1980,7 +1985,9 @@ impl ReplayStage {
         );
 
         if let Some(next_leader) = leader_schedule_cache.slot_leader_at(poh_slot, Some(&parent)) {
-            if !has_new_vote_been_rooted {
+            // Sonic: the sequencer leads every slot and never votes
+            let sequencer_mode = parent.is_sequencer_mode();
+            if !has_new_vote_been_rooted && !sequencer_mode {
                 info!("Haven't landed a vote, so skipping my leader slot");
                 return;
             }
@@ -2003,7 +2010,9 @@ impl ReplayStage {
                 ("leader", next_leader.to_string(), String),
             );
 
-            if !Self::check_propagation_for_start_leader(poh_slot, parent_slot, progress_map) {
+            if !sequencer_mode
+                && !Self::check_propagation_for_start_leader(poh_slot, parent_slot, progress_map)
+            {
                 let latest_unconfirmed_leader_slot = progress_map.get_latest_leader_slot_must_exist(parent_slot)
                     .expect("In order for propagated check to fail, latest leader must exist in progress map");
                 if poh_slot != skipped_slots_info.last_skipped_slot {
@@ -2236,12 +2245,27 @@ impl ReplayStage {
         epoch_slots_frozen_slots: &mut EpochSlotsFrozenSlots,
         drop_bank_sender: &Sender<Vec<Arc<Bank>>>,
         wait_to_vote_slot: Option<Slot>,
+        tower_storage: &dyn TowerStorage,
     ) {
         if bank.is_empty() {
             datapoint_info!("replay_stage-voted_empty_bank", ("slot", bank.slot(), i64));
         }
         trace!("handle votable bank {}", bank.slot());
-        let new_root = tower.record_bank_vote(bank);
+        // Sonic: the blocks of the sequencer are final once replayed. The tower still records
+        // them and is saved, as no vote goes through the voting service to save it, so a
+        // restart restores it onto the rooted fork. Its root trails the bank root by the
+        // lockouts, which restoring reconciles like for any tower behind the replayed root.
+        let sequencer_mode = bank.is_sequencer_mode();
+        let new_root = if sequencer_mode {
+            tower.record_bank_vote(bank);
+            if let Err(err) = tower.save(tower_storage, identity_keypair) {
+                error!("Unable to save tower to storage: {:?}", err);
+                std::process::exit(1);
+            }
+            Some(bank.slot())
+        } else {
+            tower.record_bank_vote(bank)
+        };
 
         if let Some(new_root) = new_root {
             // get the root bank before squash
@@ -2328,6 +2352,9 @@ impl ReplayStage {
         update_commitment_cache_time.stop();
         replay_timing.update_commitment_cache_us += update_commitment_cache_time.as_us();
 
+        if sequencer_mode {
+            return;
+        }
         Self::push_vote(
             bank,
             vote_account_pubkey,
@@ -3533,6 +3560,20 @@ impl ReplayStage {
         }
     }
 
+    /// Sonic: a sequencer grid has a single fork, whose banks are rooted as soon as they are
+    /// frozen, without checking lockouts, thresholds or propagation
+    fn select_sequencer_forks(
+        heaviest_bank: &Arc<Bank>,
+        root: Slot,
+    ) -> SelectVoteAndResetForkResult {
+        SelectVoteAndResetForkResult {
+            vote_bank: (heaviest_bank.slot() > root)
+                .then(|| (heaviest_bank.clone(), SwitchForkDecision::SameFork)),
+            reset_bank: Some(heaviest_bank.clone()),
+            heaviest_fork_failures: vec![],
+        }
+    }
+
     /// Given a `heaviest_bank` and a `heaviest_bank_on_same_voted_fork`, return
     /// a bank to vote on, a bank to reset to, and a list of switch failure
     /// reasons.
@@ -4259,7 +4300,7 @@ pub(crate) mod tests {
         solana_runtime::{
             accounts_background_service::AbsRequestSender,
             commitment::{BlockCommitment, VOTE_THRESHOLD_SIZE},
-            genesis_utils::{GenesisConfigInfo, ValidatorVoteKeypairs},
+            genesis_utils::{activate_sequencer_mode, GenesisConfigInfo, ValidatorVoteKeypairs},
         },
         solana_sdk::{
             clock::NUM_CONSECUTIVE_LEADER_SLOTS,
@@ -8452,6 +8493,36 @@ pub(crate) mod tests {
         assert!(!in_vote_only_mode.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_select_sequencer_forks() {
+        let mut genesis_config = create_genesis_config(10_000).genesis_config;
+        activate_sequencer_mode(&mut genesis_config, Pubkey::new_unique());
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Arc::new(Bank::new_from_parent(bank0, &Pubkey::default(), 1));
+        assert!(bank1.is_sequencer_mode());
+
+        // The heaviest bank is rooted without a vote
+        let SelectVoteAndResetForkResult {
+            vote_bank,
+            reset_bank,
+            heaviest_fork_failures,
+        } = ReplayStage::select_sequencer_forks(&bank1, 0);
+        let (vote_bank, switch_fork_decision) = vote_bank.unwrap();
+        assert_eq!(vote_bank.slot(), 1);
+        assert_eq!(switch_fork_decision, SwitchForkDecision::SameFork);
+        assert_eq!(reset_bank.unwrap().slot(), 1);
+        assert!(heaviest_fork_failures.is_empty());
+
+        // Until a new bank is frozen, there is nothing left to root
+        let SelectVoteAndResetForkResult {
+            vote_bank,
+            reset_bank,
+            ..
+        } = ReplayStage::select_sequencer_forks(&bank1, 1);
+        assert!(vote_bank.is_none());
+        assert_eq!(reset_bank.unwrap().slot(), 1);
+    }
+
     #[test]
     fn test_tower_sync_from_bank_failed_switch() {
         solana_logger::setup_with_default(
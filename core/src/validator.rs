@@ -14,6 +14,7 @@ use {
             tower_storage::{NullTowerStorage, TowerStorage},
             ExternalRootSource, Tower,
         },
+        fee_bill_service::FeeBillService,
         poh_timing_report_service::PohTimingReportService,
         repair::{self, serve_repair::ServeRepair, serve_repair_service::ServeRepairService},
         rewards_recorder_service::{RewardsRecorderSender, RewardsRecorderService},
@@ -121,6 +122,7 @@ use {
     solana_unified_scheduler_pool::DefaultSchedulerPool,
     solana_vote_program::vote_state,
     solana_wen_restart::wen_restart::wait_for_wen_restart,
+    sonic_hypergrid::{config::FeeBillConfig, remote_source::RemoteAccountSource},
     std::{
         collections::{HashMap, HashSet},
         net::SocketAddr,
@@ -265,6 +267,10 @@ pub struct ValidatorConfig {
     pub generator_config: Option<GeneratorConfig>,
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
     pub wen_restart_proto_path: Option<PathBuf>,
+    /// Sonic: generate fee bills from the collected transaction fees
+    pub fee_bill_config: Option<FeeBillConfig>,
+    /// Sonic: migrate remote accounts from this source instead of the base layer and HSSN
+    pub remote_source: Option<Arc<dyn RemoteAccountSource>>,
 }
 
 impl Default for ValidatorConfig {
@@ -334,6 +340,8 @@ impl Default for ValidatorConfig {
             generator_config: None,
             use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup::default(),
             wen_restart_proto_path: None,
+            fee_bill_config: None,
+            remote_source: None,
         }
     }
 }
@@ -471,6 +479,7 @@ pub struct Validator {
     blockstore_metric_report_service: BlockstoreMetricReportService,
     accounts_background_service: AccountsBackgroundService,
     accounts_hash_verifier: AccountsHashVerifier,
+    fee_bill_service: Option<FeeBillService>,
     turbine_quic_endpoint: Option<Endpoint>,
     turbine_quic_endpoint_runtime: Option<TokioRuntime>,
     turbine_quic_endpoint_join_handle: Option<solana_turbine::quic_endpoint::AsyncTryJoinHandle>,
@@ -782,6 +791,39 @@ impl Validator {
             config.snapshot_config.clone(),
         );
 
+        let fee_bill_service = config
+            .fee_bill_config
+            .clone()
+            .map(|fee_bill_config| {
+                let (fee_details_sender, fee_details_receiver) = unbounded();
+                for bank in bank_forks.read().unwrap().banks().values() {
+                    bank.set_collected_fee_details_sender(Some(fee_details_sender.clone()));
+                }
+                // Sonic: bills are posted to the same HSSN endpoint as the remote loader's
+                // transactions
+                let hssn_tx_url = bank_forks
+                    .read()
+                    .unwrap()
+                    .root_bank()
+                    .rc
+                    .accounts
+                    .accounts_db
+                    .accounts_cache
+                    .remote_loader
+                    .config()
+                    .hssn_tx_url
+                    .clone();
+                FeeBillService::new(
+                    fee_details_receiver,
+                    bank_forks.clone(),
+                    ledger_path,
+                    fee_bill_config,
+                    hssn_tx_url.as_deref(),
+                    exit.clone(),
+                )
+            })
+            .transpose()?;
+
         let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
         let accounts_background_request_sender =
             AbsRequestSender::new(snapshot_request_sender.clone());
@@ -1428,6 +1470,7 @@ impl Validator {
             blockstore_metric_report_service,
             accounts_background_service,
             accounts_hash_verifier,
+            fee_bill_service,
             turbine_quic_endpoint,
             turbine_quic_endpoint_runtime,
             turbine_quic_endpoint_join_handle,
@@ -1567,6 +1610,9 @@ impl Validator {
         self.accounts_hash_verifier
             .join()
             .expect("accounts_hash_verifier");
+        if let Some(fee_bill_service) = self.fee_bill_service {
+            fee_bill_service.join().expect("fee_bill_service");
+        }
         if let Some(turbine_quic_endpoint) = &self.turbine_quic_endpoint {
             solana_turbine::quic_endpoint::close_quic_endpoint(turbine_quic_endpoint);
         }
@@ -1876,6 +1922,18 @@ fn load_blockstore(
     leader_schedule_cache.set_fixed_leader_schedule(config.fixed_leader_schedule.clone());
     {
         let mut bank_forks = bank_forks.write().unwrap();
+        // Sonic: the banks share the remote loader, plug the source in before any of them
+        // processes a transaction
+        if let Some(remote_source) = &config.remote_source {
+            bank_forks
+                .root_bank()
+                .rc
+                .accounts
+                .accounts_db
+                .accounts_cache
+                .remote_loader
+                .set_remote_source(remote_source.clone());
+        }
         bank_forks.set_snapshot_config(Some(config.snapshot_config.clone()));
         bank_forks.set_accounts_hash_interval_slots(config.accounts_hash_interval_slots);
         if let Some(ref shrink_paths) = config.account_shrink_paths {
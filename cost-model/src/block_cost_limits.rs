@@ -5,7 +5,7 @@ use {
     solana_sdk::{
         address_lookup_table, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable,
         compute_budget, ed25519_program, loader_v4, pubkey::Pubkey, secp256k1_program,
-        sonic_account_migrater, sonic_fee_settlement,
+        sonic_account_migrater, sonic_bridge, sonic_fee_settlement,
     },
     std::collections::HashMap,
 };
@@ -44,6 +44,7 @@ lazy_static! {
         (compute_budget::id(), solana_compute_budget_program::DEFAULT_COMPUTE_UNITS),
         (address_lookup_table::program::id(), solana_address_lookup_table_program::processor::DEFAULT_COMPUTE_UNITS),
         (sonic_account_migrater::program::id(), sonic_account_migrater_program::processor::DEFAULT_COMPUTE_UNITS),
+        (sonic_bridge::program::id(), sonic_bridge_program::processor::DEFAULT_COMPUTE_UNITS),
         (sonic_fee_settlement::program::id(), sonic_fee_settlement_program::processor::DEFAULT_COMPUTE_UNITS),
         (bpf_loader_upgradeable::id(), solana_bpf_loader_program::UPGRADEABLE_LOADER_COMPUTE_UNITS),
         (bpf_loader_deprecated::id(), solana_bpf_loader_program::DEPRECATED_LOADER_COMPUTE_UNITS),
@@ -111,6 +111,41 @@ pub struct TransactionParams {
         help = "Number of instructions in transfer transaction"
     )]
     pub num_instructions: Option<usize>,
+
+    #[clap(
+        long,
+        required_if_eq_any(&[
+            ("transaction-type", "remote-migration"),
+            ("transaction-type", "remote-nonexistent"),
+            ("transaction-type", "remote-refresh"),
+        ]),
+        help = "Number of remote accounts migrated by each transaction"
+    )]
+    pub num_remote_accounts: Option<usize>,
+
+    #[clap(
+        long,
+        help = "Data size of the remote accounts created on the source cluster [default: 10240]"
+    )]
+    pub remote_account_size: Option<usize>,
+
+    #[clap(
+        long,
+        required_if_eq_any(&[
+            ("transaction-type", "remote-migration"),
+            ("transaction-type", "remote-refresh"),
+        ]),
+        help = "RPC URL of the cluster the remote accounts are created on"
+    )]
+    pub remote_source_url: Option<String>,
+
+    #[clap(
+        long,
+        parse(try_from_str = pubkey_parser),
+        help = "Hypergrid node the remote accounts are refreshed from \
+                [default: identity of the source cluster RPC node]"
+    )]
+    pub remote_source_node: Option<Pubkey>,
 }
 
 #[derive(ArgEnum, Clone, Copy, Debug, Eq, PartialEq)]
@@ -139,6 +174,12 @@ pub enum DataType {
 pub enum TransactionType {
     Transfer,
     AccountCreation,
+    /// Sonic: migrate large accounts created on the source cluster
+    RemoteMigration,
+    /// Sonic: migrate random accounts which exist on no cluster
+    RemoteNonexistent,
+    /// Sonic: refresh the same accounts of the source cluster over and over
+    RemoteRefresh,
 }
 
 fn addr_parser(addr: &str) -> Result<SocketAddr, &'static str> {
@@ -270,6 +311,10 @@ mod tests {
                     unique_transactions: true,
                     transaction_type: None,
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: true,
                 send_batch_size: 1,
@@ -314,6 +359,10 @@ mod tests {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::Transfer),
                     num_instructions: Some(1),
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: 1,
@@ -373,6 +422,10 @@ mod tests {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::Transfer),
                     num_instructions: Some(8),
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: 1,
@@ -415,11 +468,87 @@ mod tests {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::AccountCreation),
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
+                },
+                tpu_use_quic: false,
+                send_batch_size: 1,
+            },
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_dos_remote_refresh() {
+        let entrypoint_addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let params = DosClientParameters::try_parse_from(vec![
+            "solana-dos",
+            "--mode",
+            "tpu",
+            "--data-type",
+            "transaction",
+            "--unique-transactions",
+            "--valid-blockhash",
+            "--transaction-type",
+            "remote-refresh",
+            "--num-remote-accounts",
+            "16",
+            "--remote-account-size",
+            "1048576",
+            "--remote-source-url",
+            "http://127.0.0.1:9899",
+            "--send-batch-size",
+            "1",
+        ])
+        .unwrap();
+        assert_eq!(
+            params,
+            DosClientParameters {
+                entrypoint_addr,
+                mode: Mode::Tpu,
+                data_size: 128, // irrelevant if not random
+                data_type: DataType::Transaction,
+                data_input: None,
+                skip_gossip: false,
+                allow_private_addr: false,
+                num_gen_threads: 1,
+                transaction_params: TransactionParams {
+                    num_signatures: None,
+                    valid_blockhash: true,
+                    valid_signatures: false,
+                    unique_transactions: true,
+                    transaction_type: Some(TransactionType::RemoteRefresh),
+                    num_instructions: None,
+                    num_remote_accounts: Some(16),
+                    remote_account_size: Some(1048576),
+                    remote_source_url: Some("http://127.0.0.1:9899".to_string()),
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: 1,
             },
         );
+
+        // the accounts to refresh are created on the source cluster
+        let result = DosClientParameters::try_parse_from(vec![
+            "solana-dos",
+            "--mode",
+            "tpu",
+            "--data-type",
+            "transaction",
+            "--unique-transactions",
+            "--valid-blockhash",
+            "--transaction-type",
+            "remote-refresh",
+            "--num-remote-accounts",
+            "16",
+        ]);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
     }
 
     #[test]
@@ -37,6 +37,19 @@
 //! ```bash
 //! solana-dos $COMMON --valid-blockhash --transaction-type account-creation
 //! ```
+//! 3. Sonic: with blockhash and payer, targeting the remote account loader of a grid:
+//! 3.1 Bulk migration of large accounts created on the source cluster
+//! ```bash
+//! solana-dos $COMMON --valid-blockhash --transaction-type remote-migration --num-remote-accounts 16 --remote-account-size 1048576 --remote-source-url $L1_URL
+//! ```
+//! 3.2 Migration of random nonexistent accounts
+//! ```bash
+//! solana-dos $COMMON --valid-blockhash --transaction-type remote-nonexistent --num-remote-accounts 16
+//! ```
+//! 3.3 Refresh storm on the same accounts of the source cluster
+//! ```bash
+//! solana-dos $COMMON --valid-blockhash --transaction-type remote-refresh --num-remote-accounts 16 --remote-source-url $L1_URL
+//! ```
 //!
 #![allow(clippy::arithmetic_side_effects)]
 #![allow(deprecated)]
@@ -45,7 +58,10 @@ use {
     itertools::Itertools,
     log::*,
     rand::{thread_rng, Rng},
-    solana_bench_tps::{bench::generate_and_fund_keypairs, bench_tps_client::BenchTpsClient},
+    solana_bench_tps::{
+        bench::generate_and_fund_keypairs, bench_tps_client::BenchTpsClient,
+        remote_accounts::create_source_accounts,
+    },
     solana_client::{connection_cache::ConnectionCache, tpu_connection::TpuConnection},
     solana_core::repair::serve_repair::{RepairProtocol, RepairRequestHeader, ServeRepair},
     solana_dos::cli::*,
@@ -57,11 +73,13 @@ use {
     solana_measure::measure::Measure,
     solana_rpc_client::rpc_client::RpcClient,
     solana_sdk::{
+        commitment_config::CommitmentConfig,
         hash::Hash,
         instruction::CompiledInstruction,
         message::Message,
         pubkey::Pubkey,
         signature::{Keypair, Signature, Signer},
+        sonic_account_migrater::instruction::{migrate_remote_accounts, migrate_source_accounts},
         stake,
         system_instruction::{self, SystemInstruction},
         system_program,
@@ -81,6 +99,8 @@ use {
 
 const PROGRESS_TIMEOUT_S: u64 = 120;
 const SAMPLE_PERIOD_MS: u64 = 10_000;
+/// Default data size of the remote accounts created on the source cluster
+const DEFAULT_REMOTE_ACCOUNT_SIZE: usize = 10 * 1024;
 fn compute_rate_per_second(count: usize) -> usize {
     (count * 1000) / (SAMPLE_PERIOD_MS as usize)
 }
@@ -94,22 +114,30 @@ fn compute_rate_per_second(count: usize) -> usize {
 /// 2. With blockhash (but still deliberately invalid):
 /// 2.1 Transfer from 1 payer to multiple destinations (many instructions per transaction)
 /// 2.2 Create an account
+/// 2.3 Migrate remote accounts: existing large ones, nonexistent ones, or refresh the same ones
 ///
 #[derive(Clone)]
 struct TransactionGenerator {
     blockhash: Hash,
     last_generated: Instant,
     transaction_params: TransactionParams,
+    /// Accounts of the source cluster migrated by remote transactions
+    remote_accounts: Arc<Vec<Pubkey>>,
+    /// Hypergrid node the remote accounts are refreshed from
+    remote_source_node: Pubkey,
 }
 
 impl TransactionGenerator {
     fn new(transaction_params: TransactionParams) -> Self {
+        let (remote_accounts, remote_source_node) = create_remote_accounts(&transaction_params);
         TransactionGenerator {
             blockhash: Hash::default(),
             last_generated: Instant::now()
                 .checked_sub(Duration::from_secs(100))
                 .unwrap(), //to force generation when generate is called
             transaction_params,
+            remote_accounts: Arc::new(remote_accounts),
+            remote_source_node,
         }
     }
 
@@ -160,6 +188,11 @@ impl TransactionGenerator {
             TransactionType::AccountCreation => {
                 self.create_account_transaction(payer, destinations[0])
             }
+            TransactionType::RemoteMigration
+            | TransactionType::RemoteNonexistent
+            | TransactionType::RemoteRefresh => {
+                self.create_remote_account_transaction(payer, destinations[0])
+            }
         }
     }
 
@@ -192,6 +225,35 @@ impl TransactionGenerator {
         Transaction::new(&signers, message, self.blockhash)
     }
 
+    /// Create a transaction which migrates remote accounts. Since any signer can migrate
+    /// accounts, `signer` makes the transaction unique while `payer` pays for it
+    fn create_remote_account_transaction(&self, payer: &Keypair, signer: &Keypair) -> Transaction {
+        let transaction_type = self.transaction_params.transaction_type.as_ref().unwrap();
+        let instruction = match transaction_type {
+            TransactionType::RemoteMigration => {
+                migrate_remote_accounts(signer.pubkey(), self.remote_accounts.to_vec())
+            }
+            TransactionType::RemoteNonexistent => {
+                let num_remote_accounts = self.transaction_params.num_remote_accounts.unwrap();
+                let addresses = (0..num_remote_accounts)
+                    .map(|_| solana_sdk::pubkey::new_rand())
+                    .collect();
+                migrate_remote_accounts(signer.pubkey(), addresses)
+            }
+            TransactionType::RemoteRefresh => migrate_source_accounts(
+                signer.pubkey(),
+                self.remote_source_node,
+                true,
+                self.remote_accounts.to_vec(),
+            ),
+            _ => unreachable!("not a remote account transaction type"),
+        };
+
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let signers: Vec<&Keypair> = vec![payer, signer];
+        Transaction::new(&signers, message, self.blockhash)
+    }
+
     fn generate_without_blockhash(
         &mut self,
         destinations: Option<Vec<&Keypair>>, // provided for valid signatures
@@ -235,6 +297,45 @@ impl TransactionGenerator {
     }
 }
 
+/// Sonic: create the accounts migrated by the `RemoteMigration` and `RemoteRefresh`
+/// transaction types on the source cluster, returning them with the hypergrid node they are
+/// refreshed from
+fn create_remote_accounts(transaction_params: &TransactionParams) -> (Vec<Pubkey>, Pubkey) {
+    let Some(source_url) = transaction_params.remote_source_url.as_ref() else {
+        return (vec![], Pubkey::default());
+    };
+    if !matches!(
+        transaction_params.transaction_type,
+        Some(TransactionType::RemoteMigration | TransactionType::RemoteRefresh)
+    ) {
+        return (vec![], Pubkey::default());
+    }
+
+    let source_client =
+        RpcClient::new_with_commitment(source_url.clone(), CommitmentConfig::confirmed());
+    let remote_source_node = transaction_params.remote_source_node.unwrap_or_else(|| {
+        source_client.get_identity().unwrap_or_else(|e| {
+            eprintln!("Error could not get the source cluster identity: {e:?}");
+            exit(1);
+        })
+    });
+    // the funding key is airdropped the lamports of the accounts on the source cluster
+    let funding_key = Keypair::new();
+    let remote_accounts = create_source_accounts(
+        &source_client,
+        &funding_key,
+        transaction_params.num_remote_accounts.unwrap(),
+        transaction_params
+            .remote_account_size
+            .unwrap_or(DEFAULT_REMOTE_ACCOUNT_SIZE),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("Error could not create the remote accounts: {e:?}");
+        exit(1);
+    });
+    (remote_accounts, remote_source_node)
+}
+
 // Multithreading-related functions
 //
 // The most computationally expensive work is signing new transactions.
@@ -1005,6 +1106,10 @@ pub mod test {
                     unique_transactions: false,
                     transaction_type: None,
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1032,6 +1137,10 @@ pub mod test {
                     unique_transactions: true,
                     transaction_type: None,
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1059,6 +1168,10 @@ pub mod test {
                     unique_transactions: true,
                     transaction_type: None,
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic: false,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1145,6 +1258,10 @@ pub mod test {
                     unique_transactions: false,
                     transaction_type: Some(TransactionType::Transfer),
                     num_instructions: Some(1),
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1174,6 +1291,10 @@ pub mod test {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::Transfer),
                     num_instructions: Some(1),
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1202,6 +1323,10 @@ pub mod test {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::Transfer),
                     num_instructions: Some(8),
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1230,6 +1355,10 @@ pub mod test {
                     unique_transactions: true,
                     transaction_type: Some(TransactionType::AccountCreation),
                     num_instructions: None,
+                    num_remote_accounts: None,
+                    remote_account_size: None,
+                    remote_source_url: None,
+                    remote_source_node: None,
                 },
                 tpu_use_quic,
                 send_batch_size: TEST_SEND_BATCH_SIZE,
@@ -1237,6 +1366,101 @@ pub mod test {
         );
     }
 
+    fn run_dos_with_remote_accounts(tpu_use_quic: bool) {
+        solana_logger::setup();
+
+        // 1. Create faucet thread
+        let faucet_keypair = Keypair::new();
+        let faucet_pubkey = faucet_keypair.pubkey();
+        let faucet_addr = run_local_faucet(faucet_keypair, None);
+
+        // 2. Create a local cluster which is aware of faucet, it is also the source cluster
+        // the remote accounts are created on
+        let num_nodes = 1;
+        let cluster = LocalCluster::new(
+            &mut ClusterConfig {
+                node_stakes: vec![999_990; num_nodes],
+                cluster_lamports: 200_000_000,
+                validator_configs: make_identical_validator_configs(
+                    &ValidatorConfig {
+                        rpc_config: JsonRpcConfig {
+                            faucet_addr: Some(faucet_addr),
+                            ..JsonRpcConfig::default_for_test()
+                        },
+                        ..ValidatorConfig::default_for_test()
+                    },
+                    num_nodes,
+                ),
+                ..ClusterConfig::default()
+            },
+            SocketAddrSpace::Unspecified,
+        );
+        assert_eq!(cluster.validators.len(), num_nodes);
+
+        // 3. Transfer funds to faucet account
+        cluster.transfer(&cluster.funding_keypair, &faucet_pubkey, 100_000_000);
+
+        let nodes = cluster.get_node_pubkeys();
+        let node = cluster
+            .get_contact_info(&nodes[0])
+            .map(LegacyContactInfo::try_from)
+            .unwrap()
+            .unwrap();
+        let nodes_slice = [node];
+        let remote_source_url = format!("http://{}", cluster.entry_point_info.rpc().unwrap());
+
+        let client = Arc::new(ThinClient::new(
+            cluster.entry_point_info.rpc().unwrap(),
+            cluster
+                .entry_point_info
+                .tpu(cluster.connection_cache.protocol())
+                .unwrap(),
+            cluster.connection_cache.clone(),
+        ));
+
+        // creates and sends unique transactions of each remote account type, migrating
+        // random accounts which exist on no cluster, then accounts created on the source
+        // cluster, then refreshing them from the node
+        for transaction_type in [
+            TransactionType::RemoteNonexistent,
+            TransactionType::RemoteMigration,
+            TransactionType::RemoteRefresh,
+        ] {
+            let remote_source_url = (transaction_type != TransactionType::RemoteNonexistent)
+                .then(|| remote_source_url.clone());
+            run_dos(
+                &nodes_slice,
+                10,
+                Some(client.clone()),
+                DosClientParameters {
+                    entrypoint_addr: cluster.entry_point_info.gossip().unwrap(),
+                    mode: Mode::Tpu,
+                    data_size: 0, // irrelevant if not random
+                    data_type: DataType::Transaction,
+                    data_input: None,
+                    skip_gossip: false,
+                    allow_private_addr: false,
+                    num_gen_threads: 1,
+                    transaction_params: TransactionParams {
+                        num_signatures: None,
+                        valid_blockhash: true,
+                        valid_signatures: true,
+                        unique_transactions: true,
+                        transaction_type: Some(transaction_type),
+                        num_instructions: None,
+                        num_remote_accounts: Some(4),
+                        // rent exempt empty accounts, so that the faucet can fund them
+                        remote_account_size: Some(0),
+                        remote_source_url,
+                        remote_source_node: Some(nodes[0]),
+                    },
+                    tpu_use_quic,
+                    send_batch_size: TEST_SEND_BATCH_SIZE,
+                },
+            );
+        }
+    }
+
     #[test]
     fn test_dos_with_blockhash_and_payer() {
         run_dos_with_blockhash_and_payer(/*tpu_use_quic*/ false)
@@ -1246,4 +1470,9 @@ pub mod test {
     fn test_dos_with_blockhash_and_payer_and_quic() {
         run_dos_with_blockhash_and_payer(/*tpu_use_quic*/ true)
     }
+
+    #[test]
+    fn test_dos_with_remote_accounts() {
+        run_dos_with_remote_accounts(/*tpu_use_quic*/ false)
+    }
 }
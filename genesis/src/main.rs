@@ -3,15 +3,20 @@
 
 use {
     base64::{prelude::BASE64_STANDARD, Engine},
-    clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg, ArgMatches},
+    clap::{
+        crate_description, crate_name, value_t, value_t_or_exit, values_t, App, Arg, ArgMatches,
+    },
     itertools::Itertools,
-    solana_accounts_db::{hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE, inline_spl_token, inline_spl_token_2022},
+    solana_accounts_db::{
+        hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE, synthetic_accounts::SyntheticAccounts,
+    },
     solana_clap_utils::{
         input_parsers::{
             cluster_type_of, pubkey_of, pubkeys_of, unix_timestamp_from_rfc3339_datetime,
         },
         input_validators::{
-            is_pubkey_or_keypair, is_rfc3339_datetime, is_slot, is_valid_percentage,
+            is_pubkey, is_pubkey_or_keypair, is_rfc3339_datetime, is_slot, is_url,
+            is_valid_percentage,
         },
     },
     solana_entry::poh::compute_hashes_per_tick,
@@ -20,8 +25,9 @@ use {
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
         bpf_loader_upgradeable::UpgradeableLoaderState,
-        clock,
+        clock::{self, Slot},
         epoch_schedule::EpochSchedule,
+        fee::FeeStructure,
         fee_calculator::FeeRateGovernor,
         genesis_config::{ClusterType, GenesisConfig},
         inflation::Inflation,
@@ -31,11 +37,32 @@ use {
         rent::Rent,
         signature::{Keypair, Signer},
         signer::keypair::read_keypair_file,
+        sonic_account_migrater::{
+            self,
+            state::{derive_genesis_clone_record_address, GenesisCloneRecord},
+        },
+        sonic_bridge::{
+            self,
+            state::{
+                derive_bridge_config_address, derive_bridge_vault_address, derive_outbox_address,
+                BridgeConfig, BridgeState, Outbox, BRIDGE_CONFIG_SIZE, OUTBOX_SIZE,
+            },
+        },
+        sonic_fee_settlement::{
+            self,
+            instruction::derive_fee_multiplier_address,
+            state::{
+                is_valid_fee_multiplier, DynamicFeeMultiplier, FeeMultiplierConfig,
+                SettlementState, FEE_MULTIPLIER_CONFIG_SIZE, MAX_FEE_MULTIPLIER,
+                MIN_FEE_MULTIPLIER,
+            },
+        },
         stake::state::StakeStateV2,
         system_program, timing,
     },
     solana_stake_program::stake_state,
     solana_vote_program::vote_state::{self, VoteState},
+    sonic_hypergrid::{config::Config as HypergridConfig, genesis_clone::GenesisCloner},
     std::{
         collections::HashMap,
         error,
@@ -62,6 +89,121 @@ fn pubkey_from_str(key_str: &str) -> Result<Pubkey, Box<dyn error::Error>> {
     })
 }
 
+fn is_fee_multiplier(value: String) -> Result<(), String> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|multiplier| is_valid_fee_multiplier(*multiplier))
+        .map(|_| ())
+        .ok_or_else(|| {
+            format!(
+                "fee multiplier should be between {MIN_FEE_MULTIPLIER} and {MAX_FEE_MULTIPLIER}"
+            )
+        })
+}
+
+fn is_target_utilization(value: String) -> Result<(), String> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|percentage| (1..=100).contains(percentage))
+        .map(|_| ())
+        .ok_or_else(|| "target utilization should be between 1 and 100".to_string())
+}
+
+/// Sonic: create the fee multiplier config account read by every bank.
+fn add_fee_multiplier_config(
+    genesis_config: &mut GenesisConfig,
+    authority: Pubkey,
+    multiplier: u32,
+    dynamic: Option<DynamicFeeMultiplier>,
+) -> Result<(), Box<dyn error::Error>> {
+    let mut config = FeeMultiplierConfig::new(authority, multiplier, dynamic);
+    if let Some(dynamic) = dynamic {
+        config.active_multiplier = multiplier.clamp(dynamic.min_multiplier, dynamic.max_multiplier);
+    }
+    if !config.is_valid() {
+        return Err(format!("invalid fee multiplier config: {config:?}").into());
+    }
+    let mut account = AccountSharedData::new(
+        genesis_config
+            .rent
+            .minimum_balance(FEE_MULTIPLIER_CONFIG_SIZE),
+        FEE_MULTIPLIER_CONFIG_SIZE,
+        &sonic_fee_settlement::program::id(),
+    );
+    account.serialize_data(&SettlementState::FeeMultiplier(config))?;
+    let (address, _) = derive_fee_multiplier_address();
+    genesis_config.add_account(address, account);
+    Ok(())
+}
+
+/// Sonic: create the bridge config, the vault SOL deposits made on L1 are paid from, and the
+/// outbox of the messages sent to L1.
+fn add_bridge_accounts(
+    genesis_config: &mut GenesisConfig,
+    authority: Pubkey,
+    vault_lamports: u64,
+) -> Result<(), Box<dyn error::Error>> {
+    let mut config_account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(BRIDGE_CONFIG_SIZE),
+        BRIDGE_CONFIG_SIZE,
+        &sonic_bridge::program::id(),
+    );
+    config_account.serialize_data(&BridgeState::Config(BridgeConfig {
+        authority,
+        deposit_count: 0,
+        deposited_lamports: 0,
+    }))?;
+    genesis_config.add_account(derive_bridge_config_address().0, config_account);
+
+    let vault_account = AccountSharedData::new(
+        genesis_config
+            .rent
+            .minimum_balance(0)
+            .saturating_add(vault_lamports),
+        0,
+        &sonic_bridge::program::id(),
+    );
+    genesis_config.add_account(derive_bridge_vault_address().0, vault_account);
+
+    let mut outbox_account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(OUTBOX_SIZE),
+        OUTBOX_SIZE,
+        &sonic_bridge::program::id(),
+    );
+    outbox_account.serialize_data(&BridgeState::Outbox(Outbox { next_message_id: 0 }))?;
+    genesis_config.add_account(derive_outbox_address().0, outbox_account);
+    Ok(())
+}
+
+/// Sonic: record the accounts cloned into genesis, and the slot they were read at.
+fn add_genesis_clone_record(
+    genesis_config: &mut GenesisConfig,
+    record: &GenesisCloneRecord,
+) -> Result<(), Box<dyn error::Error>> {
+    let data = bincode::serialize(record)?;
+    let account = AccountSharedData::from(Account {
+        lamports: genesis_config.rent.minimum_balance(data.len()),
+        data,
+        owner: sonic_account_migrater::program::id(),
+        executable: false,
+        rent_epoch: 0,
+    });
+    let (address, _) = derive_genesis_clone_record_address();
+    genesis_config.add_account(address, account);
+    Ok(())
+}
+
+/// Sonic: the hypergrid configuration at its default path.
+fn load_hypergrid_config() -> HypergridConfig {
+    let config_path = HypergridConfig::default_path();
+    HypergridConfig::load(&config_path).unwrap_or_else(|err| {
+        eprintln!("Error: failed to load the hypergrid config {config_path}: {err}");
+        process::exit(1);
+    })
+}
+
 pub fn load_genesis_accounts(file: &str, genesis_config: &mut GenesisConfig) -> io::Result<u64> {
     let mut lamports = 0;
     let accounts_file = File::open(file)?;
@@ -279,6 +421,87 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .help("percentage of collected fee to burn")
                 .validator(is_valid_percentage),
         )
+        .arg(
+            Arg::with_name("fee_multiplier")
+                .long("fee-multiplier")
+                .value_name("MULTIPLIER")
+                .takes_value(true)
+                .validator(is_fee_multiplier)
+                .help(
+                    "Sonic: fee multiplier in units of 1/10000, e.g. 10000 charges the \
+                     base fees [default: SONIC_FEE_MULTIPLIER at build time, or 10000]",
+                ),
+        )
+        .arg(
+            Arg::with_name("fee_multiplier_authority")
+                .long("fee-multiplier-authority")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey_or_keypair)
+                .help(
+                    "Sonic: authority allowed to change the fee multiplier once governance \
+                     is activated [default: the first bootstrap validator identity]",
+                ),
+        )
+        .arg(
+            Arg::with_name("dynamic_fee_multiplier")
+                .long("dynamic-fee-multiplier")
+                .value_name("MIN_MULTIPLIER MAX_MULTIPLIER")
+                .takes_value(true)
+                .number_of_values(2)
+                .validator(is_fee_multiplier)
+                .help(
+                    "Sonic: let the fee multiplier follow the block utilization, \
+                     between the given bounds",
+                ),
+        )
+        .arg(
+            Arg::with_name("fee_multiplier_target_utilization")
+                .long("fee-multiplier-target-utilization")
+                .value_name("PERCENTAGE")
+                .takes_value(true)
+                .default_value("50")
+                .requires("dynamic_fee_multiplier")
+                .validator(is_target_utilization)
+                .help(
+                    "Sonic: block utilization at which the dynamic fee multiplier \
+                     stays unchanged",
+                ),
+        )
+        .arg(
+            Arg::with_name("bridge_authority")
+                .long("bridge-authority")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey_or_keypair)
+                .help(
+                    "Sonic: authority allowed to credit the deposits made on L1, and to \
+                     write the receipts of the messages sent to L1",
+                ),
+        )
+        .arg(
+            Arg::with_name("bridge_vault_lamports")
+                .long("bridge-vault-lamports")
+                .value_name("LAMPORTS")
+                .takes_value(true)
+                .default_value("0")
+                .requires("bridge_authority")
+                .help("Sonic: lamports of the vault SOL deposits made on L1 are paid from"),
+        )
+        .arg(
+            Arg::with_name("sequencer")
+                .long("sequencer")
+                .value_name("IDENTITY_PUBKEY")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1)
+                .validator(is_pubkey_or_keypair)
+                .help(
+                    "Sonic: run the grid with a single leader rooting its blocks without \
+                     votes, disabling the vote program [default: the first bootstrap \
+                     validator]",
+                ),
+        )
         .arg(
             Arg::with_name("vote_commission_percentage")
                 .long("vote-commission-percentage")
@@ -399,6 +622,59 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .possible_values(&["pico", "full", "none"])
                 .help("Selects inflation"),
         )
+        .arg(
+            Arg::with_name("clone_account")
+                .long("clone")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .multiple(true)
+                .requires("clone_slot")
+                .help("Copy an account from the base layer into the genesis configuration"),
+        )
+        .arg(
+            Arg::with_name("clone_upgradeable_program")
+                .long("clone-upgradeable-program")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .multiple(true)
+                .requires("clone_slot")
+                .help(
+                    "Copy an upgradeable program and its executable data from the base layer \
+                     into the genesis configuration",
+                ),
+        )
+        .arg(
+            Arg::with_name("clone_slot")
+                .long("clone-slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .help(
+                    "Slot of the base layer to clone the accounts at. RPC nodes only serve \
+                     their latest state, so the accounts are read at this slot or later, \
+                     and the slot each one was read at is recorded in genesis",
+                ),
+        )
+        .arg(
+            Arg::with_name("clone_url")
+                .long("clone-url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .conflicts_with("clone_from_hssn")
+                .help(
+                    "Base layer RPC node to clone the accounts from \
+                     [default: the baselayer_rpc_url of the hypergrid config]",
+                ),
+        )
+        .arg(
+            Arg::with_name("clone_from_hssn")
+                .long("clone-from-hssn")
+                .takes_value(false)
+                .help("Clone the accounts recorded in the HSSN of the hypergrid config"),
+        )
         .get_matches();
 
     let ledger_path = PathBuf::from(matches.value_of("ledger_path").unwrap());
@@ -691,25 +967,79 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         }
     }
 
-    // Sonic: Add the native mint
-    let native_mint_account = solana_sdk::account::AccountSharedData::from(Account {
-        owner: inline_spl_token::id(),
-        data: inline_spl_token::native_mint::ACCOUNT_DATA.to_vec(),
-        lamports: sol_to_lamports(1.),
-        executable: false,
-        rent_epoch: 18446744073709551615,
+    // Sonic: Add the synthetic accounts, the native mints unless configured otherwise
+    let synthetic_accounts = SyntheticAccounts::new_from_default_config().unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        process::exit(1);
     });
-    genesis_config.add_account(inline_spl_token::native_mint::id(), native_mint_account);
+    for (pubkey, synthetic) in synthetic_accounts.iter() {
+        genesis_config.add_account(*pubkey, synthetic.account.clone());
+    }
 
-    // Sonic: Add the native mint 2022
-    let native_mint_account = solana_sdk::account::AccountSharedData::from(Account {
-        owner: inline_spl_token_2022::id(),
-        data: inline_spl_token::native_mint::ACCOUNT_DATA.to_vec(),
-        lamports: sol_to_lamports(1.),
-        executable: false,
-        rent_epoch: 18446744073709551615,
-    });
-    genesis_config.add_account(inline_spl_token_2022::native_mint::id(), native_mint_account);
+    // Sonic: Clone accounts and upgradeable programs from the base layer
+    let clone_accounts = pubkeys_of(&matches, "clone_account").unwrap_or_default();
+    let clone_programs = pubkeys_of(&matches, "clone_upgradeable_program").unwrap_or_default();
+    if !clone_accounts.is_empty() || !clone_programs.is_empty() {
+        let slot = value_t_or_exit!(matches, "clone_slot", Slot);
+        let mut cloner = if matches.is_present("clone_from_hssn") {
+            GenesisCloner::new_with_hssn(load_hypergrid_config(), slot)
+        } else {
+            let url = matches
+                .value_of("clone_url")
+                .map(str::to_string)
+                .unwrap_or_else(|| load_hypergrid_config().baselayer_rpc_url);
+            GenesisCloner::new_with_rpc(url, slot)
+        };
+        clone_accounts
+            .into_iter()
+            .try_for_each(|pubkey| cloner.clone_account(pubkey))
+            .and_then(|()| {
+                clone_programs
+                    .into_iter()
+                    .try_for_each(|program_id| cloner.clone_upgradeable_program(program_id))
+            })
+            .unwrap_or_else(|err| {
+                eprintln!("Error: failed to clone from the base layer: {err}");
+                process::exit(1);
+            });
+        let (accounts, record) = cloner.finish();
+        for (pubkey, account) in accounts {
+            genesis_config.add_account(pubkey, account);
+        }
+        add_genesis_clone_record(&mut genesis_config, &record)?;
+    }
+
+    if matches.is_present("fee_multiplier")
+        || matches.is_present("fee_multiplier_authority")
+        || matches.is_present("dynamic_fee_multiplier")
+    {
+        let multiplier = value_t!(matches, "fee_multiplier", u32)
+            .unwrap_or_else(|_| FeeStructure::default_fee_multiplier());
+        let authority = pubkey_of(&matches, "fee_multiplier_authority")
+            .unwrap_or(bootstrap_validator_pubkeys[0]);
+        let dynamic = values_t!(matches, "dynamic_fee_multiplier", u32)
+            .ok()
+            .map(|bounds| DynamicFeeMultiplier {
+                min_multiplier: bounds[0],
+                max_multiplier: bounds[1],
+                target_utilization_percent: value_t_or_exit!(
+                    matches,
+                    "fee_multiplier_target_utilization",
+                    u8
+                ),
+            });
+        add_fee_multiplier_config(&mut genesis_config, authority, multiplier, dynamic)?;
+    }
+
+    if let Some(authority) = pubkey_of(&matches, "bridge_authority") {
+        let vault_lamports = value_t_or_exit!(matches, "bridge_vault_lamports", u64);
+        add_bridge_accounts(&mut genesis_config, authority, vault_lamports)?;
+    }
+
+    if matches.is_present("sequencer") {
+        let sequencer = pubkey_of(&matches, "sequencer").unwrap_or(bootstrap_validator_pubkeys[0]);
+        solana_runtime::genesis_utils::activate_sequencer_mode(&mut genesis_config, sequencer);
+    }
 
     solana_logger::setup();
     create_new_ledger(
@@ -122,6 +122,65 @@ pub enum ReplicaAccountInfoVersions<'a> {
     V0_0_3(&'a ReplicaAccountInfoV3<'a>),
 }
 
+/// Sonic: How a remote account changed in the remote account cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RemoteAccountEventKind {
+    /// The account was loaded from its source into the cache
+    Load,
+    /// The account replaced an older copy in the cache
+    Refresh,
+    /// The account was removed from the cache
+    Evict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(C)]
+/// Sonic: Information about a remote account loaded into, refreshed in or
+/// evicted from the remote account cache
+pub struct ReplicaRemoteAccountInfo<'a> {
+    /// The Pubkey for the account
+    pub pubkey: &'a [u8],
+
+    /// The lamports for the account
+    pub lamports: u64,
+
+    /// The Pubkey of the owner program account
+    pub owner: &'a [u8],
+
+    /// This account's data contains a loaded program (and is now read-only)
+    pub executable: bool,
+
+    /// The epoch at which this account will next owe rent
+    pub rent_epoch: u64,
+
+    /// The data held in this account. For an eviction, the data of the
+    /// account as it was in the cache.
+    pub data: &'a [u8],
+
+    /// Whether the account was migrated from a remote chain
+    pub remote: bool,
+
+    /// The Pubkey of the hypergrid node the account was migrated from,
+    /// None for the base layer
+    pub source: Option<&'a [u8]>,
+
+    /// The slot the account was read at on its source, if known
+    pub source_slot: Option<Slot>,
+
+    /// What happened to the account in the cache
+    pub kind: RemoteAccountEventKind,
+}
+
+/// A wrapper to future-proof ReplicaRemoteAccountInfo handling.
+/// If there were a change to the structure of ReplicaRemoteAccountInfo,
+/// there would be new enum entry for the newer version, forcing
+/// plugin implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaRemoteAccountInfoVersions<'a> {
+    V0_0_1(&'a ReplicaRemoteAccountInfo<'a>),
+}
+
 /// Information about a transaction
 #[derive(Clone, Debug)]
 #[repr(C)]
@@ -418,6 +477,13 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Sonic: Called when a remote account is loaded into, refreshed in or
+    /// evicted from the remote account cache.
+    #[allow(unused_variables)]
+    fn notify_remote_account(&self, account: ReplicaRemoteAccountInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
     /// Check if the plugin is interested in account data
     /// Default is true -- if the plugin is not interested in
     /// account data, please return false.
@@ -438,4 +504,11 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
     fn entry_notifications_enabled(&self) -> bool {
         false
     }
+
+    /// Sonic: Check if the plugin is interested in remote account data
+    /// Default is false -- if the plugin is interested in the loads,
+    /// refreshes and evictions of remote accounts, return true.
+    fn remote_account_notifications_enabled(&self) -> bool {
+        false
+    }
 }
@@ -4,10 +4,14 @@ use {
     log::*,
     solana_accounts_db::{
         account_storage::meta::StoredAccountMeta,
-        accounts_update_notifier_interface::AccountsUpdateNotifierInterface,
+        accounts_update_notifier_interface::{
+            AccountsUpdateNotifierInterface, RemoteAccountEvent,
+            RemoteAccountEventKind as CacheEventKind,
+        },
     },
     solana_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaAccountInfoV3, ReplicaAccountInfoVersions,
+        RemoteAccountEventKind, ReplicaAccountInfoV3, ReplicaAccountInfoVersions,
+        ReplicaRemoteAccountInfo, ReplicaRemoteAccountInfoVersions,
     },
     solana_measure::measure::Measure,
     solana_metrics::*,
@@ -97,6 +101,63 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
             );
         }
     }
+
+    fn notify_remote_account(&self, event: &RemoteAccountEvent) {
+        let mut measure = Measure::start("geyser-plugin-notify-remote-account");
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let source = event.origin.source;
+        let account = ReplicaRemoteAccountInfo {
+            pubkey: event.pubkey.as_ref(),
+            lamports: event.account.lamports(),
+            owner: event.account.owner().as_ref(),
+            executable: event.account.executable(),
+            rent_epoch: event.account.rent_epoch(),
+            data: event.account.data(),
+            remote: event.account.remote,
+            source: source.as_ref().map(|source| source.as_ref()),
+            source_slot: event.origin.source_slot,
+            kind: match event.kind {
+                CacheEventKind::Load => RemoteAccountEventKind::Load,
+                CacheEventKind::Refresh => RemoteAccountEventKind::Refresh,
+                CacheEventKind::Evict => RemoteAccountEventKind::Evict,
+            },
+        };
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.remote_account_notifications_enabled() {
+                continue;
+            }
+            match plugin.notify_remote_account(ReplicaRemoteAccountInfoVersions::V0_0_1(&account)) {
+                Err(err) => {
+                    error!(
+                        "Failed to notify remote account {} ({:?}), error: {} to plugin {}",
+                        event.pubkey,
+                        event.kind,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified remote account {} ({:?}) to plugin {}",
+                        event.pubkey,
+                        event.kind,
+                        plugin.name()
+                    );
+                }
+            }
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify-remote-account-us",
+            measure.as_us() as usize,
+            100000,
+            100000
+        );
+    }
 }
 
 impl AccountsUpdateNotifierImpl {
@@ -152,6 +213,9 @@ impl AccountsUpdateNotifierImpl {
             return;
         }
         for plugin in plugin_manager.plugins.iter() {
+            if !plugin.account_data_notifications_enabled() {
+                continue;
+            }
             let mut measure = Measure::start("geyser-plugin-update-account");
             match plugin.update_account(
                 ReplicaAccountInfoVersions::V0_0_3(&account),
@@ -100,6 +100,16 @@ impl GeyserPluginManager {
         false
     }
 
+    /// Sonic: Check if there is any plugin interested in remote account data
+    pub fn remote_account_notifications_enabled(&self) -> bool {
+        for plugin in &self.plugins {
+            if plugin.remote_account_notifications_enabled() {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Admin RPC request handler
     pub(crate) fn list_plugins(&self) -> JsonRpcResult<Vec<String>> {
         Ok(self.plugins.iter().map(|p| p.name().to_owned()).collect())
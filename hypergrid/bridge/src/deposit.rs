@@ -0,0 +1,472 @@
+//! Watcher of the deposits made to the L1 escrow program.
+//!
+//! The escrow program logs a [`DepositEvent`] with `sol_log_data` for every deposit. The
+//! watcher polls the finalized signatures of the escrow program, waits for them to be
+//! `confirmation_depth` slots below the finalized slot, then credits every deposit event of the
+//! transaction on the grid. Transactions are persisted before they are credited, and deposits whose record
+//! already exists on the grid are skipped, so the watcher can be restarted at any time.
+
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        sonic_bridge::{
+            instruction::{credit_deposit, credit_token_deposit},
+            state::{derive_deposit_record_address, DepositEvent, L1Signature},
+        },
+        transaction::Transaction,
+    },
+    solana_transaction_status::UiTransactionEncoding,
+    sonic_hypergrid::config::Config,
+    std::{
+        collections::HashMap,
+        fs::{self, File},
+        io::{self, BufReader, BufWriter},
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    },
+};
+
+pub const DEPOSIT_LEDGER_FILE: &str = "bridge_deposits.json";
+
+/// Maximum number of signatures requested at once.
+const SIGNATURES_PAGE_SIZE: usize = 1_000;
+
+/// An L1 transaction of the escrow program, waiting to be credited.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PendingDeposit {
+    pub signature: String,
+    pub slot: Slot,
+}
+
+/// Persistent state of the deposit watcher.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct DepositLedger {
+    /// The latest transaction of the escrow program seen.
+    pub last_signature: Option<String>,
+    /// Transactions not credited yet, in L1 order.
+    pub pending: Vec<PendingDeposit>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl DepositLedger {
+    /// Load the ledger from `dir`, starting a new one if none exists.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(DEPOSIT_LEDGER_FILE);
+        let mut ledger = if path.exists() {
+            let file = File::open(&path)?;
+            serde_json::from_reader(BufReader::new(file))
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?
+        } else {
+            Self::default()
+        };
+        ledger.path = path;
+        Ok(ledger)
+    }
+
+    /// Atomically write the ledger back to disk.
+    pub fn save(&self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let file = File::create(&tmp_path)?;
+            serde_json::to_writer(BufWriter::new(file), self)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+        }
+        fs::rename(tmp_path, &self.path)
+    }
+
+    /// Queue the transactions seen after `last_signature`, given newest first like
+    /// `getSignaturesForAddress` returns them.
+    pub fn add_signatures(&mut self, newest_first: Vec<PendingDeposit>) {
+        if let Some(newest) = newest_first.first() {
+            self.last_signature = Some(newest.signature.clone());
+        }
+        self.pending.extend(newest_first.into_iter().rev());
+    }
+
+    /// The pending transactions at least `confirmation_depth` slots below `slot`.
+    pub fn confirmed(&self, slot: Slot, confirmation_depth: u64) -> Vec<PendingDeposit> {
+        self.pending
+            .iter()
+            .filter(|deposit| deposit.slot.saturating_add(confirmation_depth) <= slot)
+            .cloned()
+            .collect()
+    }
+
+    pub fn remove(&mut self, signature: &str) {
+        self.pending
+            .retain(|deposit| deposit.signature != signature);
+    }
+}
+
+/// The deposit events logged by `escrow_program_id` in the logs of a transaction, in order.
+/// Data logged by the programs it invokes, or by other programs, is ignored.
+pub fn parse_deposit_events(
+    log_messages: &[String],
+    escrow_program_id: &Pubkey,
+) -> Vec<DepositEvent> {
+    let escrow_program_id = escrow_program_id.to_string();
+    let mut invoke_stack: Vec<&str> = vec![];
+    let mut events = vec![];
+    for log in log_messages {
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if invoke_stack.last() != Some(&escrow_program_id.as_str()) {
+                continue;
+            }
+            let fields: Result<Vec<_>, _> = data
+                .split(' ')
+                .map(|field| BASE64_STANDARD.decode(field))
+                .collect();
+            if let Some(event) = fields.ok().and_then(|fields| DepositEvent::decode(&fields)) {
+                events.push(event);
+            }
+        } else if let Some(invoke) = log.strip_prefix("Program ") {
+            let mut words = invoke.split(' ');
+            match (words.next(), words.next()) {
+                (Some(program_id), Some("invoke")) => invoke_stack.push(program_id),
+                (Some(program_id), Some("success" | "failed:"))
+                    if invoke_stack.last() == Some(&program_id) =>
+                {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+/// Credits the deposits made to the L1 escrow program on the grid.
+pub struct DepositWatcher {
+    l1_client: RpcClient,
+    grid_client: RpcClient,
+    authority: Keypair,
+    escrow_program_id: Pubkey,
+    confirmation_depth: u64,
+    /// Grid mint of each L1 mint.
+    token_mints: HashMap<Pubkey, Pubkey>,
+    ledger: DepositLedger,
+}
+
+impl DepositWatcher {
+    pub fn new(config: &Config, ledger_dir: &Path) -> Result<Self, String> {
+        let bridge = &config.bridge;
+        let authority = read_keypair_file(&bridge.authority_keypair_file).map_err(|err| {
+            format!(
+                "failed to read bridge authority keypair {}: {err}",
+                bridge.authority_keypair_file
+            )
+        })?;
+        let escrow_program_id = Pubkey::from_str(&bridge.escrow_program_id).map_err(|err| {
+            format!(
+                "invalid escrow program {:?}: {err}",
+                bridge.escrow_program_id
+            )
+        })?;
+        let parse =
+            |address: &String| Pubkey::from_str(address).map_err(|err| format!("{address}: {err}"));
+        let token_mints = bridge
+            .token_mints
+            .iter()
+            .map(|(l1_mint, grid_mint)| Ok((parse(l1_mint)?, parse(grid_mint)?)))
+            .collect::<Result<_, String>>()?;
+        let ledger = DepositLedger::open(ledger_dir).map_err(|err| {
+            format!(
+                "failed to open the deposit ledger in {}: {err}",
+                ledger_dir.display()
+            )
+        })?;
+        Ok(Self {
+            // Deposits are only credited once final on L1, a rolled back deposit would
+            // leave the grid with unbacked funds
+            l1_client: RpcClient::new_with_timeout_and_commitment(
+                config.l1_rpc_url().to_string(),
+                Duration::from_secs(30),
+                CommitmentConfig::finalized(),
+            ),
+            grid_client: RpcClient::new_with_timeout_and_commitment(
+                bridge.grid_rpc_url.clone(),
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+            authority,
+            escrow_program_id,
+            confirmation_depth: bridge.confirmation_depth,
+            token_mints,
+            ledger,
+        })
+    }
+
+    pub fn ledger(&self) -> &DepositLedger {
+        &self.ledger
+    }
+
+    /// Queue the new finalized transactions of the escrow program, then credit the ones
+    /// `confirmation_depth` slots below the finalized slot.
+    pub fn poll(&mut self) -> Result<(), String> {
+        self.fetch_new_deposits()?;
+        let slot = self
+            .l1_client
+            .get_slot_with_commitment(CommitmentConfig::finalized())
+            .map_err(|err| format!("failed to get the L1 slot: {err}"))?;
+        for deposit in self.ledger.confirmed(slot, self.confirmation_depth) {
+            if let Err(err) = self.credit_transaction(&deposit) {
+                warn!(
+                    "failed to credit the deposits of {}: {err}",
+                    deposit.signature
+                );
+                continue;
+            }
+            self.ledger.remove(&deposit.signature);
+            self.save_ledger()?;
+        }
+        Ok(())
+    }
+
+    fn save_ledger(&self) -> Result<(), String> {
+        self.ledger
+            .save()
+            .map_err(|err| format!("failed to save the deposit ledger: {err}"))
+    }
+
+    /// Queue the successful transactions of the escrow program since the last poll.
+    fn fetch_new_deposits(&mut self) -> Result<(), String> {
+        let until = self
+            .ledger
+            .last_signature
+            .as_deref()
+            .map(Signature::from_str)
+            .transpose()
+            .map_err(|err| format!("invalid last signature: {err}"))?;
+        let mut new_signatures = vec![];
+        let mut before = None;
+        loop {
+            let page = self
+                .l1_client
+                .get_signatures_for_address_with_config(
+                    &self.escrow_program_id,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until,
+                        limit: Some(SIGNATURES_PAGE_SIZE),
+                        commitment: Some(CommitmentConfig::finalized()),
+                    },
+                )
+                .map_err(|err| format!("failed to get the escrow program signatures: {err}"))?;
+            let Some(oldest) = page.last() else {
+                break;
+            };
+            before = Some(
+                Signature::from_str(&oldest.signature)
+                    .map_err(|err| format!("invalid signature {}: {err}", oldest.signature))?,
+            );
+            let full_page = page.len() == SIGNATURES_PAGE_SIZE;
+            new_signatures.extend(page);
+            if !full_page {
+                break;
+            }
+        }
+        if new_signatures.is_empty() {
+            return Ok(());
+        }
+
+        // Failed transactions are queued too, so that `last_signature` moves past them, but
+        // they have no deposit events to credit
+        let new_deposits: Vec<_> = new_signatures
+            .into_iter()
+            .map(|status| PendingDeposit {
+                signature: status.signature,
+                slot: status.slot,
+            })
+            .collect();
+        info!("{} new escrow program transactions", new_deposits.len());
+        self.ledger.add_signatures(new_deposits);
+        self.save_ledger()
+    }
+
+    /// Credit the deposit events of an L1 transaction that are not credited yet.
+    fn credit_transaction(&self, deposit: &PendingDeposit) -> Result<(), String> {
+        let signature = Signature::from_str(&deposit.signature)
+            .map_err(|err| format!("invalid signature: {err}"))?;
+        let transaction = self
+            .l1_client
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::finalized()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .map_err(|err| format!("failed to get the transaction: {err}"))?;
+        let Some(meta) = transaction.transaction.meta else {
+            return Err("the transaction has no status".to_string());
+        };
+        if meta.err.is_some() {
+            return Ok(());
+        }
+        let log_messages: Option<Vec<String>> = meta.log_messages.into();
+        let events =
+            parse_deposit_events(&log_messages.unwrap_or_default(), &self.escrow_program_id);
+
+        let l1_signature = L1Signature::new(signature.into());
+        for (event_index, event) in events.into_iter().enumerate() {
+            let Ok(event_index) = u8::try_from(event_index) else {
+                warn!("{signature}: deposits after the 256th are not credited");
+                break;
+            };
+            let (record_address, _) = derive_deposit_record_address(&l1_signature, event_index);
+            let record = self
+                .grid_client
+                .get_account_with_commitment(&record_address, CommitmentConfig::confirmed())
+                .map_err(|err| format!("failed to get the deposit record {record_address}: {err}"))?
+                .value;
+            if record.is_some() {
+                info!("deposit {event_index} of {signature} is already credited");
+                continue;
+            }
+            let Some(instruction) = self.credit_instruction(l1_signature, event_index, &event)
+            else {
+                warn!(
+                    "deposit {event_index} of {signature}: no grid mint for {:?}, skipped",
+                    event.mint
+                );
+                continue;
+            };
+            self.send(instruction)?;
+            info!(
+                "deposit {event_index} of {signature} credited: {} to {}",
+                event.amount, event.recipient
+            );
+        }
+        Ok(())
+    }
+
+    fn credit_instruction(
+        &self,
+        l1_signature: L1Signature,
+        event_index: u8,
+        event: &DepositEvent,
+    ) -> Option<Instruction> {
+        let authority = self.authority.pubkey();
+        match event.mint {
+            None => Some(credit_deposit(
+                authority,
+                event.recipient,
+                l1_signature,
+                event_index,
+                event.amount,
+            )),
+            Some(l1_mint) => self.token_mints.get(&l1_mint).map(|grid_mint| {
+                credit_token_deposit(
+                    authority,
+                    event.recipient,
+                    *grid_mint,
+                    l1_signature,
+                    event_index,
+                    event.amount,
+                )
+            }),
+        }
+    }
+
+    fn send(&self, instruction: Instruction) -> Result<Signature, String> {
+        let blockhash = self
+            .grid_client
+            .get_latest_blockhash()
+            .map_err(|err| format!("failed to get blockhash: {err}"))?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            blockhash,
+        );
+        self.grid_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|err| format!("failed to credit: {err}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_data(event: &DepositEvent) -> String {
+        let fields: Vec<_> = event
+            .encode()
+            .iter()
+            .map(|field| BASE64_STANDARD.encode(field))
+            .collect();
+        format!("Program data: {}", fields.join(" "))
+    }
+
+    #[test]
+    fn test_parse_deposit_events() {
+        let escrow = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let event = |amount| DepositEvent {
+            depositor: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            mint: None,
+            amount,
+        };
+        let (first, nested, second) = (event(1), event(2), event(3));
+        let logs = vec![
+            format!("Program {escrow} invoke [1]"),
+            program_data(&first),
+            format!("Program {other} invoke [2]"),
+            program_data(&nested),
+            format!("Program {other} success"),
+            "Program data: not base64".to_string(),
+            "Program data: c25jZGVwc3Q= AAAA".to_string(),
+            program_data(&second),
+            format!("Program {escrow} consumed 1000 of 200000 compute units"),
+            format!("Program {escrow} success"),
+            format!("Program {other} invoke [1]"),
+            program_data(&nested),
+            format!("Program {other} success"),
+        ];
+        assert_eq!(parse_deposit_events(&logs, &escrow), vec![first, second]);
+        assert!(parse_deposit_events(&logs, &Pubkey::new_unique()).is_empty());
+    }
+
+    #[test]
+    fn test_deposit_ledger() {
+        let dir = std::env::temp_dir().join(format!("deposit_ledger_{}", Pubkey::new_unique()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut ledger = DepositLedger::open(&dir).unwrap();
+        let deposit = |signature: &str, slot| PendingDeposit {
+            signature: signature.to_string(),
+            slot,
+        };
+        ledger.add_signatures(vec![deposit("c", 12), deposit("b", 11)]);
+        ledger.add_signatures(vec![]);
+        ledger.add_signatures(vec![deposit("d", 20)]);
+        assert_eq!(ledger.last_signature.as_deref(), Some("d"));
+        assert_eq!(
+            ledger.pending,
+            vec![deposit("b", 11), deposit("c", 12), deposit("d", 20)]
+        );
+        assert_eq!(ledger.confirmed(43, 32), vec![deposit("b", 11)]);
+        assert_eq!(ledger.confirmed(44, 32).len(), 2);
+
+        ledger.remove("b");
+        ledger.save().unwrap();
+        let reopened = DepositLedger::open(&dir).unwrap();
+        assert_eq!(reopened, ledger);
+        assert_eq!(reopened.pending.len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
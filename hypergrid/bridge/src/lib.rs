@@ -0,0 +1,9 @@
+//! The bridge between an L1 and the grid.
+//!
+//! Deposits made to the L1 escrow program are credited on the grid by the bridge builtin,
+//! which records the L1 transaction of every credited deposit so that it is credited once.
+//! Messages sent by grid programs through the outbox of the bridge builtin are relayed to L1,
+//! and receive a receipt on the grid.
+
+pub mod deposit;
+pub mod outbox;
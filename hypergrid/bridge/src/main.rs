@@ -0,0 +1,80 @@
+//! The `sonic-bridge` binary relays between an L1 and the grid until it is killed.
+use {
+    clap::{crate_description, crate_name, App, AppSettings, Arg, SubCommand},
+    log::*,
+    sonic_bridge::{deposit::DepositWatcher, outbox::OutboxRelayer},
+    sonic_hypergrid::config::Config,
+    std::{path::Path, process, thread, time::Duration},
+};
+
+fn main() {
+    solana_logger::setup_with_default("solana=info,sonic=info");
+    let default_config_path = Config::default_path();
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .takes_value(true)
+                .global(true)
+                .default_value(&default_config_path)
+                .help("Hypergrid configuration file, read for its `bridge` settings"),
+        )
+        .arg(
+            Arg::with_name("ledger_dir")
+                .long("ledger-dir")
+                .value_name("DIR")
+                .takes_value(true)
+                .global(true)
+                .default_value(".")
+                .help("Directory the progress of the bridge is persisted in"),
+        )
+        .subcommand(
+            SubCommand::with_name("watch-deposits")
+                .about("Credit the deposits made to the L1 escrow program on the grid"),
+        )
+        .subcommand(
+            SubCommand::with_name("relay-outbox")
+                .about("Submit the messages of the grid outbox to L1, and write their receipts"),
+        )
+        .get_matches();
+
+    let config_path = matches.value_of("config").unwrap();
+    let config = Config::load(config_path).unwrap_or_else(|err| {
+        eprintln!("Failed to load the hypergrid config {config_path}: {err}");
+        process::exit(1);
+    });
+    let ledger_dir = Path::new(matches.value_of("ledger_dir").unwrap());
+    let poll_interval = Duration::from_millis(config.bridge.poll_interval_ms);
+
+    match matches.subcommand() {
+        ("watch-deposits", _) => {
+            let mut watcher = DepositWatcher::new(&config, ledger_dir).unwrap_or_else(|err| {
+                eprintln!("Failed to start the deposit watcher: {err}");
+                process::exit(1);
+            });
+            loop {
+                if let Err(err) = watcher.poll() {
+                    warn!("deposit watcher: {err}");
+                }
+                thread::sleep(poll_interval);
+            }
+        }
+        ("relay-outbox", _) => {
+            let mut relayer = OutboxRelayer::new(&config, ledger_dir).unwrap_or_else(|err| {
+                eprintln!("Failed to start the outbox relayer: {err}");
+                process::exit(1);
+            });
+            loop {
+                if let Err(err) = relayer.poll() {
+                    warn!("outbox relayer: {err}");
+                }
+                thread::sleep(poll_interval);
+            }
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,451 @@
+//! Relayer of the messages sent to L1 through the grid outbox.
+//!
+//! Messages are relayed in id order once rooted on the grid. Each one is submitted to L1 in a
+//! transaction paid and signed by [`Config::keypair_file`], then its outcome is written back
+//! into the message as its receipt by the bridge authority. The signature of every submitted
+//! transaction is persisted before it is sent, and kept until the receipt is rooted, so a
+//! restarted relayer waits for it instead of submitting the message again.
+
+use {
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig},
+    solana_sdk::{
+        account::{Account, ReadableAccount},
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        sonic_bridge::{
+            instruction::{escrow_release, write_receipt, EscrowRelease},
+            state::{
+                derive_outbox_address, derive_outbox_message_address, BridgeState, L1Signature,
+                OutboxMessage, OutboxPayload, OutboxReceipt,
+            },
+        },
+        transaction::Transaction,
+    },
+    sonic_hypergrid::config::Config,
+    std::{
+        fs::{self, File},
+        io::{self, BufReader, BufWriter},
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    },
+};
+
+pub const OUTBOX_LEDGER_FILE: &str = "bridge_outbox.json";
+
+/// Maximum number of messages handled by one poll.
+const MAX_MESSAGES_PER_POLL: u64 = 64;
+
+/// An L1 transaction submitted for a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SubmittedMessage {
+    pub message_id: u64,
+    pub signature: String,
+    /// The transaction can't land once the L1 block height is past this one.
+    pub last_valid_block_height: u64,
+}
+
+/// Persistent state of the relayer.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct OutboxLedger {
+    /// Messages below this id all have a rooted receipt.
+    pub next_message_id: u64,
+    /// Transactions submitted for messages without a rooted receipt yet.
+    pub submitted: Vec<SubmittedMessage>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl OutboxLedger {
+    /// Load the ledger from `dir`, starting a new one if none exists.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(OUTBOX_LEDGER_FILE);
+        let mut ledger = if path.exists() {
+            let file = File::open(&path)?;
+            serde_json::from_reader(BufReader::new(file))
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?
+        } else {
+            Self::default()
+        };
+        ledger.path = path;
+        Ok(ledger)
+    }
+
+    /// Atomically write the ledger back to disk.
+    pub fn save(&self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let file = File::create(&tmp_path)?;
+            serde_json::to_writer(BufWriter::new(file), self)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+        }
+        fs::rename(tmp_path, &self.path)
+    }
+
+    pub fn submitted(&self, message_id: u64) -> Option<&SubmittedMessage> {
+        self.submitted
+            .iter()
+            .find(|submitted| submitted.message_id == message_id)
+    }
+
+    /// Record a transaction submitted for a message, replacing any earlier one.
+    pub fn submit(&mut self, submitted: SubmittedMessage) {
+        self.remove(submitted.message_id);
+        self.submitted.push(submitted);
+    }
+
+    pub fn remove(&mut self, message_id: u64) {
+        self.submitted
+            .retain(|submitted| submitted.message_id != message_id);
+    }
+}
+
+/// The L1 instruction of a message relayed by `relayer`, `None` if it must not be submitted.
+/// A call referencing the relayer is refused: the relayer signs the transaction, so the call
+/// would act with its signature.
+pub fn l1_instruction(
+    message: &OutboxMessage,
+    escrow_program_id: &Pubkey,
+    relayer: &Pubkey,
+) -> Option<Instruction> {
+    match &message.payload {
+        OutboxPayload::Withdraw { recipient, amount } => Some(escrow_release(
+            *escrow_program_id,
+            *relayer,
+            &EscrowRelease {
+                message_id: message.id,
+                recipient: *recipient,
+                amount: *amount,
+            },
+        )),
+        OutboxPayload::Call(instruction) if !instruction.references(relayer) => {
+            Some(instruction.to_instruction())
+        }
+        OutboxPayload::Call(_) => None,
+    }
+}
+
+/// What the relayer did with a message.
+#[derive(Debug, PartialEq, Eq)]
+enum Relayed {
+    /// The message has a rooted receipt.
+    Done,
+    /// Its L1 transaction or its receipt is not confirmed, or rooted, yet.
+    InFlight,
+}
+
+/// Relays the messages of the grid outbox to L1.
+pub struct OutboxRelayer {
+    l1_client: RpcClient,
+    grid_client: RpcClient,
+    /// Pays and signs the L1 transactions.
+    relayer: Keypair,
+    /// Writes the receipts on the grid.
+    authority: Keypair,
+    escrow_program_id: Pubkey,
+    ledger: OutboxLedger,
+}
+
+impl OutboxRelayer {
+    pub fn new(config: &Config, ledger_dir: &Path) -> Result<Self, String> {
+        let bridge = &config.bridge;
+        let relayer = read_keypair_file(&config.keypair_file).map_err(|err| {
+            format!(
+                "failed to read relayer keypair {}: {err}",
+                config.keypair_file
+            )
+        })?;
+        let authority = read_keypair_file(&bridge.authority_keypair_file).map_err(|err| {
+            format!(
+                "failed to read bridge authority keypair {}: {err}",
+                bridge.authority_keypair_file
+            )
+        })?;
+        let escrow_program_id = Pubkey::from_str(&bridge.escrow_program_id).map_err(|err| {
+            format!(
+                "invalid escrow program {:?}: {err}",
+                bridge.escrow_program_id
+            )
+        })?;
+        let ledger = OutboxLedger::open(ledger_dir).map_err(|err| {
+            format!(
+                "failed to open the outbox ledger in {}: {err}",
+                ledger_dir.display()
+            )
+        })?;
+        Ok(Self {
+            l1_client: RpcClient::new_with_timeout_and_commitment(
+                config.l1_rpc_url().to_string(),
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+            grid_client: RpcClient::new_with_timeout_and_commitment(
+                bridge.grid_rpc_url.clone(),
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+            relayer,
+            authority,
+            escrow_program_id,
+            ledger,
+        })
+    }
+
+    pub fn ledger(&self) -> &OutboxLedger {
+        &self.ledger
+    }
+
+    /// Load a rooted account of the grid.
+    fn get_rooted_account(&self, address: &Pubkey) -> Result<Option<Account>, String> {
+        self.grid_client
+            .get_account_with_commitment(address, CommitmentConfig::finalized())
+            .map(|response| response.value)
+            .map_err(|err| format!("failed to load {address}: {err}"))
+    }
+
+    fn has_confirmed_receipt(&self, message_address: &Pubkey) -> Result<bool, String> {
+        let account = self
+            .grid_client
+            .get_account_with_commitment(message_address, CommitmentConfig::confirmed())
+            .map_err(|err| format!("failed to load {message_address}: {err}"))?
+            .value;
+        Ok(matches!(
+            account.map(|account| bincode::deserialize(account.data())),
+            Some(Ok(BridgeState::Message(OutboxMessage {
+                receipt: Some(_),
+                ..
+            })))
+        ))
+    }
+
+    /// Relay the rooted messages without a rooted receipt.
+    pub fn poll(&mut self) -> Result<(), String> {
+        let outbox_address = derive_outbox_address().0;
+        let outbox = self.get_rooted_account(&outbox_address)?;
+        let next_message_id = match outbox.map(|outbox| bincode::deserialize(outbox.data())) {
+            Some(Ok(BridgeState::Outbox(outbox))) => outbox.next_message_id,
+            _ => return Err(format!("{outbox_address} is not an outbox")),
+        };
+
+        let end_message_id = next_message_id.min(
+            self.ledger
+                .next_message_id
+                .saturating_add(MAX_MESSAGES_PER_POLL),
+        );
+        let mut all_done = true;
+        for message_id in self.ledger.next_message_id..end_message_id {
+            let relayed = self.relay(message_id).unwrap_or_else(|err| {
+                warn!("failed to relay message {message_id}: {err}");
+                Relayed::InFlight
+            });
+            all_done &= relayed == Relayed::Done;
+            if all_done {
+                self.ledger.next_message_id = message_id + 1;
+            }
+            self.save_ledger()?;
+        }
+        Ok(())
+    }
+
+    fn save_ledger(&self) -> Result<(), String> {
+        self.ledger
+            .save()
+            .map_err(|err| format!("failed to save the outbox ledger: {err}"))
+    }
+
+    fn relay(&mut self, message_id: u64) -> Result<Relayed, String> {
+        let message_address = derive_outbox_message_address(message_id).0;
+        let account = self.get_rooted_account(&message_address)?;
+        let message = match account.map(|account| bincode::deserialize(account.data())) {
+            Some(Ok(BridgeState::Message(message))) => message,
+            _ => return Err(format!("{message_address} is not a message")),
+        };
+        if message.receipt.is_some() {
+            self.ledger.remove(message_id);
+            return Ok(Relayed::Done);
+        }
+        // The receipt is written, but not rooted yet
+        if self.has_confirmed_receipt(&message_address)? {
+            return Ok(Relayed::InFlight);
+        }
+
+        if let Some(submitted) = self.ledger.submitted(message_id).cloned() {
+            let signature = Signature::from_str(&submitted.signature)
+                .map_err(|err| format!("invalid signature: {err}"))?;
+            let status = self
+                .l1_client
+                .get_signature_statuses_with_history(&[signature])
+                .map_err(|err| format!("failed to get the status of {signature}: {err}"))?
+                .value
+                .pop()
+                .flatten()
+                .filter(|status| status.satisfies_commitment(CommitmentConfig::confirmed()));
+            if let Some(status) = status {
+                self.send_receipt(
+                    message_id,
+                    OutboxReceipt {
+                        l1_signature: Some(L1Signature::new(signature.into())),
+                        l1_slot: status.slot,
+                        success: status.err.is_none(),
+                    },
+                )?;
+                return Ok(Relayed::InFlight);
+            }
+            let block_height = self
+                .l1_client
+                .get_block_height()
+                .map_err(|err| format!("failed to get the L1 block height: {err}"))?;
+            if block_height <= submitted.last_valid_block_height {
+                return Ok(Relayed::InFlight);
+            }
+            info!("{signature} of message {message_id} expired, submitting it again");
+        }
+
+        let relayer = self.relayer.pubkey();
+        let Some(instruction) = l1_instruction(&message, &self.escrow_program_id, &relayer) else {
+            warn!("message {message_id} references the relayer, refused");
+            self.send_receipt(
+                message_id,
+                OutboxReceipt {
+                    l1_signature: None,
+                    l1_slot: 0,
+                    success: false,
+                },
+            )?;
+            return Ok(Relayed::InFlight);
+        };
+        let (blockhash, last_valid_block_height) = self
+            .l1_client
+            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+            .map_err(|err| format!("failed to get blockhash: {err}"))?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&relayer),
+            &[&self.relayer],
+            blockhash,
+        );
+        self.ledger.submit(SubmittedMessage {
+            message_id,
+            signature: transaction.signatures[0].to_string(),
+            last_valid_block_height,
+        });
+        self.save_ledger()?;
+        // A failing call is still executed, so that its failure gets a receipt
+        let signature = self
+            .l1_client
+            .send_transaction_with_config(
+                &transaction,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    ..RpcSendTransactionConfig::default()
+                },
+            )
+            .map_err(|err| format!("failed to submit the message: {err}"))?;
+        info!("message {message_id} submitted to L1: {signature}");
+        Ok(Relayed::InFlight)
+    }
+
+    fn send_receipt(&self, message_id: u64, receipt: OutboxReceipt) -> Result<(), String> {
+        let blockhash = self
+            .grid_client
+            .get_latest_blockhash()
+            .map_err(|err| format!("failed to get blockhash: {err}"))?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[write_receipt(self.authority.pubkey(), message_id, receipt)],
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            blockhash,
+        );
+        let signature = self
+            .grid_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|err| format!("failed to write the receipt: {err}"))?;
+        info!("receipt of message {message_id} written: {signature}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::sonic_bridge::state::{L1AccountMeta, L1Instruction},
+    };
+
+    #[test]
+    fn test_l1_instruction() {
+        let escrow = Pubkey::new_unique();
+        let relayer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let message = |payload| OutboxMessage {
+            id: 3,
+            sender: Pubkey::new_unique(),
+            payload,
+            slot: 10,
+            receipt: None,
+        };
+
+        let withdraw = l1_instruction(
+            &message(OutboxPayload::Withdraw {
+                recipient,
+                amount: 42,
+            }),
+            &escrow,
+            &relayer,
+        )
+        .unwrap();
+        assert_eq!(withdraw.program_id, escrow);
+        assert!(withdraw.accounts[0].is_signer);
+        assert_eq!(withdraw.accounts[0].pubkey, relayer);
+        assert_eq!(withdraw.accounts[2].pubkey, recipient);
+
+        let call = |pubkey| {
+            OutboxPayload::Call(L1Instruction {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![L1AccountMeta {
+                    pubkey,
+                    is_writable: true,
+                }],
+                data: vec![1],
+            })
+        };
+        let instruction = l1_instruction(&message(call(recipient)), &escrow, &relayer).unwrap();
+        assert_eq!(instruction.data, vec![1]);
+        assert!(instruction.accounts.iter().all(|meta| !meta.is_signer));
+        // The relayer would sign for the account
+        assert_eq!(
+            l1_instruction(&message(call(relayer)), &escrow, &relayer),
+            None
+        );
+    }
+
+    #[test]
+    fn test_outbox_ledger() {
+        let dir = std::env::temp_dir().join(format!("outbox_ledger_{}", Pubkey::new_unique()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut ledger = OutboxLedger::open(&dir).unwrap();
+        let submitted = |message_id, signature: &str| SubmittedMessage {
+            message_id,
+            signature: signature.to_string(),
+            last_valid_block_height: 100,
+        };
+        ledger.submit(submitted(0, "a"));
+        ledger.submit(submitted(1, "b"));
+        // Submitting a message again replaces its transaction
+        ledger.submit(submitted(0, "c"));
+        assert_eq!(ledger.submitted.len(), 2);
+        assert_eq!(ledger.submitted(0), Some(&submitted(0, "c")));
+        ledger.remove(1);
+        ledger.next_message_id = 1;
+        ledger.save().unwrap();
+
+        let reopened = OutboxLedger::open(&dir).unwrap();
+        assert_eq!(reopened, ledger);
+        assert_eq!(reopened.submitted(1), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
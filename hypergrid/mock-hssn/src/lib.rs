@@ -0,0 +1,462 @@
+//! A stand-in for the HSSN REST API, so that hypergrid nodes can be tested offline.
+//!
+//! The mock serves the node registry and the recorded solana accounts from fixtures, and
+//! accepts the `hypergridssn` transactions posted by nodes configured with `hssn_tx_url`.
+//! Transactions are recorded in order, and applied to the recorded accounts where that can
+//! be done without the source chain.
+
+use {
+    base64::{self, Engine},
+    hyper::{
+        body,
+        service::{make_service_fn, service_fn},
+        Body, Method, Request, Response, Server, StatusCode,
+    },
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        pubkey::Pubkey,
+    },
+    sonic_hypergrid::config::Config,
+    std::{
+        collections::HashMap,
+        convert::Infallible,
+        fs::File,
+        io::{self, BufReader},
+        net::{Ipv4Addr, SocketAddr},
+        path::Path,
+        sync::{Arc, RwLock},
+        thread::{self, JoinHandle},
+    },
+    tokio::sync::oneshot,
+};
+
+pub const HYPERGRID_NODE_PATH: &str = "/hypergrid-ssn/hypergridssn/hypergrid_node";
+pub const SOLANA_ACCOUNT_PATH: &str = "/hypergrid-ssn/hypergridssn/solana_account";
+/// Transactions are posted here, see [`HssnTransaction`].
+pub const TX_PATH: &str = "/hypergrid-ssn/hypergridssn/tx";
+
+/// A hypergrid node, as registered in HSSN.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HypergridNodeFixture {
+    pub pubkey: String,
+    pub name: String,
+    pub rpc: String,
+    /// See the `NODE_TYPE_*` constants of `sonic_hypergrid::remote_loader`.
+    pub role: i32,
+}
+
+/// A solana account recorded in HSSN, in the format written by
+/// `solana account --output json`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AccountFixture {
+    pub pubkey: String,
+    pub account: serde_json::Value,
+    /// The node the account was migrated from, if not the base layer.
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub version: u64,
+}
+
+/// The state the mock starts with.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HssnFixtures {
+    pub hypergrid_nodes: Vec<HypergridNodeFixture>,
+    pub accounts: Vec<AccountFixture>,
+}
+
+impl HssnFixtures {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{err:?}")))
+    }
+}
+
+/// A `hypergridssn` transaction, as posted to [`TX_PATH`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HssnTransaction {
+    /// The `hypergrid-ssnd tx hypergridssn` subcommand, e.g. `create-solana-account`.
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Default)]
+struct HssnState {
+    nodes: Vec<HypergridNodeFixture>,
+    accounts: HashMap<String, AccountFixture>,
+    transactions: Vec<HssnTransaction>,
+}
+
+impl HssnState {
+    fn new(fixtures: HssnFixtures) -> Self {
+        Self {
+            nodes: fixtures.hypergrid_nodes,
+            accounts: fixtures
+                .accounts
+                .into_iter()
+                .map(|account| (account.pubkey.clone(), account))
+                .collect(),
+            transactions: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, transaction: &HssnTransaction) -> Result<(), String> {
+        match transaction.command.as_str() {
+            "create-solana-account" => {
+                let [pubkey, version, source] = transaction.args.as_slice() else {
+                    return Err("expected <pubkey> <version> <source>".to_string());
+                };
+                // The real HSSN fetches the account from `source`, the mock only records the
+                // migration. Accounts seeded by the test are left as they are.
+                let version = version.parse().map_err(|err| format!("{version}: {err}"))?;
+                self.accounts
+                    .entry(pubkey.clone())
+                    .and_modify(|account| account.source = source.clone())
+                    .or_insert_with(|| AccountFixture {
+                        pubkey: pubkey.clone(),
+                        account: serde_json::Value::Null,
+                        source: source.clone(),
+                        version,
+                    });
+                Ok(())
+            }
+            "update-solana-account" => {
+                let [pubkey, _version] = transaction.args.as_slice() else {
+                    return Err("expected <pubkey> <version>".to_string());
+                };
+                match self.accounts.get_mut(pubkey) {
+                    Some(account) => {
+                        account.version += 1;
+                        Ok(())
+                    }
+                    None => Err(format!("{pubkey} is not recorded")),
+                }
+            }
+            "create-fee-settlement-bill" => match transaction.args.as_slice() {
+                [_from_id, _end_id, _bills] => Ok(()),
+                _ => Err("expected <from_id> <end_id> <bills>".to_string()),
+            },
+            command => Err(format!("unknown command {command}")),
+        }
+    }
+}
+
+/// A running mock HSSN service. It is shut down when dropped.
+pub struct MockHssn {
+    addr: SocketAddr,
+    state: Arc<RwLock<HssnState>>,
+    exit: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockHssn {
+    /// Serve `fixtures` on an unused localhost port.
+    pub fn start(fixtures: HssnFixtures) -> io::Result<Self> {
+        Self::start_on(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), fixtures)
+    }
+
+    /// Serve `fixtures` on `addr`.
+    pub fn start_on(addr: SocketAddr, fixtures: HssnFixtures) -> io::Result<Self> {
+        let state = Arc::new(RwLock::new(HssnState::new(fixtures)));
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("solMockHssn")
+            .enable_all()
+            .build()?;
+        let server = {
+            let _guard = runtime.enter();
+            Server::try_bind(&addr).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
+        };
+        let state_inner = state.clone();
+        let service = make_service_fn(move |_| {
+            let state = state_inner.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    Self::handle(state.clone(), request)
+                }))
+            }
+        });
+        let server = server.serve(service);
+        let addr = server.local_addr();
+        let (exit, exit_receiver) = oneshot::channel();
+        let thread = thread::Builder::new()
+            .name("solMockHssn".to_string())
+            .spawn(move || {
+                let server = server.with_graceful_shutdown(async {
+                    exit_receiver.await.ok();
+                });
+                if let Err(err) = runtime.block_on(server) {
+                    error!("mock HSSN: {err:?}");
+                }
+            })?;
+        info!("mock HSSN running on http://{addr}");
+        Ok(Self {
+            addr,
+            state,
+            exit: Some(exit),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The URL to use as `hssn_rpc_url`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// The URL to use as `hssn_tx_url`.
+    pub fn tx_url(&self) -> String {
+        format!("{}{}", self.url(), TX_PATH)
+    }
+
+    /// A hypergrid configuration reading from and submitting to this mock.
+    pub fn hypergrid_config(&self, baselayer_rpc_url: &str) -> Config {
+        Config {
+            baselayer_rpc_url: baselayer_rpc_url.to_string(),
+            hssn_rpc_url: self.url(),
+            hssn_tx_url: Some(self.tx_url()),
+            ..Config::default()
+        }
+    }
+
+    pub fn add_node(&self, pubkey: &Pubkey, name: &str, rpc: &str, role: i32) {
+        let node = HypergridNodeFixture {
+            pubkey: pubkey.to_string(),
+            name: name.to_string(),
+            rpc: rpc.to_string(),
+            role,
+        };
+        let mut state = self.state.write().unwrap();
+        state.nodes.retain(|node| node.pubkey != pubkey.to_string());
+        state.nodes.push(node);
+    }
+
+    /// Record `account` as migrated from `source`, or from the base layer if `source` is `None`.
+    pub fn add_account(
+        &self,
+        pubkey: &Pubkey,
+        account: &AccountSharedData,
+        source: Option<&Pubkey>,
+    ) {
+        let account = AccountFixture {
+            pubkey: pubkey.to_string(),
+            account: ui_account_json(account),
+            source: source.map(ToString::to_string).unwrap_or_default(),
+            version: 0,
+        };
+        self.state
+            .write()
+            .unwrap()
+            .accounts
+            .insert(pubkey.to_string(), account);
+    }
+
+    pub fn remove_account(&self, pubkey: &Pubkey) {
+        self.state
+            .write()
+            .unwrap()
+            .accounts
+            .remove(&pubkey.to_string());
+    }
+
+    /// The transactions received so far, in order.
+    pub fn transactions(&self) -> Vec<HssnTransaction> {
+        self.state.read().unwrap().transactions.clone()
+    }
+
+    async fn handle(
+        state: Arc<RwLock<HssnState>>,
+        request: Request<Body>,
+    ) -> Result<Response<Body>, Infallible> {
+        let path = request.uri().path().to_string();
+        info!("mock HSSN: {} {}", request.method(), path);
+        let response = match (request.method(), path.as_str()) {
+            (&Method::GET, HYPERGRID_NODE_PATH) => {
+                let state = state.read().unwrap();
+                json_response(
+                    StatusCode::OK,
+                    serde_json::json!({ "hypergridNode": state.nodes }),
+                )
+            }
+            (&Method::GET, path) if path.starts_with(SOLANA_ACCOUNT_PATH) => {
+                Self::handle_get_account(&state, &path[SOLANA_ACCOUNT_PATH.len()..])
+            }
+            (&Method::POST, TX_PATH) => {
+                let Ok(bytes) = body::to_bytes(request.into_body()).await else {
+                    return Ok(error_response(
+                        StatusCode::BAD_REQUEST,
+                        "failed to read body",
+                    ));
+                };
+                Self::handle_tx(&state, &bytes)
+            }
+            _ => error_response(StatusCode::NOT_FOUND, "Not Implemented"),
+        };
+        Ok(response)
+    }
+
+    fn handle_get_account(state: &RwLock<HssnState>, path: &str) -> Response<Body> {
+        // `/{pubkey}/{version}`, the version is ignored like it is by HSSN
+        let Some(pubkey) = path.trim_start_matches('/').split('/').next() else {
+            return error_response(StatusCode::BAD_REQUEST, "missing pubkey");
+        };
+        let state = state.read().unwrap();
+        match state.accounts.get(pubkey) {
+            Some(account) if !account.account.is_null() => json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                    "solanaAccount": {
+                        "pubkey": account.pubkey,
+                        "version": account.version.to_string(),
+                        "source": account.source,
+                        "value": account.account.to_string(),
+                    }
+                }),
+            ),
+            _ => error_response(StatusCode::NOT_FOUND, "not found"),
+        }
+    }
+
+    fn handle_tx(state: &RwLock<HssnState>, bytes: &[u8]) -> Response<Body> {
+        let transaction: HssnTransaction = match serde_json::from_slice(bytes) {
+            Ok(transaction) => transaction,
+            Err(err) => return error_response(StatusCode::BAD_REQUEST, &format!("{err}")),
+        };
+        info!("mock HSSN: tx {:?}", transaction);
+        let mut state = state.write().unwrap();
+        if let Err(err) = state.apply(&transaction) {
+            return error_response(StatusCode::BAD_REQUEST, &err);
+        }
+        state.transactions.push(transaction);
+        let txhash = state.transactions.len().to_string();
+        json_response(StatusCode::OK, serde_json::json!({ "txhash": txhash }))
+    }
+}
+
+impl Drop for MockHssn {
+    fn drop(&mut self) {
+        if let Some(exit) = self.exit.take() {
+            let _ = exit.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// `account` in the JSON representation returned by RPC `getAccountInfo`.
+pub fn ui_account_json(account: &AccountSharedData) -> serde_json::Value {
+    serde_json::json!({
+        "lamports": account.lamports(),
+        "data": [
+            base64::engine::general_purpose::STANDARD.encode(account.data()),
+            "base64",
+        ],
+        "owner": account.owner().to_string(),
+        "executable": account.executable(),
+        "rentEpoch": account.rent_epoch(),
+        "space": account.data().len(),
+    })
+}
+
+fn json_response(status: StatusCode, value: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(value.to_string()))
+        .unwrap()
+}
+
+// Errors are shaped like those of the cosmos gRPC gateway.
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(
+        status,
+        serde_json::json!({
+            "code": status.as_u16(),
+            "message": message,
+            "details": [],
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::signature::{Keypair, Signer},
+        sonic_hypergrid::{
+            error::RemoteLoadError,
+            remote_loader::{RemoteAccountLoader, NODE_TYPE_GRID},
+        },
+    };
+
+    #[test]
+    fn test_mock_hssn() {
+        let node_id = Keypair::new().pubkey();
+        let hssn = MockHssn::start(HssnFixtures {
+            hypergrid_nodes: vec![HypergridNodeFixture {
+                pubkey: node_id.to_string(),
+                name: "grid".to_string(),
+                // Nothing listens there, loading from the node fails right away
+                rpc: "http://127.0.0.1:1".to_string(),
+                role: NODE_TYPE_GRID,
+            }],
+            accounts: Vec::new(),
+        })
+        .unwrap();
+        let loader =
+            RemoteAccountLoader::new_with_config(hssn.hypergrid_config("http://127.0.0.1:1"));
+
+        let nodes = loader.get_hypergrid_nodes().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].pubkey, node_id);
+        assert_eq!(nodes[0].role, NODE_TYPE_GRID);
+
+        let recorded = Keypair::new().pubkey();
+        let owner = Pubkey::new_unique();
+        let account = AccountSharedData::new(42, 3, &owner);
+        hssn.add_account(&recorded, &account, Some(&node_id));
+        let loaded = loader.get_hssn_account(&recorded).unwrap().unwrap();
+        assert_eq!(loaded.lamports(), 42);
+        assert_eq!(loaded.data(), &[0, 0, 0]);
+        assert_eq!(loaded.owner(), &owner);
+        assert_eq!(
+            loader.load_account(&recorded, Some(node_id), false),
+            Ok(Some(loaded))
+        );
+        assert!(hssn.transactions().is_empty());
+
+        // Unknown accounts are created in HSSN, then loaded from the source node
+        let missing = Keypair::new().pubkey();
+        assert_eq!(loader.get_hssn_account(&missing).unwrap(), None);
+        assert!(matches!(
+            loader.load_account(&missing, Some(node_id), false),
+            Err(RemoteLoadError::SourceUnreachable { pubkey, .. }) if pubkey == missing
+        ));
+        // Refreshes are recorded as updates. A new loader is used, the account stays cached in
+        // the first one for a while.
+        let loader =
+            RemoteAccountLoader::new_with_config(hssn.hypergrid_config("http://127.0.0.1:1"));
+        assert!(loader.load_account(&recorded, Some(node_id), true).is_err());
+        assert_eq!(
+            hssn.transactions(),
+            vec![
+                HssnTransaction {
+                    command: "create-solana-account".to_string(),
+                    args: vec![missing.to_string(), "0".to_string(), node_id.to_string()],
+                },
+                HssnTransaction {
+                    command: "update-solana-account".to_string(),
+                    args: vec![recorded.to_string(), "0".to_string()],
+                },
+            ]
+        );
+    }
+}
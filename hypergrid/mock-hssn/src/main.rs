@@ -0,0 +1,53 @@
+//! The `sonic-mock-hssn` binary serves a mock HSSN REST API until it is killed.
+use {
+    clap::{crate_description, crate_name, value_t_or_exit, App, Arg},
+    sonic_mock_hssn::{HssnFixtures, MockHssn},
+    std::{net::SocketAddr, path::Path, thread},
+};
+
+fn main() {
+    solana_logger::setup_with_default("solana=info,sonic=info");
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::with_name("fixtures")
+                .long("fixtures")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "JSON file with the registered hypergrid nodes and the recorded accounts: \
+                     {\"hypergridNodes\": [...], \"accounts\": [...]}",
+                ),
+        )
+        .arg(
+            Arg::with_name("bind_address")
+                .long("bind-address")
+                .value_name("HOST:PORT")
+                .takes_value(true)
+                .default_value("127.0.0.1:1317")
+                .help("Address to serve the HSSN REST API on"),
+        )
+        .get_matches();
+
+    let fixtures = match matches.value_of("fixtures") {
+        Some(path) => HssnFixtures::load(Path::new(path)).unwrap_or_else(|err| {
+            eprintln!("Failed to load fixtures from {path}: {err}");
+            std::process::exit(1);
+        }),
+        None => HssnFixtures::default(),
+    };
+    let bind_address = value_t_or_exit!(matches, "bind_address", SocketAddr);
+
+    let hssn = MockHssn::start_on(bind_address, fixtures).unwrap_or_else(|err| {
+        eprintln!("Failed to start the mock HSSN on {bind_address}: {err}");
+        std::process::exit(1);
+    });
+    println!("Mock HSSN running on {}", hssn.url());
+    println!("Transactions are accepted at {}", hssn.tx_url());
+
+    // Transactions are logged as they are received
+    loop {
+        thread::park();
+    }
+}
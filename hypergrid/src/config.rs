@@ -1,11 +1,15 @@
 use {
     std::{
+        collections::HashMap,
         fs::File, io,
         path::Path,
     },
     serde_derive::{Deserialize, Serialize},
 };
 
+/// Environment variable naming the configuration file, see [`Config::default_path`].
+pub const CONFIG_FILE_ENV: &str = "SONIC_CONFIG_FILE";
+
 fn load_config_file<T, P>(config_file: P) -> Result<T, io::Error>
 where
     T: serde::de::DeserializeOwned,
@@ -21,8 +25,239 @@ where
 pub struct Config {
     pub baselayer_rpc_url: String,
     pub hssn_rpc_url: String,
+    /// HSSN REST endpoint transactions are posted to. If unset, they are run
+    /// through the local `hypergrid-ssnd` client.
+    #[serde(default)]
+    pub hssn_tx_url: Option<String>,
+    /// Keypair paying and signing the transactions the bridge relayer submits to L1.
     pub keypair_file: String,
     // pub sonic_program_id: String,
+    #[serde(default)]
+    pub fee_bill: FeeBillConfig,
+    /// If set, remote accounts are only cached once enough independent copies match.
+    #[serde(default)]
+    pub quorum: Option<QuorumConfig>,
+    /// Accounts served with pinned content, the native mints if unset.
+    #[serde(default)]
+    pub synthetic_accounts: Option<Vec<SyntheticAccountConfig>>,
+    /// Limits of the cache of the remote accounts.
+    #[serde(default)]
+    pub cache: RemoteCacheConfig,
+    /// Limits of the requests sent to the RPC nodes remote accounts are loaded from.
+    #[serde(default)]
+    pub rpc_pool: RpcPoolConfig,
+    /// Kinds of namespaces by decreasing precedence, the ones left out last. An account
+    /// already loaded from a namespace is only replaced by a copy from a namespace preceding
+    /// it.
+    #[serde(default = "Config::default_namespace_precedence")]
+    pub namespace_precedence: Vec<NamespaceKind>,
+    /// Settings of the bridge between L1 and the grid.
+    #[serde(default)]
+    pub bridge: BridgeRelayConfig,
+}
+
+/// Kinds of the chains remote accounts are loaded from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamespaceKind {
+    /// The base layer at `baselayer_rpc_url`.
+    BaseLayer,
+    /// Other Solana L1s, each served by a hypergrid node.
+    L1,
+    /// Grids, each served by a hypergrid node.
+    Grid,
+}
+
+/// Where a synthetic account is declared to come from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntheticAccountOrigin {
+    /// Part of the state of this chain, counted in its capitalization.
+    Local,
+    /// A copy of an account of the base layer, like the migrated accounts.
+    Remote,
+}
+
+/// A well-known account served with pinned content.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticAccountConfig {
+    pub pubkey: String,
+    pub owner: String,
+    pub lamports: u64,
+    /// Base64 encoded account data.
+    #[serde(default)]
+    pub data: String,
+    #[serde(default)]
+    pub executable: bool,
+    #[serde(default = "SyntheticAccountConfig::default_rent_epoch")]
+    pub rent_epoch: u64,
+    pub origin: SyntheticAccountOrigin,
+}
+
+impl SyntheticAccountConfig {
+    fn default_rent_epoch() -> u64 {
+        u64::MAX
+    }
+}
+
+/// Settings of the quorum verification of remote accounts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct QuorumConfig {
+    /// Independent RPC nodes every loaded account is fetched from as well. They must serve
+    /// the chain the accounts are migrated from.
+    pub rpc_urls: Vec<String>,
+    /// Number of matching copies required, the loaded one included.
+    pub min_matches: usize,
+    /// Maximum distance between the slots two matching copies were read at.
+    pub slot_tolerance: u64,
+}
+
+impl Default for QuorumConfig {
+    fn default() -> Self {
+        Self {
+            rpc_urls: Vec::new(),
+            min_matches: 2,
+            slot_tolerance: 150,
+        }
+    }
+}
+
+/// Which cached remote accounts are evicted first once the cache is full.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// The least recently used.
+    Lru,
+    /// The least frequently used, the least recently used among equals.
+    Lfu,
+}
+
+/// Limits of the cache of the remote accounts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RemoteCacheConfig {
+    /// Maximum number of cached accounts.
+    pub max_accounts: usize,
+    /// Maximum total size of the cached accounts, in bytes.
+    pub max_bytes: usize,
+    pub policy: EvictionPolicy,
+}
+
+impl Default for RemoteCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_accounts: 100_000,
+            max_bytes: 1024 * 1024 * 1024,
+            policy: EvictionPolicy::Lru,
+        }
+    }
+}
+
+/// Limits of the requests sent to each RPC node remote accounts are loaded from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RpcPoolConfig {
+    /// Timeout of a single request, in milliseconds.
+    pub timeout_ms: u64,
+    /// Sustained number of requests per second.
+    pub requests_per_second: u32,
+    /// Number of requests allowed in a burst above the sustained rate.
+    pub burst: u32,
+    /// Number of consecutive failures after which the node is considered down.
+    pub failure_threshold: u32,
+    /// Time requests to a node considered down fail fast for, in milliseconds. It doubles
+    /// every time the node fails again, up to `max_backoff_ms`.
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RpcPoolConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 5_000,
+            requests_per_second: 20,
+            burst: 40,
+            failure_threshold: 3,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 60_000,
+        }
+    }
+}
+
+/// Which key the collected fees of a bill are aggregated by.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeBillKey {
+    FeePayer,
+    Program,
+}
+
+/// Settings of the fee bill generator.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct FeeBillConfig {
+    /// RPC of the chain running the fee settlement program.
+    pub settlement_rpc_url: String,
+    /// Settlement accounts credited by each bill batch.
+    pub settlement_accounts: Vec<String>,
+    /// Keypair of the settler designated in the settlement cursor.
+    pub settler_keypair_file: String,
+    pub aggregate_by: FeeBillKey,
+    /// Number of rooted slots aggregated into one set of bills.
+    pub slots_per_bill: u64,
+    /// Maximum number of bills settled by one transaction.
+    pub max_bills_per_batch: usize,
+}
+
+impl Default for FeeBillConfig {
+    fn default() -> Self {
+        Self {
+            settlement_rpc_url: "http://localhost:8899".to_string(),
+            settlement_accounts: Vec::new(),
+            settler_keypair_file: "~/.config/solana/id.json".to_string(),
+            aggregate_by: FeeBillKey::FeePayer,
+            slots_per_bill: 150,
+            max_bills_per_batch: 16,
+        }
+    }
+}
+
+/// Settings of the bridge crediting the deposits made on L1 to the grid, and relaying the
+/// messages of the grid outbox to L1.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct BridgeRelayConfig {
+    /// RPC of the L1, `baselayer_rpc_url` if unset.
+    pub l1_rpc_url: Option<String>,
+    /// RPC of the grid deposits are credited on.
+    pub grid_rpc_url: String,
+    /// L1 escrow program logging the deposit events, and releasing the withdrawals.
+    pub escrow_program_id: String,
+    /// Number of L1 slots a finalized deposit waits for before it is credited.
+    pub confirmation_depth: u64,
+    /// Interval between two polls of the L1, in milliseconds.
+    pub poll_interval_ms: u64,
+    /// Keypair of the authority designated in the bridge config, which credits the deposits
+    /// and writes the receipts of the relayed messages.
+    pub authority_keypair_file: String,
+    /// Grid mint credited for the deposits of each L1 mint. Deposits of other mints are
+    /// skipped.
+    pub token_mints: HashMap<String, String>,
+}
+
+impl Default for BridgeRelayConfig {
+    fn default() -> Self {
+        Self {
+            l1_rpc_url: None,
+            grid_rpc_url: "http://localhost:8899".to_string(),
+            escrow_program_id: String::new(),
+            confirmation_depth: 32,
+            poll_interval_ms: 2_000,
+            authority_keypair_file: "~/.config/solana/id.json".to_string(),
+            token_mints: HashMap::new(),
+        }
+    }
 }
 
 impl Default for Config {
@@ -35,13 +270,41 @@ impl Default for Config {
         Self {
             baselayer_rpc_url,
             hssn_rpc_url,
+            hssn_tx_url: None,
             keypair_file,
             // sonic_program_id,
+            fee_bill: FeeBillConfig::default(),
+            quorum: None,
+            synthetic_accounts: None,
+            cache: RemoteCacheConfig::default(),
+            rpc_pool: RpcPoolConfig::default(),
+            namespace_precedence: Self::default_namespace_precedence(),
+            bridge: BridgeRelayConfig::default(),
         }
     }
 }
 
 impl Config {
+    fn default_namespace_precedence() -> Vec<NamespaceKind> {
+        vec![NamespaceKind::BaseLayer, NamespaceKind::L1, NamespaceKind::Grid]
+    }
+
+    /// Rank of `kind` in the namespace precedence, lower ranks preceding higher ones.
+    pub fn namespace_rank(&self, kind: NamespaceKind) -> usize {
+        self.namespace_precedence
+            .iter()
+            .position(|precedence| *precedence == kind)
+            .unwrap_or(self.namespace_precedence.len())
+    }
+
+    /// RPC of the L1 the bridge watches.
+    pub fn l1_rpc_url(&self) -> &str {
+        self.bridge
+            .l1_rpc_url
+            .as_deref()
+            .unwrap_or(&self.baselayer_rpc_url)
+    }
+
     /// Load a configuration from file.
     ///
     /// # Errors
@@ -50,4 +313,28 @@ impl Config {
     pub fn load(config_file: &str) -> Result<Self, io::Error> {
         load_config_file(config_file)
     }
+
+    /// Save the configuration to file.
+    pub fn save(&self, config_file: &str) -> Result<(), io::Error> {
+        let file = File::create(config_file)?;
+        serde_yaml::to_writer(file, self)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))
+    }
+
+    /// The configuration file used when none is given: `SONIC_CONFIG_FILE` at
+    /// run time or at build time, otherwise `~/.config/hypergrid.yml`.
+    pub fn default_path() -> String {
+        if let Ok(config_path) = std::env::var(CONFIG_FILE_ENV) {
+            return config_path;
+        }
+        let config_path: Option<&'static str> = option_env!("SONIC_CONFIG_FILE");
+        match config_path {
+            Some(config_path) => config_path.to_string(),
+            None => {
+                let mut default_config_path = dirs_next::home_dir().expect("home directory");
+                default_config_path.extend([".config", "hypergrid.yml"]);
+                default_config_path.to_str().unwrap().to_string()
+            }
+        }
+    }
 }
\ No newline at end of file
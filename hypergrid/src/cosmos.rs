@@ -14,7 +14,64 @@ const COSMOS_HOME: &str = ".hypergrid-ssn";
 const COSMOS_APP: &str = " bin/hypergrid-ssnd";
 const COSMOS_SIGNER: &str = "my_key";
 
-pub fn run_load_solana_account(pub_key: &str, version:  &str, source: &str, update: bool) {
+/// Where `hypergridssn` transactions are submitted to.
+pub enum HssnTxSubmitter {
+    /// Through the local HSSN client, `~/.hypergrid-ssn/bin/hypergrid-ssnd`.
+    Cli,
+    /// Posted as JSON to an HSSN REST endpoint.
+    Rest { client: HttpClient, url: String },
+}
+
+impl HssnTxSubmitter {
+    /// Submit through `tx_url` if given, otherwise through the local HSSN client.
+    pub fn new(tx_url: Option<&str>) -> Self {
+        match tx_url {
+            Some(url) => Self::Rest {
+                client: HttpClient::new(Duration::from_secs(30)),
+                url: url.to_string(),
+            },
+            None => Self::Cli,
+        }
+    }
+
+    pub fn run_load_solana_account(&self, pub_key: &str, version:  &str, source: &str, update: bool) {
+        let result = if update {
+            self.run_hssn_tx("update-solana-account", &[pub_key, version])
+        } else {
+            self.run_hssn_tx("create-solana-account", &[pub_key, version, source])
+        };
+
+        if let Err(e) = result {
+            error!("Error: {:?}", e);
+        }
+    }
+
+    /// Record a settled fee bill range in HSSN.
+    pub fn run_create_fee_settlement_bill(&self, from_id: u64, end_id: u64, bills: &str) -> Result<String, String> {
+        self.run_hssn_tx(
+            "create-fee-settlement-bill",
+            &[&from_id.to_string(), &end_id.to_string(), bills],
+        )
+    }
+
+    /// Run the `command` transaction of the `hypergridssn` module.
+    fn run_hssn_tx(&self, command: &str, args: &[&str]) -> Result<String, String> {
+        match self {
+            Self::Cli => run_hssn_cli_tx(command, args),
+            Self::Rest { client, url } => {
+                let body = serde_json::json!({
+                    "command": command,
+                    "args": args,
+                });
+                info!("hssn tx: {} {}", url, body);
+                client.post(url, body.to_string())
+            }
+        }
+    }
+}
+
+/// Run a `hypergridssn` transaction through the local HSSN client.
+fn run_hssn_cli_tx(command: &str, args: &[&str]) -> Result<String, String> {
     let home_path = dirs_next::home_dir().expect("home directory");
     
     let cosmos_home_path = {
@@ -28,30 +85,27 @@ pub fn run_load_solana_account(pub_key: &str, version:  &str, source: &str, upda
         _path.extend([COSMOS_HOME, COSMOS_APP]);
         _path.to_str().unwrap().to_string()
     };
+
+    let args: Vec<String> = args.iter().map(|arg| format!("'{}'", arg)).collect();
     
     //format the command string
-    let cmd_str: String;
-    if update {
-        cmd_str = format!("{} tx hypergridssn update-solana-account {} {} --home {} --from {} --chain-id {} --gas 50000000 --keyring-backend test -y", 
-        cosmos_app_path, pub_key, version, cosmos_home_path, COSMOS_SIGNER, COSMOS_CHAIN_ID);
-    } else {
-        cmd_str = format!("{} tx hypergridssn create-solana-account {} {} {} --home {} --from {} --chain-id {} --gas 50000000 --keyring-backend test -y", 
-        cosmos_app_path, pub_key, version, source, cosmos_home_path, COSMOS_SIGNER, COSMOS_CHAIN_ID);
-    }
+    let cmd_str = format!("{} tx hypergridssn {} {} --home {} --from {} --chain-id {} --gas 50000000 --keyring-backend test -y", 
+        cosmos_app_path, command, args.join(" "), cosmos_home_path, COSMOS_SIGNER, COSMOS_CHAIN_ID);
 
-    println!("cmd_str: {}", cmd_str);
     info!("cmd_str: {}", cmd_str);
     
     let output = Command::new("sh").arg("-c").arg(cmd_str).output();
     match output {
         Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
+            let output_str = String::from_utf8_lossy(&output.stdout).to_string();
             info!("output: {}", output_str);
-            // println!("{:?}", String::from_utf8_lossy(&output.stdout));
+            if output.status.success() {
+                Ok(output_str)
+            } else {
+                Err(format!("{:?}: {}", output.status, String::from_utf8_lossy(&output.stderr)))
+            }
         },
-        Err(e) => {
-            error!("Error: {:?}", e);
-        }
+        Err(e) => Err(format!("{:?}", e)),
     }
 }
 
@@ -115,6 +169,27 @@ impl HttpClient {
         
     }
 
+    /// POST a JSON `body` to `url`.
+    pub fn post<U: ToString>(&self, url: U, body: String) -> Result<String, String> {
+        tokio::task::block_in_place(move || self.runtime().block_on(async {
+            let response = self.rpc_client
+                .post(url.to_string())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body)
+                .send()
+                .await
+                .map_err(|e| format!("Error: {:?}", e))?;
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if status.is_success() {
+                Ok(body)
+            } else {
+                error!("Error: {:?}, {:?}", status, body);
+                Err(format!("{:?}: {:?}", status, body))
+            }
+        }))
+    }
+
     pub fn runtime(&self) -> &tokio::runtime::Runtime {
         self.runtime.as_ref().expect("runtime")
     }
@@ -0,0 +1,68 @@
+//! Errors of the remote account loader.
+
+use {
+    crate::remote_loader::RemoteNamespace,
+    solana_sdk::{pubkey::Pubkey, transaction::RemoteAccountUnavailableReason},
+    thiserror::Error,
+};
+
+/// Reasons a remote account could not be loaded.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RemoteLoadError {
+    #[error("account {pubkey} does not exist on its source")]
+    NotFound { pubkey: Pubkey },
+
+    #[error("failed to fetch account {pubkey} from {url}: {reason}")]
+    SourceUnreachable {
+        pubkey: Pubkey,
+        url: String,
+        reason: String,
+    },
+
+    #[error("account {pubkey} requested from hypergrid node {node} with role {role}, which serves no accounts")]
+    InvalidSource {
+        pubkey: Pubkey,
+        node: Pubkey,
+        role: i32,
+    },
+
+    #[error("invalid data for account {pubkey}: {reason}")]
+    InvalidData { pubkey: Pubkey, reason: String },
+
+    #[error("account {pubkey} was rejected by the quorum of sources")]
+    QuorumRejected { pubkey: Pubkey },
+
+    #[error("account {pubkey} requested from {requested} is already loaded from {loaded}, which takes precedence")]
+    NamespaceConflict {
+        pubkey: Pubkey,
+        loaded: RemoteNamespace,
+        requested: RemoteNamespace,
+    },
+}
+
+impl RemoteLoadError {
+    /// The account that failed to load.
+    pub fn pubkey(&self) -> &Pubkey {
+        match self {
+            Self::NotFound { pubkey }
+            | Self::SourceUnreachable { pubkey, .. }
+            | Self::InvalidSource { pubkey, .. }
+            | Self::InvalidData { pubkey, .. }
+            | Self::QuorumRejected { pubkey }
+            | Self::NamespaceConflict { pubkey, .. } => pubkey,
+        }
+    }
+}
+
+impl From<&RemoteLoadError> for RemoteAccountUnavailableReason {
+    fn from(err: &RemoteLoadError) -> Self {
+        match err {
+            RemoteLoadError::NotFound { .. } => Self::NotFound,
+            RemoteLoadError::SourceUnreachable { .. } => Self::SourceUnreachable,
+            RemoteLoadError::InvalidSource { .. } => Self::InvalidSource,
+            RemoteLoadError::InvalidData { .. } => Self::InvalidData,
+            RemoteLoadError::QuorumRejected { .. } => Self::QuorumRejected,
+            RemoteLoadError::NamespaceConflict { .. } => Self::NamespaceConflict,
+        }
+    }
+}
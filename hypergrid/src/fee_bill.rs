@@ -0,0 +1,338 @@
+//! Sonic: fee bills generated from the transaction fees collected on the grid.
+//!
+//! Collected fees of rooted slots are aggregated into bills with monotonically
+//! increasing ids. Bills are persisted before they are submitted, so a failed
+//! `SettleFeeBill` or HSSN submission is retried with exactly the same range.
+
+use {
+    crate::{config::FeeBillConfig, cosmos},
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        account::ReadableAccount,
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+        sonic_fee_settlement::{
+            instruction::{derive_settlement_cursor_address, settle_fee_bill, SettlementBillParam},
+            state::SettlementState,
+        },
+        transaction::Transaction,
+    },
+    std::{
+        collections::HashMap,
+        fs::{self, File},
+        io::{self, BufReader, BufWriter},
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::Duration,
+    },
+};
+
+pub const FEE_BILL_LEDGER_FILE: &str = "fee_bills.json";
+
+/// A contiguous range of bills, settled by one `SettleFeeBill` instruction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeeBillBatch {
+    pub from_id: u64,
+    pub end_id: u64,
+    /// Rooted slots the bills were aggregated from (inclusive).
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+    pub bills: Vec<SettlementBillParam>,
+    pub settled: bool,
+    pub hssn_recorded: bool,
+}
+
+impl FeeBillBatch {
+    pub fn is_done(&self) -> bool {
+        self.settled && self.hssn_recorded
+    }
+}
+
+/// Fees aggregated over rooted slots that are not billed yet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UnbilledFees {
+    /// First rooted slot the fees were aggregated from.
+    pub start_slot: Slot,
+    pub fees: Vec<SettlementBillParam>,
+}
+
+/// Persistent state of the fee bill generator.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct FeeBillLedger {
+    /// The id assigned to the next bill.
+    pub next_bill_id: u64,
+    /// The last rooted slot whose fees were turned into bills.
+    pub last_billed_slot: Option<Slot>,
+    /// The last rooted slot whose fees were aggregated, billed or not.
+    #[serde(default)]
+    pub last_rooted_slot: Option<Slot>,
+    /// Fees of the rooted slots after `last_billed_slot`, so they survive a restart.
+    #[serde(default)]
+    pub unbilled: Option<UnbilledFees>,
+    /// Batches that are not yet settled or recorded in HSSN, in id order.
+    pub pending: Vec<FeeBillBatch>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl FeeBillLedger {
+    /// Load the ledger from `dir`, starting a new one if none exists.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let path = dir.join(FEE_BILL_LEDGER_FILE);
+        let mut ledger = if path.exists() {
+            let file = File::open(&path)?;
+            serde_json::from_reader(BufReader::new(file))
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?
+        } else {
+            Self::default()
+        };
+        ledger.path = path;
+        Ok(ledger)
+    }
+
+    /// Atomically write the ledger back to disk.
+    pub fn save(&self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        {
+            let file = File::create(&tmp_path)?;
+            serde_json::to_writer(BufWriter::new(file), self)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+        }
+        fs::rename(tmp_path, &self.path)
+    }
+
+    /// Check if the fees of `slot` were already billed.
+    pub fn is_billed(&self, slot: Slot) -> bool {
+        self.last_billed_slot
+            .map(|last_billed_slot| slot <= last_billed_slot)
+            .unwrap_or(false)
+    }
+
+    /// Check if the fees of `slot` were already aggregated, into bills or into `unbilled`.
+    pub fn is_aggregated(&self, slot: Slot) -> bool {
+        self.last_rooted_slot
+            .or(self.last_billed_slot)
+            .map(|last_rooted_slot| slot <= last_rooted_slot)
+            .unwrap_or(false)
+    }
+
+    /// Record the fees aggregated over the rooted slots `[start_slot, root]` that are not
+    /// billed yet.
+    pub fn set_unbilled(&mut self, start_slot: Slot, root: Slot, fees: &HashMap<Pubkey, u64>) {
+        let mut fees: Vec<_> = fees
+            .iter()
+            .map(|(key, amount)| SettlementBillParam {
+                key: *key,
+                amount: *amount,
+            })
+            .collect();
+        fees.sort_by(|a, b| a.key.cmp(&b.key));
+        self.unbilled = Some(UnbilledFees { start_slot, fees });
+        self.last_rooted_slot = Some(root);
+    }
+
+    /// Turn the fees aggregated over the rooted slots `[start_slot, end_slot]`
+    /// into bills, split into batches of at most `max_bills_per_batch` bills.
+    pub fn add_bills(
+        &mut self,
+        start_slot: Slot,
+        end_slot: Slot,
+        fees: HashMap<Pubkey, u64>,
+        max_bills_per_batch: usize,
+    ) {
+        let mut bills: Vec<_> = fees
+            .into_iter()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(key, amount)| SettlementBillParam { key, amount })
+            .collect();
+        // Deterministic order, so bill ids can be reproduced from the ledger.
+        bills.sort_by(|a, b| a.key.cmp(&b.key));
+
+        for chunk in bills.chunks(max_bills_per_batch.max(1)) {
+            let from_id = self.next_bill_id;
+            let end_id = from_id + chunk.len() as u64 - 1;
+            self.next_bill_id = end_id + 1;
+            self.pending.push(FeeBillBatch {
+                from_id,
+                end_id,
+                start_slot,
+                end_slot,
+                bills: chunk.to_vec(),
+                settled: false,
+                hssn_recorded: false,
+            });
+        }
+        self.last_billed_slot = Some(end_slot);
+        self.last_rooted_slot = Some(end_slot);
+        self.unbilled = None;
+    }
+
+    /// Drop the batches that are settled and recorded in HSSN.
+    pub fn prune(&mut self) {
+        self.pending.retain(|batch| !batch.is_done());
+    }
+}
+
+/// Submits fee bill batches to the fee settlement program and HSSN.
+pub struct FeeBillSubmitter {
+    rpc_client: RpcClient,
+    settler: Keypair,
+    settlement_accounts: Vec<Pubkey>,
+    hssn_tx: cosmos::HssnTxSubmitter,
+}
+
+impl FeeBillSubmitter {
+    /// Bills are recorded through `hssn_tx_url`, the `hssn_tx_url` of the hypergrid config.
+    pub fn new(config: &FeeBillConfig, hssn_tx_url: Option<&str>) -> Result<Self, String> {
+        let settler = read_keypair_file(&config.settler_keypair_file).map_err(|err| {
+            format!(
+                "failed to read settler keypair {}: {err}",
+                config.settler_keypair_file
+            )
+        })?;
+        let settlement_accounts = config
+            .settlement_accounts
+            .iter()
+            .map(|address| Pubkey::from_str(address).map_err(|err| format!("{address}: {err}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            rpc_client: RpcClient::new_with_timeout_and_commitment(
+                config.settlement_rpc_url.clone(),
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+            settler,
+            settlement_accounts,
+            hssn_tx: cosmos::HssnTxSubmitter::new(hssn_tx_url),
+        })
+    }
+
+    /// The id of the next bill expected by the settlement cursor.
+    fn settled_next_bill_id(&self) -> Result<u64, String> {
+        let (cursor_address, _) = derive_settlement_cursor_address();
+        let account = self
+            .rpc_client
+            .get_account(&cursor_address)
+            .map_err(|err| format!("failed to load settlement cursor: {err}"))?;
+        match bincode::deserialize::<SettlementState>(account.data()) {
+            Ok(SettlementState::SettlementCursor(cursor)) => Ok(cursor.next_bill_id),
+            _ => Err(format!("{cursor_address} is not a settlement cursor")),
+        }
+    }
+
+    /// Settle `batch` unless the settlement cursor shows it was already settled,
+    /// which makes retrying a batch safe.
+    pub fn settle(&self, batch: &mut FeeBillBatch) -> Result<(), String> {
+        if batch.settled {
+            return Ok(());
+        }
+        let next_bill_id = self.settled_next_bill_id()?;
+        if next_bill_id > batch.end_id {
+            info!(
+                "fee bills {}..={} are already settled",
+                batch.from_id, batch.end_id
+            );
+            batch.settled = true;
+            return Ok(());
+        }
+        if next_bill_id != batch.from_id {
+            return Err(format!(
+                "fee bills {}..={} can't be settled, the cursor expects bill {}",
+                batch.from_id, batch.end_id, next_bill_id
+            ));
+        }
+
+        let instruction = settle_fee_bill(
+            self.settler.pubkey(),
+            self.settlement_accounts.clone(),
+            batch.from_id,
+            batch.end_id,
+            batch.bills.clone(),
+        );
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash()
+            .map_err(|err| format!("failed to get blockhash: {err}"))?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.settler.pubkey()),
+            &[&self.settler],
+            blockhash,
+        );
+        let signature = self
+            .rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|err| {
+                format!(
+                    "failed to settle fee bills {}..={}: {err}",
+                    batch.from_id, batch.end_id
+                )
+            })?;
+        info!(
+            "fee bills {}..={} settled: {}",
+            batch.from_id, batch.end_id, signature
+        );
+        batch.settled = true;
+        Ok(())
+    }
+
+    /// Record `batch` in HSSN.
+    pub fn record_in_hssn(&self, batch: &mut FeeBillBatch) -> Result<(), String> {
+        if batch.hssn_recorded {
+            return Ok(());
+        }
+        let bills = serde_json::to_string(&batch.bills).map_err(|err| format!("{err:?}"))?;
+        self.hssn_tx
+            .run_create_fee_settlement_bill(batch.from_id, batch.end_id, &bills)?;
+        batch.hssn_recorded = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_bill_ledger_add_bills() {
+        let dir = std::env::temp_dir().join(format!("fee_bill_ledger_{}", Pubkey::new_unique()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut ledger = FeeBillLedger::open(&dir).unwrap();
+        assert!(!ledger.is_billed(0));
+
+        let fees: HashMap<Pubkey, u64> = (0..5).map(|i| (Pubkey::new_unique(), i)).collect();
+        ledger.add_bills(1, 10, fees, 2);
+        // The zero fee is dropped, the rest is split into batches of 2
+        assert_eq!(ledger.pending.len(), 2);
+        assert_eq!(
+            (ledger.pending[0].from_id, ledger.pending[0].end_id),
+            (0, 1)
+        );
+        assert_eq!(
+            (ledger.pending[1].from_id, ledger.pending[1].end_id),
+            (2, 3)
+        );
+        assert_eq!(ledger.next_bill_id, 4);
+        assert!(ledger.is_billed(10));
+        assert!(!ledger.is_billed(11));
+
+        ledger.pending[0].settled = true;
+        ledger.pending[0].hssn_recorded = true;
+        ledger.prune();
+        ledger.set_unbilled(11, 12, &HashMap::from([(Pubkey::new_unique(), 7)]));
+        assert!(ledger.is_aggregated(12));
+        assert!(!ledger.is_aggregated(13));
+        ledger.save().unwrap();
+
+        let reopened = FeeBillLedger::open(&dir).unwrap();
+        assert_eq!(reopened, ledger);
+        assert_eq!(reopened.pending.len(), 1);
+        assert_eq!(reopened.pending[0].from_id, 2);
+        assert_eq!(reopened.unbilled.as_ref().unwrap().start_slot, 11);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
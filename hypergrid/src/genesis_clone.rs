@@ -0,0 +1,239 @@
+//! Cloning of base layer accounts and upgradeable programs into the genesis of a grid.
+//!
+//! The cloned accounts are recorded in a [`GenesisCloneRecord`], with the slot they were read
+//! at, so that the grid knows which base layer state it started from.
+
+use {
+    crate::{config::Config, remote_loader::RemoteAccountLoader},
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcAccountInfoConfig},
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        account_utils::StateMut,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+        sonic_account_migrater::state::{CloneSource, ClonedAccount, GenesisCloneRecord},
+    },
+    std::time::Duration,
+};
+
+enum Fetcher {
+    Rpc(RpcClient),
+    Hssn(Box<RemoteAccountLoader>),
+}
+
+/// Fetches the accounts to clone into genesis from a single source.
+pub struct GenesisCloner {
+    fetcher: Fetcher,
+    record: GenesisCloneRecord,
+    accounts: Vec<(Pubkey, AccountSharedData)>,
+}
+
+impl GenesisCloner {
+    /// Clone from the base layer RPC node at `url`. RPC nodes only serve their latest state:
+    /// the accounts are read at `slot` or later, and the slot each one was read at is recorded.
+    pub fn new_with_rpc(url: String, slot: Slot) -> Self {
+        let rpc_client = RpcClient::new_with_timeout_and_commitment(
+            url.clone(),
+            Duration::from_secs(30),
+            CommitmentConfig::finalized(),
+        );
+        Self::new(Fetcher::Rpc(rpc_client), CloneSource::BaseLayer, url, slot)
+    }
+
+    /// Clone the accounts recorded in the HSSN of `config`. HSSN records carry no slot, so
+    /// only `slot` is recorded.
+    pub fn new_with_hssn(config: Config, slot: Slot) -> Self {
+        let url = config.hssn_rpc_url.clone();
+        let loader = RemoteAccountLoader::new_with_config(config);
+        Self::new(
+            Fetcher::Hssn(Box::new(loader)),
+            CloneSource::Hssn,
+            url,
+            slot,
+        )
+    }
+
+    fn new(fetcher: Fetcher, source: CloneSource, url: String, slot: Slot) -> Self {
+        Self {
+            fetcher,
+            record: GenesisCloneRecord {
+                source,
+                url,
+                slot,
+                accounts: vec![],
+            },
+            accounts: vec![],
+        }
+    }
+
+    fn fetch(&self, pubkey: &Pubkey) -> Result<(AccountSharedData, Option<Slot>), String> {
+        let url = &self.record.url;
+        match &self.fetcher {
+            Fetcher::Rpc(rpc_client) => {
+                let config = RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64Zstd),
+                    commitment: Some(rpc_client.commitment()),
+                    min_context_slot: Some(self.record.slot),
+                    ..RpcAccountInfoConfig::default()
+                };
+                let response = rpc_client
+                    .get_account_with_config(pubkey, config)
+                    .map_err(|err| format!("failed to fetch {pubkey} from {url}: {err}"))?;
+                let account = response
+                    .value
+                    .ok_or_else(|| format!("account {pubkey} does not exist on {url}"))?;
+                Ok((
+                    AccountSharedData::from(account),
+                    Some(response.context.slot),
+                ))
+            }
+            Fetcher::Hssn(loader) => {
+                let account = loader
+                    .get_hssn_account(pubkey)
+                    .map_err(|err| format!("failed to fetch {pubkey} from {url}: {err}"))?
+                    .ok_or_else(|| format!("account {pubkey} is not recorded in {url}"))?;
+                Ok((account, None))
+            }
+        }
+    }
+
+    fn add(&mut self, pubkey: Pubkey, mut account: AccountSharedData, source_slot: Option<Slot>) {
+        account.remote = false;
+        self.record.accounts.push(ClonedAccount {
+            pubkey,
+            source_slot,
+        });
+        self.accounts.push((pubkey, account));
+    }
+
+    /// Clone the account at `pubkey`.
+    pub fn clone_account(&mut self, pubkey: Pubkey) -> Result<(), String> {
+        let (account, source_slot) = self.fetch(&pubkey)?;
+        self.add(pubkey, account, source_slot);
+        Ok(())
+    }
+
+    /// Clone the upgradeable program `program_id` and its programdata account.
+    pub fn clone_upgradeable_program(&mut self, program_id: Pubkey) -> Result<(), String> {
+        let (program, source_slot) = self.fetch(&program_id)?;
+        if !bpf_loader_upgradeable::check_id(program.owner()) {
+            return Err(format!(
+                "{program_id} is not an upgradeable program, its owner is {}",
+                program.owner()
+            ));
+        }
+        let Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = program.state()
+        else {
+            return Err(format!(
+                "{program_id} is not an upgradeable program account"
+            ));
+        };
+        let (mut programdata, programdata_slot) = self.fetch(&programdata_address)?;
+        reset_programdata_slot(&programdata_address, &mut programdata)?;
+        self.add(program_id, program, source_slot);
+        self.add(programdata_address, programdata, programdata_slot);
+        Ok(())
+    }
+
+    /// The cloned accounts, and the record of the clone.
+    pub fn finish(self) -> (Vec<(Pubkey, AccountSharedData)>, GenesisCloneRecord) {
+        (self.accounts, self.record)
+    }
+}
+
+/// Overwrite the deployment slot of a cloned programdata account with `0`: the base layer
+/// slot it was deployed at is meaningless on the grid, and would keep the program from
+/// being invoked until the grid reaches that slot.
+pub fn reset_programdata_slot(
+    programdata_address: &Pubkey,
+    programdata: &mut AccountSharedData,
+) -> Result<(), String> {
+    let Ok(UpgradeableLoaderState::ProgramData {
+        upgrade_authority_address,
+        ..
+    }) = programdata.state()
+    else {
+        return Err(format!(
+            "{programdata_address} is not an upgradeable programdata account"
+        ));
+    };
+    programdata
+        .set_state(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address,
+        })
+        .map_err(|err| format!("failed to write programdata account {programdata_address}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, serde_json::json, solana_account_decoder::UiAccount,
+        solana_client::rpc_request::RpcRequest, std::collections::HashMap,
+    };
+
+    fn cloner_with_account(
+        pubkey: &Pubkey,
+        account: &AccountSharedData,
+        slot: Slot,
+    ) -> GenesisCloner {
+        let value = UiAccount::encode(pubkey, account, UiAccountEncoding::Base64Zstd, None, None);
+        let mocks = HashMap::from([(
+            RpcRequest::GetAccountInfo,
+            json!({"context": {"slot": slot}, "value": value}),
+        )]);
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        GenesisCloner::new(
+            Fetcher::Rpc(rpc_client),
+            CloneSource::BaseLayer,
+            "succeeds".to_string(),
+            100,
+        )
+    }
+
+    #[test]
+    fn test_genesis_clone() {
+        let pubkey = Pubkey::new_unique();
+        let mut account = AccountSharedData::new(42, 3, &Pubkey::new_unique());
+        account.set_data_from_slice(&[1, 2, 3]);
+        let mut cloner = cloner_with_account(&pubkey, &account, 105);
+        cloner.clone_account(pubkey).unwrap();
+        // The mocked account is served once, missing accounts fail the clone
+        assert!(cloner.clone_account(Pubkey::new_unique()).is_err());
+        let (accounts, record) = cloner.finish();
+        assert_eq!(accounts, vec![(pubkey, account.clone())]);
+        assert_eq!(record.source, CloneSource::BaseLayer);
+        assert_eq!(record.slot, 100);
+        assert_eq!(
+            record.accounts,
+            vec![ClonedAccount {
+                pubkey,
+                source_slot: Some(105),
+            }]
+        );
+
+        // Programs are only cloned along with their programdata
+        let program_id = Pubkey::new_unique();
+        let program = AccountSharedData::new_data(
+            42,
+            &UpgradeableLoaderState::Program {
+                programdata_address: Pubkey::new_unique(),
+            },
+            &bpf_loader_upgradeable::id(),
+        )
+        .unwrap();
+        let mut cloner = cloner_with_account(&program_id, &program, 105);
+        assert!(cloner.clone_upgradeable_program(program_id).is_err());
+        let (accounts, record) = cloner.finish();
+        assert!(accounts.is_empty());
+        assert!(record.accounts.is_empty());
+
+        let mut cloner = cloner_with_account(&pubkey, &account, 105);
+        assert!(cloner.clone_upgradeable_program(pubkey).is_err());
+    }
+}
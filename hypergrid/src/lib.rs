@@ -1,6 +1,13 @@
 #![cfg_attr(RUSTC_WITH_SPECIALIZATION, feature(min_specialization))]
 
-mod config;
+pub mod config;
 mod cosmos;
+pub mod error;
+pub mod fee_bill;
+pub mod genesis_clone;
+pub mod quorum;
+pub mod remote_cache;
 pub mod remote_loader;
+pub mod remote_source;
+pub mod rpc_pool;
 
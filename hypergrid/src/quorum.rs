@@ -0,0 +1,311 @@
+//! Quorum verification of remote accounts.
+//!
+//! A single base layer RPC node or HSSN response is otherwise trusted as is. When a
+//! [`QuorumConfig`] is set, the [`RemoteAccountLoader`](crate::remote_loader::RemoteAccountLoader)
+//! fetches every loaded account from independent sources as well, and only caches it if enough
+//! copies match.
+
+use {
+    crate::config::QuorumConfig,
+    log::*,
+    solana_client::rpc_client::RpcClient,
+    solana_metrics::{datapoint_info, datapoint_warn},
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        commitment_config::CommitmentConfig,
+        hash::{hash, Hash},
+        pubkey::Pubkey,
+    },
+    std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    },
+};
+
+/// A source remote accounts are verified against.
+pub trait QuorumSource: Send + Sync {
+    /// Name the source is reported by in the metrics.
+    fn name(&self) -> &str;
+
+    /// Fetch `pubkey` and the slot it was read at, `Ok(None)` if the source has no such account.
+    fn get_account(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<(AccountSharedData, Option<Slot>)>, String>;
+}
+
+/// A base layer RPC node remote accounts are verified against.
+pub struct RpcQuorumSource {
+    url: String,
+    rpc_client: RpcClient,
+}
+
+impl RpcQuorumSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            rpc_client: RpcClient::new_with_timeout_and_commitment(
+                url.clone(),
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+            url,
+        }
+    }
+}
+
+impl QuorumSource for RpcQuorumSource {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    fn get_account(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<(AccountSharedData, Option<Slot>)>, String> {
+        let response = self
+            .rpc_client
+            .get_account_with_commitment(pubkey, self.rpc_client.commitment())
+            .map_err(|err| err.to_string())?;
+        let slot = response.context.slot;
+        Ok(response
+            .value
+            .map(|account| (AccountSharedData::from(account), Some(slot))))
+    }
+}
+
+/// The parts of a copy of an account compared by the quorum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AccountCopy {
+    lamports: u64,
+    owner: Pubkey,
+    data_hash: Hash,
+    slot: Option<Slot>,
+}
+
+impl AccountCopy {
+    fn new(account: &AccountSharedData, slot: Option<Slot>) -> Self {
+        Self {
+            lamports: account.lamports(),
+            owner: *account.owner(),
+            data_hash: hash(account.data()),
+            slot,
+        }
+    }
+
+    /// The first field `other` disagrees on, `None` if it matches. Slots are only compared
+    /// when both are known.
+    fn mismatch(&self, other: &Self, slot_tolerance: u64) -> Option<&'static str> {
+        if self.lamports != other.lamports {
+            Some("lamports")
+        } else if self.owner != other.owner {
+            Some("owner")
+        } else if self.data_hash != other.data_hash {
+            Some("data")
+        } else if self
+            .slot
+            .zip(other.slot)
+            .is_some_and(|(a, b)| a.abs_diff(b) > slot_tolerance)
+        {
+            Some("slot")
+        } else {
+            None
+        }
+    }
+}
+
+/// Counters of the quorum verifications, also reported as metrics.
+#[derive(Debug, Default)]
+pub struct QuorumStats {
+    /// Accounts accepted by the quorum.
+    pub verified: AtomicU64,
+    /// Accounts rejected for lack of matching copies.
+    pub rejected: AtomicU64,
+    /// Copies disagreeing with the account being verified.
+    pub disagreements: AtomicU64,
+    /// Sources that could not be queried.
+    pub source_errors: AtomicU64,
+}
+
+/// Requires `min_matches` matching copies of a remote account, the loaded one included,
+/// before it is cached.
+pub struct QuorumVerifier {
+    sources: Vec<Arc<dyn QuorumSource>>,
+    min_matches: usize,
+    slot_tolerance: u64,
+    stats: QuorumStats,
+}
+
+impl QuorumVerifier {
+    pub fn new(
+        sources: Vec<Arc<dyn QuorumSource>>,
+        min_matches: usize,
+        slot_tolerance: u64,
+    ) -> Self {
+        if sources.len() + 1 < min_matches {
+            warn!(
+                "quorum of {} copies can never be reached with {} sources",
+                min_matches,
+                sources.len()
+            );
+        }
+        Self {
+            sources,
+            min_matches,
+            slot_tolerance,
+            stats: QuorumStats::default(),
+        }
+    }
+
+    /// Verify against the RPC nodes of `config`.
+    pub fn new_from_config(config: &QuorumConfig) -> Self {
+        let sources = config
+            .rpc_urls
+            .iter()
+            .map(|url| Arc::new(RpcQuorumSource::new(url.clone())) as Arc<dyn QuorumSource>)
+            .collect();
+        Self::new(sources, config.min_matches, config.slot_tolerance)
+    }
+
+    pub fn stats(&self) -> &QuorumStats {
+        &self.stats
+    }
+
+    /// Check `account`, read at `slot` if known, against the sources. Returns whether enough
+    /// copies match it.
+    pub fn verify(&self, pubkey: &Pubkey, account: &AccountSharedData, slot: Option<Slot>) -> bool {
+        let candidate = AccountCopy::new(account, slot);
+        let copies: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .sources
+                .iter()
+                .map(|source| scope.spawn(move || source.get_account(pubkey)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err("source panicked".to_string()))
+                })
+                .collect()
+        });
+
+        let mut matches = 1;
+        for (source, copy) in self.sources.iter().zip(copies) {
+            let reason = match copy {
+                Ok(Some((account, slot))) => {
+                    match candidate.mismatch(&AccountCopy::new(&account, slot), self.slot_tolerance)
+                    {
+                        None => {
+                            matches += 1;
+                            continue;
+                        }
+                        Some(reason) => reason,
+                    }
+                }
+                Ok(None) => "missing",
+                Err(err) => {
+                    warn!(
+                        "quorum source {} failed for {}: {}",
+                        source.name(),
+                        pubkey,
+                        err
+                    );
+                    self.stats.source_errors.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+            };
+            self.stats.disagreements.fetch_add(1, Ordering::Relaxed);
+            datapoint_warn!(
+                "sonic-remote-quorum-disagreement",
+                ("pubkey", pubkey.to_string(), String),
+                ("source", source.name(), String),
+                ("reason", reason, String),
+            );
+        }
+
+        let verified = matches >= self.min_matches;
+        if verified {
+            self.stats.verified.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.stats.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+        datapoint_info!(
+            "sonic-remote-quorum",
+            ("matches", matches, i64),
+            ("sources", self.sources.len() + 1, i64),
+            ("verified", verified, bool),
+        );
+        verified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::WritableAccount};
+
+    struct FixedSource(Result<Option<(AccountSharedData, Option<Slot>)>, String>);
+
+    impl QuorumSource for FixedSource {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn get_account(
+            &self,
+            _pubkey: &Pubkey,
+        ) -> Result<Option<(AccountSharedData, Option<Slot>)>, String> {
+            self.0.clone()
+        }
+    }
+
+    fn source(account: Option<&AccountSharedData>, slot: Option<Slot>) -> Arc<dyn QuorumSource> {
+        Arc::new(FixedSource(Ok(
+            account.map(|account| (account.clone(), slot))
+        )))
+    }
+
+    #[test]
+    fn test_quorum_verifier() {
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::new(42, 8, &Pubkey::new_unique());
+        let mut data_changed = account.clone();
+        data_changed.data_as_mut_slice()[0] = 1;
+
+        // Two matching copies out of three, one within the slot tolerance
+        let verifier = QuorumVerifier::new(
+            vec![
+                source(Some(&account), Some(105)),
+                source(Some(&data_changed), Some(100)),
+            ],
+            2,
+            10,
+        );
+        assert!(verifier.verify(&pubkey, &account, Some(100)));
+        assert_eq!(verifier.stats().verified.load(Ordering::Relaxed), 1);
+        assert_eq!(verifier.stats().disagreements.load(Ordering::Relaxed), 1);
+
+        // Too far apart, missing or failing sources do not count
+        let verifier = QuorumVerifier::new(
+            vec![
+                source(Some(&account), Some(200)),
+                source(None, None),
+                Arc::new(FixedSource(Err("timeout".to_string()))),
+            ],
+            2,
+            10,
+        );
+        assert!(!verifier.verify(&pubkey, &account, Some(100)));
+        assert_eq!(verifier.stats().rejected.load(Ordering::Relaxed), 1);
+        assert_eq!(verifier.stats().disagreements.load(Ordering::Relaxed), 2);
+        assert_eq!(verifier.stats().source_errors.load(Ordering::Relaxed), 1);
+
+        // Slots are not compared when unknown, as for HSSN records
+        assert!(verifier.verify(&pubkey, &account, None));
+    }
+}
@@ -0,0 +1,348 @@
+//! Bounded cache of the remote accounts.
+//!
+//! Every cached account is charged its data length plus a fixed overhead. Once an insertion
+//! takes the cache over one of the limits of its [`RemoteCacheConfig`], entries are evicted
+//! following its [`EvictionPolicy`], except for the pinned ones: those referenced by slots
+//! that are not rooted yet.
+
+use {
+    crate::{
+        config::{EvictionPolicy, RemoteCacheConfig},
+        remote_loader::RemoteOrigin,
+    },
+    dashmap::DashMap,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::BTreeMap,
+        mem,
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            Mutex,
+        },
+        time::Instant,
+    },
+};
+
+/// Bytes charged for a cached account on top of its data.
+pub const CACHED_ACCOUNT_OVERHEAD: usize = mem::size_of::<Pubkey>() + mem::size_of::<Entry>();
+
+#[derive(Debug)]
+struct Entry {
+    account: AccountSharedData,
+    loaded_at: Instant,
+    origin: RemoteOrigin,
+    /// Tick of the cache clock the entry was last used at.
+    last_used: AtomicU64,
+    /// Number of times the entry was used.
+    uses: AtomicU64,
+    /// Highest slot referencing the entry, until it is rooted.
+    pinned_until: Option<Slot>,
+}
+
+impl Entry {
+    fn size(&self) -> usize {
+        self.account.data().len() + CACHED_ACCOUNT_OVERHEAD
+    }
+}
+
+/// An account evicted from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictedAccount {
+    pub pubkey: Pubkey,
+    pub account: AccountSharedData,
+    pub origin: RemoteOrigin,
+}
+
+/// Cache of the remote accounts, bounded in number of accounts and bytes.
+#[derive(Debug)]
+pub struct RemoteAccountCache {
+    entries: DashMap<Pubkey, Entry>,
+    /// Accounts pinned by each unrooted slot.
+    pins: Mutex<BTreeMap<Slot, Vec<Pubkey>>>,
+    config: RemoteCacheConfig,
+    bytes: AtomicUsize,
+    /// Logical clock ordering the uses of the entries.
+    clock: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl RemoteAccountCache {
+    pub fn new(config: RemoteCacheConfig) -> Self {
+        Self {
+            entries: DashMap::default(),
+            pins: Mutex::default(),
+            config,
+            bytes: AtomicUsize::default(),
+            clock: AtomicU64::default(),
+            evictions: AtomicU64::default(),
+        }
+    }
+
+    fn touch(&self, entry: &Entry) {
+        let tick = self.clock.fetch_add(1, Ordering::Relaxed);
+        entry.last_used.store(tick, Ordering::Relaxed);
+        entry.uses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The cached account, when it was loaded and where from.
+    pub fn get(&self, pubkey: &Pubkey) -> Option<(AccountSharedData, Instant, RemoteOrigin)> {
+        self.entries.get(pubkey).map(|entry| {
+            self.touch(&entry);
+            (entry.account.clone(), entry.loaded_at, entry.origin)
+        })
+    }
+
+    pub fn contains_key(&self, pubkey: &Pubkey) -> bool {
+        self.entries.contains_key(pubkey)
+    }
+
+    /// Cache `account`, then evict other entries until the cache is within its limits.
+    /// Returns the replaced entry, if any, and the evicted ones. The pin of a replaced entry
+    /// is kept.
+    pub fn insert(
+        &self,
+        pubkey: Pubkey,
+        account: AccountSharedData,
+        origin: RemoteOrigin,
+    ) -> (Option<EvictedAccount>, Vec<EvictedAccount>) {
+        let mut entry = Entry {
+            account,
+            loaded_at: Instant::now(),
+            origin,
+            last_used: AtomicU64::default(),
+            uses: AtomicU64::default(),
+            pinned_until: None,
+        };
+        self.touch(&entry);
+        self.bytes.fetch_add(entry.size(), Ordering::Relaxed);
+        let replaced = self.entries.get(&pubkey).and_then(|old| old.pinned_until);
+        entry.pinned_until = replaced;
+        let replaced = self.entries.insert(pubkey, entry).map(|old| {
+            self.bytes.fetch_sub(old.size(), Ordering::Relaxed);
+            EvictedAccount {
+                pubkey,
+                account: old.account,
+                origin: old.origin,
+            }
+        });
+        (replaced, self.evict_to_limits(&pubkey))
+    }
+
+    /// Remove the account from the cache.
+    pub fn remove(&self, pubkey: &Pubkey) -> Option<EvictedAccount> {
+        self.entries.remove(pubkey).map(|(pubkey, entry)| {
+            self.bytes.fetch_sub(entry.size(), Ordering::Relaxed);
+            EvictedAccount {
+                pubkey,
+                account: entry.account,
+                origin: entry.origin,
+            }
+        })
+    }
+
+    /// Keep the account cached until `slot` is rooted.
+    pub fn pin(&self, pubkey: &Pubkey, slot: Slot) {
+        if let Some(mut entry) = self.entries.get_mut(pubkey) {
+            // `None` orders below any slot
+            if entry.pinned_until < Some(slot) {
+                entry.pinned_until = Some(slot);
+                self.pins
+                    .lock()
+                    .unwrap()
+                    .entry(slot)
+                    .or_default()
+                    .push(*pubkey);
+            }
+        }
+    }
+
+    /// Release the pins of the slots up to `root`, then evict entries until the cache is
+    /// within its limits.
+    pub fn unpin(&self, root: Slot) -> Vec<EvictedAccount> {
+        let unpinned = {
+            let mut pins = self.pins.lock().unwrap();
+            let pinned = pins.split_off(&root.saturating_add(1));
+            mem::replace(&mut *pins, pinned)
+        };
+        for pubkey in unpinned.into_values().flatten() {
+            if let Some(mut entry) = self.entries.get_mut(&pubkey) {
+                if entry.pinned_until.is_some_and(|slot| slot <= root) {
+                    entry.pinned_until = None;
+                }
+            }
+        }
+        self.evict_to_limits(&Pubkey::default())
+    }
+
+    fn over_limits(&self, accounts: usize, bytes: usize) -> bool {
+        accounts > self.config.max_accounts || bytes > self.config.max_bytes
+    }
+
+    /// Evict unpinned entries other than `keep` until the cache is within its limits, or only
+    /// such entries remain.
+    fn evict_to_limits(&self, keep: &Pubkey) -> Vec<EvictedAccount> {
+        let mut accounts = self.entries.len();
+        let mut bytes = self.bytes.load(Ordering::Relaxed);
+        if !self.over_limits(accounts, bytes) {
+            return vec![];
+        }
+
+        let mut candidates: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key() != keep && entry.pinned_until.is_none())
+            .map(|entry| {
+                let last_used = entry.last_used.load(Ordering::Relaxed);
+                let rank = match self.config.policy {
+                    EvictionPolicy::Lru => (0, last_used),
+                    EvictionPolicy::Lfu => (entry.uses.load(Ordering::Relaxed), last_used),
+                };
+                (rank, *entry.key())
+            })
+            .collect();
+        candidates.sort_unstable();
+
+        let mut evicted = vec![];
+        for (_, pubkey) in candidates {
+            if !self.over_limits(accounts, bytes) {
+                break;
+            }
+            // Skip the entries pinned since they were ranked
+            let Some((pubkey, entry)) = self
+                .entries
+                .remove_if(&pubkey, |_, entry| entry.pinned_until.is_none())
+            else {
+                continue;
+            };
+            let size = entry.size();
+            self.bytes.fetch_sub(size, Ordering::Relaxed);
+            accounts -= 1;
+            bytes = bytes.saturating_sub(size);
+            evicted.push(EvictedAccount {
+                pubkey,
+                account: entry.account,
+                origin: entry.origin,
+            });
+        }
+        self.evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
+        evicted
+    }
+
+    /// Number of cached accounts.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Total size of the cached accounts, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Number of accounts evicted to keep the cache within its limits.
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Make the cached copy of the account look loaded `age` ago.
+    #[cfg(test)]
+    pub(crate) fn age(&self, pubkey: &Pubkey, age: std::time::Duration) {
+        if let Some(mut entry) = self.entries.get_mut(pubkey) {
+            entry.loaded_at = Instant::now() - age;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(max_accounts: usize, max_bytes: usize, policy: EvictionPolicy) -> RemoteAccountCache {
+        RemoteAccountCache::new(RemoteCacheConfig {
+            max_accounts,
+            max_bytes,
+            policy,
+        })
+    }
+
+    fn insert(cache: &RemoteAccountCache, pubkey: Pubkey, space: usize) -> Vec<Pubkey> {
+        let account = AccountSharedData::new(1, space, &Pubkey::default());
+        let (_, evicted) = cache.insert(pubkey, account, RemoteOrigin::default());
+        evicted.into_iter().map(|evicted| evicted.pubkey).collect()
+    }
+
+    #[test]
+    fn test_remote_cache_lru() {
+        let cache = cache(2, usize::MAX, EvictionPolicy::Lru);
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert!(insert(&cache, a, 0).is_empty());
+        assert!(insert(&cache, b, 0).is_empty());
+        cache.get(&a);
+        assert_eq!(insert(&cache, c, 0), vec![b]);
+        assert!(cache.contains_key(&a));
+        assert_eq!(cache.evictions(), 1);
+
+        // Replacing an entry evicts nothing
+        assert!(insert(&cache, c, 0).is_empty());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_remote_cache_lfu() {
+        let cache = cache(2, usize::MAX, EvictionPolicy::Lfu);
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        insert(&cache, a, 0);
+        insert(&cache, b, 0);
+        cache.get(&a);
+        cache.get(&b);
+        cache.get(&a);
+        assert_eq!(insert(&cache, c, 0), vec![b]);
+    }
+
+    #[test]
+    fn test_remote_cache_bytes_and_pins() {
+        let size = |space| space + CACHED_ACCOUNT_OVERHEAD;
+        let cache = cache(usize::MAX, size(100) * 2, EvictionPolicy::Lru);
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        insert(&cache, a, 100);
+        insert(&cache, b, 100);
+        assert_eq!(cache.bytes(), size(100) * 2);
+
+        // Pinned entries stay cached until their slot is rooted
+        cache.pin(&a, 5);
+        cache.pin(&b, 7);
+        assert!(insert(&cache, c, 50).is_empty());
+        assert_eq!(cache.len(), 3);
+        let evicted = cache.unpin(6);
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].pubkey, a);
+        assert!(cache.unpin(7).is_empty());
+        assert_eq!(cache.bytes(), size(100) + size(50));
+
+        // An entry too large for the cache evicts all others, but is kept
+        assert_eq!(insert(&cache, a, 1000).len(), 2);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.remove(&a).unwrap().pubkey, a);
+        assert_eq!(cache.bytes(), 0);
+    }
+}
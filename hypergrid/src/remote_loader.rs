@@ -1,41 +1,165 @@
 use {
-    crate::{config::Config, cosmos}, base64::{self, Engine}, core::fmt, dashmap::DashMap, log::*, serde_derive::{Deserialize, Serialize}, sha2::{Digest, Sha256}, solana_client::rpc_client::RpcClient, solana_measure::measure::Measure, solana_sdk::{
-        account::{AccountSharedData, ReadableAccount, WritableAccount}, account_utils::StateMut, bpf_loader_upgradeable::{self, UpgradeableLoaderState}, commitment_config::CommitmentConfig, instruction::{AccountMeta, Instruction}, pubkey::Pubkey, signature::{Keypair, Signature, Signer}, signer::EncodableKey, transaction::Transaction
+    crate::{config::{Config, NamespaceKind}, cosmos, error::RemoteLoadError, quorum::QuorumVerifier, remote_cache::{EvictedAccount, RemoteAccountCache}, remote_source::RemoteAccountSource, rpc_pool::RpcClientPool}, base64::{self, Engine}, core::fmt, dashmap::DashMap, log::*, serde_derive::{Deserialize, Serialize}, sha2::{Digest, Sha256}, solana_client::rpc_response::Response, solana_measure::measure::Measure, solana_metrics::{datapoint_info, datapoint_warn}, solana_sdk::{
+        account::{AccountSharedData, ReadableAccount, WritableAccount}, account_utils::StateMut, bpf_loader_upgradeable::{self, UpgradeableLoaderState}, clock::Slot, hash::{hash, Hash}, instruction::{AccountMeta, Instruction}, pubkey::Pubkey, signature::{Keypair, Signature, Signer}, signer::EncodableKey, sonic_remote_account::{self, RemoteAccountMeta}, transaction::Transaction
     }, std::{
-        option_env, str::FromStr, thread,
+        str::FromStr, sync::{Arc, RwLock}, thread,
         time::{Duration, Instant},
     }, zstd
 };
 
 
-type AccountCacheKeyMap = DashMap<Pubkey, (AccountSharedData, Instant)>;
+/// Sonic: where a remote account in the cache was loaded from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteOrigin {
+    /// Node the account was migrated from, `None` for the base layer.
+    pub source: Option<Pubkey>,
+    /// Slot the account was read at on the source, if known.
+    pub source_slot: Option<Slot>,
+    /// Chain the account was loaded from.
+    pub namespace: RemoteNamespace,
+}
+
+/// Sonic: the chain a remote account was loaded from. Chains may hold different accounts at
+/// the same address, `Config::namespace_precedence` says which one is served.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemoteNamespace {
+    /// The base layer at `Config::baselayer_rpc_url`.
+    #[default]
+    BaseLayer,
+    /// Another Solana L1, served by the hypergrid node.
+    L1(Pubkey),
+    /// A grid, served by the hypergrid node.
+    Grid(Pubkey),
+}
+
+impl RemoteOrigin {
+    /// The origin as told to the programs by the `sol_get_remote_account_meta` syscall.
+    pub fn account_meta(&self, remote: bool) -> RemoteAccountMeta {
+        let (namespace, source) = match self.namespace {
+            RemoteNamespace::BaseLayer => (sonic_remote_account::RemoteNamespace::BaseLayer, Pubkey::default()),
+            RemoteNamespace::L1(node) => (sonic_remote_account::RemoteNamespace::L1, node),
+            RemoteNamespace::Grid(node) => (sonic_remote_account::RemoteNamespace::Grid, node),
+        };
+        RemoteAccountMeta {
+            remote,
+            namespace,
+            source,
+            source_slot: self.source_slot.unwrap_or_default(),
+        }
+    }
+}
+
+impl RemoteNamespace {
+    pub fn kind(&self) -> NamespaceKind {
+        match self {
+            Self::BaseLayer => NamespaceKind::BaseLayer,
+            Self::L1(_) => NamespaceKind::L1,
+            Self::Grid(_) => NamespaceKind::Grid,
+        }
+    }
+}
+
+impl fmt::Display for RemoteNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BaseLayer => write!(f, "the base layer"),
+            Self::L1(node) => write!(f, "L1 node {node}"),
+            Self::Grid(node) => write!(f, "grid node {node}"),
+        }
+    }
+}
+
+/// Sonic: how a remote account changed in the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteAccountEventKind {
+    /// The account was loaded into the cache.
+    Load,
+    /// The account replaced an older copy in the cache.
+    Refresh,
+    /// The account was removed from the cache.
+    Evict,
+}
+
+/// Sonic: a change of a remote account in the cache.
+#[derive(Debug)]
+pub struct RemoteAccountEvent<'a> {
+    pub pubkey: &'a Pubkey,
+    /// The account as cached, or as it was before its eviction.
+    pub account: &'a AccountSharedData,
+    pub origin: RemoteOrigin,
+    pub kind: RemoteAccountEventKind,
+}
+
+/// Sonic: notified of the loads, refreshes and evictions of the remote accounts.
+pub trait RemoteAccountNotifier: Send + Sync {
+    fn notify_remote_account(&self, event: &RemoteAccountEvent);
+}
 
 
-#[derive(Debug, Default)]
-struct HypergridNode {
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HypergridNode {
     pub pubkey: Pubkey,
     pub name: String,
     pub rpc: String,
     pub role: i32, // 0: unknown, 1: HSSN, 2: Sonic Grid, 3: Grid, 4: Solana L1
 }
 
-const NODE_TYPE_HSSN: i32 = 1;
-const NODE_TYPE_SONIC: i32 = 2;
-const NODE_TYPE_GRID: i32 = 3;
-const NODE_TYPE_L1: i32 = 4;
+pub const NODE_TYPE_HSSN: i32 = 1;
+pub const NODE_TYPE_SONIC: i32 = 2;
+pub const NODE_TYPE_GRID: i32 = 3;
+pub const NODE_TYPE_L1: i32 = 4;
+
+impl HypergridNode {
+    pub fn role_name(&self) -> &'static str {
+        match self.role {
+            NODE_TYPE_HSSN => "HSSN",
+            NODE_TYPE_SONIC => "Sonic Grid",
+            NODE_TYPE_GRID => "Grid",
+            NODE_TYPE_L1 => "Solana L1",
+            _ => "Unknown",
+        }
+    }
+}
 
 type HypergridNodes = DashMap<Pubkey, HypergridNode>;
 
+/// Sonic: the version of a remote program, as last migrated from its source.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteProgramVersion {
+    /// Slot the programdata was deployed at on the source, 0 if the program is not upgradeable.
+    pub programdata_slot: Slot,
+    /// Hash of the executable data.
+    pub data_hash: Hash,
+    /// Slot the version is deployed at on this chain: 0 for the first migration, otherwise
+    /// the slot of the refresh that pulled it.
+    pub deployment_slot: Slot,
+}
+
 pub struct RemoteAccountLoader {
     ///RPC client used to send requests to the remote.
     // rpc_client: RpcClient,
     cosmos_client: cosmos::HttpClient,
+    /// Clients of the RPC nodes accounts are loaded from.
+    rpc_pool: RpcClientPool,
+    hssn_tx: cosmos::HssnTxSubmitter,
     /// Cache of accounts loaded from the remote.
-    account_cache: AccountCacheKeyMap,
+    account_cache: RemoteAccountCache,
+    /// Origins of the accounts migrated so far, evicted from the cache or not. They are only
+    /// removed on deactivation.
+    known_accounts: DashMap<Pubkey, RemoteOrigin>,
     hypergrid_nodes: HypergridNodes,
+    /// Origins of the accounts cloned into genesis. They are part of the local state, so they
+    /// are not in the cache.
+    genesis_origins: DashMap<Pubkey, RemoteOrigin>,
     /// Enable or disable the remote loader.
     enable: bool,
     config: Config,
+    /// If set, accounts are migrated from this source instead of the base layer and HSSN.
+    remote_source: RwLock<Option<Arc<dyn RemoteAccountSource>>>,
+    /// If set, notified of the changes of the cache.
+    notifier: RwLock<Option<Arc<dyn RemoteAccountNotifier>>>,
+    /// If set, accounts are only cached once it accepts them.
+    quorum: RwLock<Option<Arc<QuorumVerifier>>>,
 }
 
 impl fmt::Debug for RemoteAccountLoader {
@@ -50,13 +174,7 @@ impl fmt::Debug for RemoteAccountLoader {
 
 impl Default for RemoteAccountLoader {
     fn default() -> Self {
-        let config_path: Option<&'static str> = option_env!("SONIC_CONFIG_FILE");
-        let default_config_path = {
-            let mut default_config_path = dirs_next::home_dir().expect("home directory");
-            default_config_path.extend([".config", "hypergrid.yml"]);
-            default_config_path.to_str().unwrap().to_string()
-        };
-        Self::new(config_path.unwrap_or(default_config_path.as_str()))
+        Self::new(&Config::default_path())
     }
 }
 
@@ -100,18 +218,117 @@ impl RemoteAccountLoader {
                 error!("setting: {:?}", e);
             },
         };
+        Self::new_with_config(config)
+    }
 
+    /// Create a new remote loader from a loaded configuration.
+    pub fn new_with_config(config: Config) -> Self {
         Self {
             // rpc_client: RpcClient::new_with_timeout_and_commitment(&config.baselayer_rpc_url, 
             // Duration::from_secs(30), CommitmentConfig::confirmed()),
             cosmos_client: cosmos::HttpClient::new(Duration::from_secs(30)),
-            account_cache: AccountCacheKeyMap::default(),
+            rpc_pool: RpcClientPool::new(config.rpc_pool.clone()),
+            hssn_tx: cosmos::HssnTxSubmitter::new(config.hssn_tx_url.as_deref()),
+            account_cache: RemoteAccountCache::new(config.cache.clone()),
+            known_accounts: DashMap::default(),
             hypergrid_nodes: HypergridNodes::default(),
+            genesis_origins: DashMap::default(),
             enable: true,
+            remote_source: RwLock::default(),
+            notifier: RwLock::default(),
+            quorum: RwLock::new(config.quorum.as_ref().map(|quorum| Arc::new(QuorumVerifier::new_from_config(quorum)))),
             config,
         }
     }
 
+    /// The configuration the loader was created with.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Migrate accounts from `remote_source` instead of the base layer and HSSN.
+    pub fn set_remote_source(&self, remote_source: Arc<dyn RemoteAccountSource>) {
+        *self.remote_source.write().unwrap() = Some(remote_source);
+    }
+
+    /// Notify `notifier` of the loads, refreshes and evictions of the remote accounts.
+    pub fn set_notifier(&self, notifier: Arc<dyn RemoteAccountNotifier>) {
+        *self.notifier.write().unwrap() = Some(notifier);
+    }
+
+    /// Only cache the accounts `quorum` accepts.
+    pub fn set_quorum_verifier(&self, quorum: Arc<QuorumVerifier>) {
+        *self.quorum.write().unwrap() = Some(quorum);
+    }
+
+    fn notify(&self, pubkey: &Pubkey, account: &AccountSharedData, origin: RemoteOrigin, kind: RemoteAccountEventKind) {
+        if let Some(notifier) = self.notifier.read().unwrap().as_ref() {
+            notifier.notify_remote_account(&RemoteAccountEvent {
+                pubkey,
+                account,
+                origin,
+                kind,
+            });
+        }
+    }
+
+    /// Insert the account loaded from `origin` into the cache, unless the quorum rejects it.
+    fn cache_account(&self, pubkey: &Pubkey, account: AccountSharedData, origin: RemoteOrigin) -> Result<AccountSharedData, RemoteLoadError> {
+        let quorum = self.quorum.read().unwrap().clone();
+        if let Some(quorum) = quorum {
+            if !quorum.verify(pubkey, &account, origin.source_slot) {
+                return Err(RemoteLoadError::QuorumRejected { pubkey: *pubkey });
+            }
+        }
+        let (replaced, evicted) = self.account_cache.insert(*pubkey, account.clone(), origin);
+        self.known_accounts.insert(*pubkey, origin);
+        let kind = match replaced {
+            Some(_) => RemoteAccountEventKind::Refresh,
+            None => RemoteAccountEventKind::Load,
+        };
+        self.notify(pubkey, &account, origin, kind);
+        self.notify_evicted(evicted);
+        Ok(account)
+    }
+
+    /// Forget the accounts evicted to keep the cache within its limits, and notify them. They
+    /// have to be migrated again before their next use.
+    fn notify_evicted(&self, evicted: Vec<EvictedAccount>) {
+        if evicted.is_empty() {
+            return;
+        }
+        for account in &evicted {
+            // Unless it was migrated again in the meantime
+            self.known_accounts.remove_if(&account.pubkey, |pubkey, _| !self.account_cache.contains_key(pubkey));
+            self.notify(&account.pubkey, &account.account, account.origin, RemoteAccountEventKind::Evict);
+        }
+        datapoint_info!(
+            "sonic-remote-cache",
+            ("accounts", self.account_cache.len(), i64),
+            ("bytes", self.account_cache.bytes(), i64),
+            ("evicted", evicted.len(), i64),
+        );
+    }
+
+    /// Remove the account from the cache, and forget it was migrated.
+    fn evict_account(&self, pubkey: &Pubkey) {
+        self.known_accounts.remove(pubkey);
+        if let Some(evicted) = self.account_cache.remove(pubkey) {
+            self.notify(pubkey, &evicted.account, evicted.origin, RemoteAccountEventKind::Evict);
+        }
+    }
+
+    /// Keep the cached account until `slot` is rooted.
+    pub fn pin_account(&self, pubkey: &Pubkey, slot: Slot) {
+        self.account_cache.pin(pubkey, slot);
+    }
+
+    /// Release the accounts pinned by the slots up to `root`.
+    pub fn unpin_accounts(&self, root: Slot) {
+        let evicted = self.account_cache.unpin(root);
+        self.notify_evicted(evicted);
+    }
+
     /// Check if the account should be ignored.
     fn ignored_account(pubkey: &Pubkey) -> bool {
         let pk = pubkey.to_string();
@@ -132,72 +349,159 @@ impl RemoteAccountLoader {
         }
         // println!("RemoteAccountLoader.get_account: {:?}, {}", thread::current().id(), pubkey.to_string());
         match self.account_cache.get(pubkey) {
-            Some(account) =>    {
+            Some((account, _, _)) =>    {
                 // println!("RemoteAccountLoader.get_account: {} match.", pubkey.to_string());
-                return Some(account.0.clone());
+                return Some(account);
             },
-            None => None, // self.load_account(pubkey),
+            //Sonic: accounts evicted for lack of room are forgotten, they are only fetched
+            //again by a new migration, a lookup must not block on the network
+            None => None,
         }
     }
 
-    /// Check if the account is in the cache.
+    /// Get where the migrated account, or the account cloned into genesis, was loaded from.
+    pub fn get_remote_origin(&self, pubkey: &Pubkey) -> Option<RemoteOrigin> {
+        self.known_accounts
+            .get(pubkey)
+            .or_else(|| self.genesis_origins.get(pubkey))
+            .map(|origin| *origin)
+    }
+
+    /// Record where an account cloned into genesis was loaded from.
+    pub fn set_genesis_origin(&self, pubkey: Pubkey, origin: RemoteOrigin) {
+        self.genesis_origins.insert(pubkey, origin);
+    }
+
+    /// Check if the account was migrated, and neither deactivated nor evicted since.
     pub fn has_account(&self, pubkey: &Pubkey) -> bool {
         if !self.enable || Self::ignored_account(pubkey) {
             return false;
         }
         // println!("RemoteAccountLoader.has_account: {:?}, {}", thread::current().id(), pubkey.to_string());
-        match self.account_cache.contains_key(pubkey) {
+        match self.known_accounts.contains_key(pubkey) {
             true => true,
             false => false, //self.load_account(pubkey).is_some(),
         }
     }
 
-    /// Load the account from the RPC.
-    pub fn load_account(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool) -> Option<AccountSharedData> {
+    /// The accounts migrated so far and the accounts cloned into genesis, with their origins.
+    /// Migrated accounts shadow the genesis copies.
+    pub fn remote_accounts(&self) -> Vec<(Pubkey, RemoteOrigin)> {
+        let mut accounts: Vec<_> = self
+            .known_accounts
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+        accounts.extend(
+            self.genesis_origins
+                .iter()
+                .filter(|entry| !self.known_accounts.contains_key(entry.key()))
+                .map(|entry| (*entry.key(), *entry.value())),
+        );
+        accounts.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+        accounts
+    }
+
+    /// The namespace of the accounts loaded from `source`. Nodes of unknown role are taken for
+    /// grids.
+    fn namespace(&self, source: Option<Pubkey>) -> RemoteNamespace {
+        let Some(source) = source else {
+            return RemoteNamespace::BaseLayer;
+        };
+        match self.hypergrid_nodes.get(&source).map(|node| node.role) {
+            Some(NODE_TYPE_L1) => RemoteNamespace::L1(source),
+            _ => RemoteNamespace::Grid(source),
+        }
+    }
+
+    /// Check that loading the account from `namespace` does not shadow a copy from a namespace
+    /// taking precedence.
+    fn check_namespace(&self, pubkey: &Pubkey, namespace: RemoteNamespace) -> Result<(), RemoteLoadError> {
+        let Some(loaded) = self.get_remote_origin(pubkey).map(|origin| origin.namespace) else {
+            return Ok(());
+        };
+        if loaded == namespace {
+            return Ok(());
+        }
+        if self.config.namespace_rank(namespace.kind()) >= self.config.namespace_rank(loaded.kind()) {
+            datapoint_warn!(
+                "sonic-remote-namespace-conflict",
+                ("pubkey", pubkey.to_string(), String),
+                ("loaded", loaded.to_string(), String),
+                ("requested", namespace.to_string(), String),
+            );
+            return Err(RemoteLoadError::NamespaceConflict { pubkey: *pubkey, loaded, requested: namespace });
+        }
+        warn!("check_namespace: {} loaded from {} is replaced by the copy from {}", pubkey, loaded, namespace);
+        Ok(())
+    }
+
+    /// Load the account from the remote, giving up on requests that cannot start within the
+    /// RPC timeout.
+    ///
+    /// Returns `Ok(None)` if the account is never loaded from the remote, as for the builtin
+    /// accounts or when the loader is disabled.
+    pub fn load_account(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool) -> Result<Option<AccountSharedData>, RemoteLoadError> {
+        let deadline = Instant::now() + Duration::from_millis(self.config.rpc_pool.timeout_ms);
+        self.load_account_with_deadline(pubkey, source, refresh, deadline)
+    }
+
+    /// Load the account from the remote, giving up on requests that cannot start before
+    /// `deadline`.
+    pub fn load_account_with_deadline(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool, deadline: Instant) -> Result<Option<AccountSharedData>, RemoteLoadError> {
         if !self.enable || Self::ignored_account(pubkey) {
-            return None;
+            return Ok(None);
         }
 
         info!("Thread {:?}: load_account: {} from {}, refresh: {}",  thread::current().id(), pubkey.to_string(), source.unwrap_or_default().to_string(), refresh);
 
-        if let Some(account_cache) = self.account_cache.get(pubkey) {
-            let (account1, time) = account_cache.clone();
-            if time.elapsed().as_secs() < 3 {
-                info!("******* cache: {}\n", pubkey.to_string());
-                return Some(account1);
+        let remote_source = self.remote_source.read().unwrap().clone();
+        if let (None, Some(source)) = (&remote_source, source) {
+            if !self.hypergrid_nodes.contains_key(&source) {
+                self.load_hypergrid_nodes();
             }
         }
+        //Sonic: an account must not silently shadow its copy from another chain
+        let namespace = self.namespace(source);
+        self.check_namespace(pubkey, namespace)?;
 
-        let account: Option<AccountSharedData>;
-        match source {
-            Some(source) => {
-                account = self.load_account_via_hssn(pubkey, Some(source), refresh);
-            },
-            None => {
-                account = self.load_account_via_rpc(pubkey, None, refresh);
-            },
+        //Sonic: a refresh always goes to the source
+        if let Some((account1, time, origin)) = self.account_cache.get(pubkey).filter(|_| !refresh) {
+            if origin.namespace == namespace && time.elapsed().as_secs() < 3 {
+                info!("******* cache: {}\n", pubkey.to_string());
+                return Ok(Some(account1));
+            }
         }
 
-        match account {
-            Some(account) => {
-                //Sonic: check if programdata account exists
-                if let Some(programdata_address) = RemoteAccountLoader::has_programdata_account(account.clone()) {
-                    //Sonic: load programdata account from remote
-                    self.load_account(&programdata_address, source, refresh);
-                }
-                Some(account)
-            },
-            None => None,
+        let account = match (remote_source, source) {
+            (Some(remote_source), _) => self.load_account_via_source(remote_source.as_ref(), pubkey, source),
+            (None, Some(source)) => self.load_account_via_hssn(pubkey, Some(source), refresh, deadline),
+            (None, None) => self.load_account_via_rpc(pubkey, None, refresh, deadline),
+        };
+        let account = account.map_err(|e| {
+            error!("load_account: {}", e);
+            e
+        })?;
+
+        //Sonic: check if programdata account exists
+        if let Some(programdata_address) = RemoteAccountLoader::has_programdata_account(account.clone()) {
+            //Sonic: load programdata account from remote
+            self.load_account_with_deadline(&programdata_address, source, refresh, deadline)?;
         }
+        Ok(Some(account))
     }
 
-    /// Load the account from the RPC.
-    fn load_account_via_rpc(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool) -> Option<AccountSharedData> {
-        if Self::ignored_account(pubkey) {
-            // print!("******* skip: {}\n", pubkey.to_string());
-            return None;
-        }
+    /// Load the account from the plugged remote source.
+    fn load_account_via_source(&self, remote_source: &dyn RemoteAccountSource, pubkey: &Pubkey, source: Option<Pubkey>) -> Result<AccountSharedData, RemoteLoadError> {
+        let mut account = remote_source
+            .get_account(pubkey, source.as_ref())
+            .ok_or(RemoteLoadError::NotFound { pubkey: *pubkey })?;
+        account.remote = true;
+        self.cache_account(pubkey, account, RemoteOrigin { source, source_slot: None, namespace: self.namespace(source) })
+    }
 
+    /// Load the account from the RPC.
+    fn load_account_via_rpc(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool, deadline: Instant) -> Result<AccountSharedData, RemoteLoadError> {
         let mut rpc_url = self.config.baselayer_rpc_url.clone();
         if let Some(source) = source {
             if self.hypergrid_nodes.len() < 1 || self.hypergrid_nodes.get(&source).is_none() {
@@ -208,20 +512,19 @@ impl RemoteAccountLoader {
                 if node.value().role == NODE_TYPE_SONIC || node.value().role == NODE_TYPE_GRID || node.value().role == NODE_TYPE_L1 {
                     rpc_url = node.value().rpc.clone();
                 } else {
-                    info!("load_account_via_rpc: invalid source role: {}", node.value().role);
-                    return None;
+                    return Err(RemoteLoadError::InvalidSource { pubkey: *pubkey, node: source, role: node.value().role });
                 }
             }
         }
 
         // println!("Thread {:?}: load_account_via_rpc: {} from {}",  thread::current().id(), pubkey.to_string(), rpc_url.clone());
 
-        let rpc_client = RpcClient::new_with_timeout_and_commitment(rpc_url, Duration::from_secs(30), CommitmentConfig::confirmed());
-
         let mut time = Measure::start("load_account_from_remote");
-        let result = rpc_client.get_account(pubkey);
+        let result = self.rpc_pool.call(&rpc_url, deadline, |rpc_client| {
+            rpc_client.get_account_with_commitment(pubkey, rpc_client.commitment())
+        });
         match result {
-            Ok(account) => {
+            Ok(Response { context, value: Some(account) }) => {
                 // println!("load_account_via_rpc1: account: {:?}", account);
                 let mut account = AccountSharedData::create(
                     account.lamports,
@@ -233,129 +536,144 @@ impl RemoteAccountLoader {
                 account.remote = true;
         
                 // println!("load_account_via_rpc2: account: {:?}", account);
-                self.account_cache.insert(pubkey.clone(), (account.clone(), Instant::now()));
+                let account = self.cache_account(pubkey, account, RemoteOrigin { source, source_slot: Some(context.slot), namespace: self.namespace(source) });
                 time.stop();
                 // println!("load_account_via_rpc: account: {:?}, {:?}", account, time.as_us());
-                Some(account)
+                account
             },
-            Err(e) => {
-                error!("load_account_via_rpc: failed to load account: {:?}\n", e);
-                None
-            }
+            Ok(Response { value: None, .. }) => Err(RemoteLoadError::NotFound { pubkey: *pubkey }),
+            Err(reason) => Err(RemoteLoadError::SourceUnreachable { pubkey: *pubkey, url: rpc_url, reason }),
         }
     }
 
-    fn deserialize_from_json(account_data: serde_json::Value) -> Option<AccountSharedData> {
+    /// Deserialize an account recorded in HSSN, `Ok(None)` if HSSN has no record of it.
+    fn deserialize_from_json(pubkey: &Pubkey, account_data: serde_json::Value) -> Result<Option<AccountSharedData>, RemoteLoadError> {
         let result = &account_data["solanaAccount"];
         if result.is_null() {
-            return None;
+            return Ok(None);
         }
 
         let value = &result["value"];
         if value.is_null() {
-            return None;
+            return Ok(None);
         }
         let value_str = value.as_str().unwrap_or("");
-        let value: serde_json::Result<serde_json::Value> = serde_json::from_str(value_str);
-        if let Ok(value) = value {
-            // println!("data: {:?}", account_data.to_string());
-            // let slot = result["slot"].as_u64().unwrap_or(0);
-            let data = value["data"][0].as_str().unwrap_or("");
-            let encoding = value["data"][1].as_str().unwrap_or("");
-            let lamports = value["lamports"].as_u64().unwrap_or(0);
-            let owner = value["owner"].as_str().unwrap_or("");
-            let rent_epoch = value["rentEpoch"].as_u64().unwrap_or(0);
-            // let space = value["space"].as_u64().unwrap();
-            let executable = value["executable"].as_bool().unwrap_or(false);
-            // if owner.eq("Feature111111111111111111111111111111111111") {
-            //     return None;
-            // }
-
-            let data = match encoding {
-                "base58" => bs58::decode(data).into_vec().unwrap_or_default(),
-                "base64" => base64::engine::general_purpose::STANDARD.decode(data).unwrap_or_default(),
-                "base64+zstd" => {
-                    let decoded = base64::engine::general_purpose::STANDARD.decode(data).unwrap_or_default();
-                    let decompressed = zstd::decode_all(decoded.as_slice()).unwrap_or_default();
-                    decompressed
-                },
-                _ => Vec::new(), // Add wildcard pattern to cover all other possible values
-            };
-
-            // println!("data: {}, {}", space, data.len());
-
-            let mut account = AccountSharedData::create(
-                    lamports,
-                    data,
-                    Pubkey::from_str(owner).unwrap(),
-                    executable,
-                    rent_epoch
-            );
-            account.remote = true;
+        let value: serde_json::Value = serde_json::from_str(value_str).map_err(|e| RemoteLoadError::InvalidData {
+            pubkey: *pubkey,
+            reason: format!("invalid HSSN record: {e}"),
+        })?;
+        // println!("data: {:?}", account_data.to_string());
+        // let slot = result["slot"].as_u64().unwrap_or(0);
+        Self::deserialize_ui_account(pubkey, &value).map(Some)
+    }
 
-            info!("deserialize_from_json account: {:?}", account);
-            Some(account)
-        } else {
-            None
+    /// Deserialize an account in the JSON representation returned by RPC `getAccountInfo`.
+    pub(crate) fn deserialize_ui_account(pubkey: &Pubkey, value: &serde_json::Value) -> Result<AccountSharedData, RemoteLoadError> {
+        let invalid_data = |reason: String| RemoteLoadError::InvalidData { pubkey: *pubkey, reason };
+        if value.is_null() {
+            return Err(RemoteLoadError::NotFound { pubkey: *pubkey });
         }
+        let data = value["data"][0].as_str().ok_or_else(|| invalid_data("missing data".to_string()))?;
+        let encoding = value["data"][1].as_str().ok_or_else(|| invalid_data("missing data encoding".to_string()))?;
+        let lamports = value["lamports"].as_u64().ok_or_else(|| invalid_data("missing lamports".to_string()))?;
+        let owner = value["owner"].as_str().ok_or_else(|| invalid_data("missing owner".to_string()))?;
+        let rent_epoch = value["rentEpoch"].as_u64().unwrap_or(0);
+        // let space = value["space"].as_u64().unwrap();
+        let executable = value["executable"].as_bool().unwrap_or(false);
+        // if owner.eq("Feature111111111111111111111111111111111111") {
+        //     return None;
+        // }
+
+        let data = match encoding {
+            "base58" => bs58::decode(data).into_vec().map_err(|e| invalid_data(format!("invalid base58 data: {e}")))?,
+            "base64" => base64::engine::general_purpose::STANDARD.decode(data).map_err(|e| invalid_data(format!("invalid base64 data: {e}")))?,
+            "base64+zstd" => {
+                let decoded = base64::engine::general_purpose::STANDARD.decode(data).map_err(|e| invalid_data(format!("invalid base64 data: {e}")))?;
+                zstd::decode_all(decoded.as_slice()).map_err(|e| invalid_data(format!("invalid zstd data: {e}")))?
+            },
+            _ => return Err(invalid_data(format!("unsupported data encoding {encoding:?}"))),
+        };
+
+        // println!("data: {}, {}", space, data.len());
+
+        let mut account = AccountSharedData::create(
+                lamports,
+                data,
+                Pubkey::from_str(owner).map_err(|e| invalid_data(format!("invalid owner {owner}: {e}")))?,
+                executable,
+                rent_epoch
+        );
+        account.remote = true;
+
+        info!("deserialize_from_json account: {:?}", account);
+        Ok(account)
     }
 
     fn load_hypergrid_nodes(&self) {
-        let url = format!("{}/hypergrid-ssn/hypergridssn/hypergrid_node", self.config.hssn_rpc_url);
-        info!("load_hypergrid_nodes: {}\n", url);
-        // let client = cosmos::HttpClient::new(Duration::from_secs(30));
-        let res = self.cosmos_client.call(url);
-        if let Ok(body) = res {
-            // println!("respone: {}", body);
-            //convert the response body to json
-            let value: serde_json::Result<serde_json::Value> = serde_json::from_str(&body);
-            if let Ok(value) = value {
-                // let value: serde_json::Value = value.unwrap();
-                
-                let nodes = value["hypergridNode"].as_array().unwrap();
-                // println!("load_hypergrid_nodes: success: {:?}\n", nodes);
+        match self.get_hypergrid_nodes() {
+            Ok(nodes) => {
                 for node in nodes {
-                    // println!("load_hypergrid_nodes: node: {:?}\n", node);
-                    let node_id = node["pubkey"].as_str().unwrap();
-                    let node_name = node["name"].as_str().unwrap();
-                    let node_url = node["rpc"].as_str().unwrap();
-                    let node_role = node["role"].as_i64().unwrap();
-                    // println!("node: {}, {}", node_id, node_url);
-                    self.hypergrid_nodes.insert(Pubkey::from_str(node_id).unwrap(), 
-                    HypergridNode {
-                        pubkey: Pubkey::from_str(node_id).unwrap(),
-                        name: node_name.to_string(),
-                        rpc: node_url.to_string(),
-                        role: node_role as i32,
-                    });
+                    self.hypergrid_nodes.insert(node.pubkey, node);
                 }
-            }
-            return;
+            },
+            Err(e) => {
+                warn!("get_hypergrid_nodes: not found: {:?}, {}\n", self.config.hssn_rpc_url, e);
+            },
         }
-        warn!("get_hypergrid_nodes: not found: {:?}\n", self.config.hssn_rpc_url);
     }
 
-    fn load_account_via_hssn(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool) -> Option<AccountSharedData> {
-        if Self::ignored_account(pubkey) {
-            // print!("******* skip: {}\n", pubkey.to_string());
-            return None;
-        }
+    /// Get the hypergrid nodes registered in HSSN.
+    pub fn get_hypergrid_nodes(&self) -> Result<Vec<HypergridNode>, String> {
+        let url = format!("{}/hypergrid-ssn/hypergridssn/hypergrid_node", self.config.hssn_rpc_url);
+        info!("load_hypergrid_nodes: {}\n", url);
+        let body = self.cosmos_client.call(url)?;
+        let value: serde_json::Value = serde_json::from_str(&body).map_err(|e| format!("{:?}", e))?;
+        let nodes = value["hypergridNode"].as_array().ok_or("missing hypergridNode")?;
+        nodes
+            .iter()
+            .map(|node| {
+                let node_id = node["pubkey"].as_str().ok_or("missing node pubkey")?;
+                Ok(HypergridNode {
+                    pubkey: Pubkey::from_str(node_id).map_err(|e| format!("{}: {:?}", node_id, e))?,
+                    name: node["name"].as_str().unwrap_or_default().to_string(),
+                    rpc: node["rpc"].as_str().unwrap_or_default().to_string(),
+                    role: node["role"].as_i64().unwrap_or_default() as i32,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the account recorded in HSSN, without loading it into the cache.
+    pub fn get_hssn_account(&self, pubkey: &Pubkey) -> Result<Option<AccountSharedData>, String> {
+        let url = format!("{}/hypergrid-ssn/hypergridssn/solana_account/{}/{}",self.config.hssn_rpc_url, pubkey.to_string(), 0);
+        let body = match self.cosmos_client.call(url) {
+            Ok(body) => body,
+            // HSSN answers 404 for accounts it has no record of
+            Err(e) if e.contains("404") => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let value: serde_json::Value = serde_json::from_str(&body).map_err(|e| format!("{:?}", e))?;
+        RemoteAccountLoader::deserialize_from_json(pubkey, value).map_err(|e| e.to_string())
+    }
+
+    fn load_account_via_hssn(&self, pubkey: &Pubkey, source: Option<Pubkey>, refresh: bool, deadline: Instant) -> Result<AccountSharedData, RemoteLoadError> {
         info!("Thread {:?}: load_account_via_hssn: {:?}",  thread::current().id(), pubkey.to_string());
 
         let url = format!("{}/hypergrid-ssn/hypergridssn/solana_account/{}/{}",self.config.hssn_rpc_url, pubkey.to_string(), 0);
         info!("load_account_from_hssn: {}\n", url);
-        let res = self.cosmos_client.call(url);
+        let res = self.cosmos_client.call(url.clone());
         let mut account: Option<AccountSharedData> = None;
         match res {
             Ok(body) => {
                 info!("respone: {:?}", body);
                 //convert the response body to json
-                let value: serde_json::Result<serde_json::Value> = serde_json::from_str(&body);
-                if let Ok(value) = value {
-                    // let value: serde_json::Value = value.unwrap();
-                    info!("load_account_via_hssn: success: {:?}\n", value);
-                    account = RemoteAccountLoader::deserialize_from_json(value);
-                } 
+                let value: serde_json::Value = serde_json::from_str(&body).map_err(|e| RemoteLoadError::SourceUnreachable {
+                    pubkey: *pubkey,
+                    url,
+                    reason: format!("invalid HSSN response: {e}"),
+                })?;
+                info!("load_account_via_hssn: success: {:?}\n", value);
+                account = RemoteAccountLoader::deserialize_from_json(pubkey, value)?;
             },
             Err(e) => {
                 warn!("load_account_from_hssn: not found: {:?}, {:?}\n", pubkey, e);
@@ -366,21 +684,20 @@ impl RemoteAccountLoader {
             Some(account) => {
                 if refresh {
                     //load the account from the source
-                    cosmos::run_load_solana_account(pubkey.to_string().as_str(), "0", "", true);
-                    self.load_account_via_rpc(pubkey, source, refresh)
+                    self.hssn_tx.run_load_solana_account(pubkey.to_string().as_str(), "0", "", true);
+                    self.load_account_via_rpc(pubkey, source, refresh, deadline)
                 } else {
-                    self.account_cache.insert(pubkey.clone(), (account.clone(), Instant::now()));
-                    Some(account)
+                    self.cache_account(pubkey, account, RemoteOrigin { source, source_slot: None, namespace: self.namespace(source) })
                 }
             },
             None => {
                 info!("load_account_from_hssn: not found: {:?}\n", pubkey);
                 if let Some(source) = source {
                     //load the account from the source
-                    cosmos::run_load_solana_account(pubkey.to_string().as_str(), "0", source.to_string().as_str(), false);
-                    self.load_account_via_rpc(pubkey, Some(source), refresh)
+                    self.hssn_tx.run_load_solana_account(pubkey.to_string().as_str(), "0", source.to_string().as_str(), false);
+                    self.load_account_via_rpc(pubkey, Some(source), refresh, deadline)
                 } else {
-                    None
+                    Err(RemoteLoadError::NotFound { pubkey: *pubkey })
                 }
             }
         }
@@ -402,6 +719,26 @@ impl RemoteAccountLoader {
         return None;
     }
 
+    /// The source version of the remote program `program_id` in the cache: the slot its
+    /// programdata was deployed at on the source, and the hash of its executable data.
+    pub fn cached_program_version(&self, program_id: &Pubkey) -> Option<(Slot, Hash)> {
+        let program = self.get_account(program_id)?;
+        if !program.executable() {
+            return None;
+        }
+        match Self::has_programdata_account(program.clone()) {
+            Some(programdata_address) => {
+                let programdata = self.get_account(&programdata_address)?;
+                let UpgradeableLoaderState::ProgramData { slot, .. } = programdata.state().ok()? else {
+                    return None;
+                };
+                let data = programdata.data().get(UpgradeableLoaderState::size_of_programdata_metadata()..)?;
+                Some((slot, hash(data)))
+            },
+            None => Some((0, hash(program.data()))),
+        }
+    }
+
     /// Deactivate the account in the cache.
     pub fn deactivate_account(&self, pubkey: &Pubkey) {
         if !self.enable || Self::ignored_account(pubkey) {
@@ -410,12 +747,12 @@ impl RemoteAccountLoader {
         // println!("RemoteAccountLoader.deactivate_account: {}", pubkey.to_string());
         match self.get_account(pubkey) {
             Some(account) => {
-                self.account_cache.remove(pubkey);
+                self.evict_account(pubkey);
 
                 //remove the related programdata account
                 match Self::has_programdata_account(account) {
                     Some(programdata_address) => {
-                        self.account_cache.remove(&programdata_address);
+                        self.evict_account(&programdata_address);
                     },
                     None => { },
                 }
@@ -452,7 +789,7 @@ mod tests {
         let loader = RemoteAccountLoader::default();
         let pubkey = Pubkey::from_str("4WTUyXNcf6QCEj76b3aRDLPewkPGkXFZkkyf3A3vua1z").unwrap();
         let account = loader.load_account(&pubkey, None, false);
-        assert_eq!(account.is_none(), true);
+        assert_eq!(account.is_err(), true);
     }
 
     #[test]
@@ -478,7 +815,176 @@ mod tests {
         let loader = RemoteAccountLoader::default();
         let pubkey = Pubkey::from_str("4WTUyXNcf6QCEj76b3aRDLPewkPGkXFZkkyf3A3vua1z").unwrap();
         let account = loader.load_account(&pubkey, None, false);
-        assert_eq!(account.is_none(), true);
+        assert_eq!(account.is_err(), true);
+    }
+
+    /// A loader migrating `accounts`, given as their lamports and the node holding them.
+    fn loader(config: Config, accounts: &[(Pubkey, u64, Option<Pubkey>)]) -> RemoteAccountLoader {
+        let remote_source = crate::remote_source::StaticRemoteSource::new();
+        for (pubkey, lamports, source) in accounts {
+            remote_source.add_account(*pubkey, AccountSharedData::new(*lamports, 0, &Pubkey::default()), *source);
+        }
+        let loader = RemoteAccountLoader::new_with_config(config);
+        loader.set_remote_source(Arc::new(remote_source));
+        loader
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        events: RwLock<Vec<(Pubkey, RemoteOrigin, RemoteAccountEventKind)>>,
+    }
+
+    impl RemoteAccountNotifier for RecordingNotifier {
+        fn notify_remote_account(&self, event: &RemoteAccountEvent) {
+            assert!(event.account.remote);
+            self.events.write().unwrap().push((*event.pubkey, event.origin, event.kind));
+        }
+    }
+
+    #[test]
+    fn test_remote_account_notifications() {
+        let (pubkey, source) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+        let loader = loader(Config::default(), &[(pubkey, 42, Some(source))]);
+        let notifier = Arc::new(RecordingNotifier::default());
+        loader.set_notifier(notifier.clone());
+
+        loader.load_account(&pubkey, Some(source), false).unwrap();
+        // Refreshed once the cached copy is stale
+        loader.account_cache.age(&pubkey, Duration::from_secs(5));
+        loader.load_account(&pubkey, Some(source), true).unwrap();
+        loader.deactivate_account(&pubkey);
+
+        let origin = RemoteOrigin { source: Some(source), source_slot: None, namespace: RemoteNamespace::Grid(source) };
+        assert_eq!(
+            *notifier.events.read().unwrap(),
+            vec![
+                (pubkey, origin, RemoteAccountEventKind::Load),
+                (pubkey, origin, RemoteAccountEventKind::Refresh),
+                (pubkey, origin, RemoteAccountEventKind::Evict),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_origin_account_meta() {
+        let node = Keypair::new().pubkey();
+        assert_eq!(
+            RemoteOrigin::default().account_meta(true),
+            RemoteAccountMeta { remote: true, namespace: sonic_remote_account::RemoteNamespace::BaseLayer, source: Pubkey::default(), source_slot: 0 }
+        );
+        let origin = RemoteOrigin { source: Some(node), source_slot: Some(7), namespace: RemoteNamespace::L1(node) };
+        assert_eq!(
+            origin.account_meta(false),
+            RemoteAccountMeta { remote: false, namespace: sonic_remote_account::RemoteNamespace::L1, source: node, source_slot: 7 }
+        );
+    }
+
+    #[test]
+    fn test_remote_accounts() {
+        let (a, b, c) = (Keypair::new().pubkey(), Keypair::new().pubkey(), Keypair::new().pubkey());
+        let source = Keypair::new().pubkey();
+        let loader = loader(Config::default(), &[(a, 42, None), (b, 43, None)]);
+        loader.load_account(&a, None, false).unwrap();
+        loader.load_account(&b, None, false).unwrap();
+        let genesis_origin = RemoteOrigin { source: Some(source), source_slot: Some(7), namespace: RemoteNamespace::Grid(source) };
+        loader.set_genesis_origin(b, genesis_origin);
+        loader.set_genesis_origin(c, genesis_origin);
+
+        // Migrated accounts shadow their genesis copies
+        let mut expected = vec![(a, RemoteOrigin::default()), (b, RemoteOrigin::default()), (c, genesis_origin)];
+        expected.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+        assert_eq!(loader.remote_accounts(), expected);
+    }
+
+    #[test]
+    fn test_remote_account_cache_limits() {
+        let (a, b) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+        let mut config = Config::default();
+        config.cache.max_accounts = 1;
+        let loader = loader(config, &[(a, 42, None), (b, 43, None)]);
+        loader.load_account(&a, None, false).unwrap();
+        loader.load_account(&b, None, false).unwrap();
+
+        // Evicted accounts are forgotten until a new migration fetches them again
+        assert!(!loader.account_cache.contains_key(&a));
+        assert!(!loader.has_account(&a));
+        assert_eq!(loader.get_remote_origin(&a), None);
+        assert_eq!(loader.get_account(&a), None);
+        loader.load_account(&a, None, false).unwrap();
+        assert!(loader.has_account(&a));
+        assert_eq!(loader.get_account(&a).unwrap().lamports(), 42);
+        assert!(!loader.has_account(&b));
+
+        // Pinned accounts are kept until their slot is rooted
+        loader.pin_account(&a, 10);
+        loader.load_account(&b, None, false).unwrap();
+        assert!(loader.account_cache.contains_key(&a));
+        loader.unpin_accounts(10);
+        assert!(!loader.has_account(&a));
+        assert_eq!(loader.account_cache.len(), 1);
+    }
+
+    #[test]
+    fn test_remote_account_namespaces() {
+        let (a, b) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+        let (l1, grid) = (Keypair::new().pubkey(), Keypair::new().pubkey());
+        let accounts: Vec<_> = [(1, None), (2, Some(l1)), (3, Some(grid))]
+            .into_iter()
+            .flat_map(|(lamports, source)| [(a, lamports, source), (b, lamports, source)])
+            .collect();
+        let new_loader = |config: Config| {
+            let loader = loader(config, &accounts);
+            for (node, role) in [(l1, NODE_TYPE_L1), (grid, NODE_TYPE_GRID)] {
+                loader.hypergrid_nodes.insert(node, HypergridNode { pubkey: node, role, ..HypergridNode::default() });
+            }
+            loader
+        };
+
+        // A grid copy never shadows the base layer one
+        let loader = new_loader(Config::default());
+        loader.load_account(&a, None, false).unwrap();
+        assert_eq!(
+            loader.load_account(&a, Some(grid), false),
+            Err(RemoteLoadError::NamespaceConflict { pubkey: a, loaded: RemoteNamespace::BaseLayer, requested: RemoteNamespace::Grid(grid) })
+        );
+
+        // But an L1 copy replaces it
+        loader.load_account(&b, Some(grid), false).unwrap();
+        assert_eq!(loader.load_account(&b, Some(l1), false).unwrap().unwrap().lamports(), 2);
+        assert!(loader.load_account(&b, Some(grid), false).is_err());
+
+        // Deactivated accounts can be loaded from any namespace
+        loader.deactivate_account(&a);
+        assert_eq!(loader.load_account(&a, Some(grid), false).unwrap().unwrap().lamports(), 3);
+
+        let mut config = Config::default();
+        config.namespace_precedence = vec![NamespaceKind::Grid];
+        let loader = new_loader(config);
+        loader.load_account(&a, None, false).unwrap();
+        assert_eq!(loader.load_account(&a, Some(grid), false).unwrap().unwrap().lamports(), 3);
+        assert!(loader.load_account(&a, Some(l1), false).is_err());
     }
 
+    #[test]
+    fn test_remote_account_quorum() {
+        let pubkey = Keypair::new().pubkey();
+
+        // The only other copy disagrees on the lamports
+        struct Disagreeing;
+        impl crate::quorum::QuorumSource for Disagreeing {
+            fn name(&self) -> &str {
+                "disagreeing"
+            }
+            fn get_account(&self, _pubkey: &Pubkey) -> Result<Option<(AccountSharedData, Option<Slot>)>, String> {
+                Ok(Some((AccountSharedData::new(43, 0, &Pubkey::default()), None)))
+            }
+        }
+        let quorum = Arc::new(QuorumVerifier::new(vec![Arc::new(Disagreeing)], 2, 0));
+
+        let loader = loader(Config::default(), &[(pubkey, 42, None)]);
+        loader.set_quorum_verifier(quorum.clone());
+        assert_eq!(loader.load_account(&pubkey, None, false), Err(RemoteLoadError::QuorumRejected { pubkey }));
+        assert!(!loader.has_account(&pubkey));
+        assert_eq!(quorum.stats().rejected.load(std::sync::atomic::Ordering::Relaxed), 1);
+    }
 }
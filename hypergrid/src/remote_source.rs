@@ -0,0 +1,211 @@
+//! Pluggable sources of remote accounts.
+//!
+//! By default the [`RemoteAccountLoader`](crate::remote_loader::RemoteAccountLoader) migrates
+//! accounts from the base layer RPC and HSSN. Tests and local validators can plug in one of
+//! the sources below instead, so that the remote path can run without a live network.
+
+use {
+    crate::remote_loader::RemoteAccountLoader,
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{account::AccountSharedData, commitment_config::CommitmentConfig, pubkey::Pubkey},
+    std::{
+        collections::HashMap,
+        fs,
+        path::PathBuf,
+        sync::{Arc, RwLock},
+        time::Duration,
+    },
+};
+
+/// A source remote accounts are migrated from.
+pub trait RemoteAccountSource: Send + Sync {
+    /// Fetch `pubkey` as held by the `source` hypergrid node, or by the base layer if `source`
+    /// is `None`.
+    fn get_account(&self, pubkey: &Pubkey, source: Option<&Pubkey>) -> Option<AccountSharedData>;
+}
+
+/// In-memory remote accounts, keyed by the node they are held by.
+///
+/// Accounts can be added or replaced at any time, which simulates updates on the source
+/// before a refresh.
+#[derive(Default)]
+pub struct StaticRemoteSource {
+    accounts: RwLock<HashMap<(Option<Pubkey>, Pubkey), AccountSharedData>>,
+    /// Consulted for the accounts not held here.
+    fallback: Option<Arc<dyn RemoteAccountSource>>,
+}
+
+impl StaticRemoteSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_fallback(fallback: Arc<dyn RemoteAccountSource>) -> Self {
+        Self {
+            accounts: RwLock::default(),
+            fallback: Some(fallback),
+        }
+    }
+
+    /// Hold `account` at `source`, or at the base layer if `source` is `None`.
+    pub fn add_account(&self, pubkey: Pubkey, account: AccountSharedData, source: Option<Pubkey>) {
+        self.accounts
+            .write()
+            .unwrap()
+            .insert((source, pubkey), account);
+    }
+
+    pub fn remove_account(&self, pubkey: &Pubkey, source: Option<Pubkey>) {
+        self.accounts.write().unwrap().remove(&(source, *pubkey));
+    }
+}
+
+impl RemoteAccountSource for StaticRemoteSource {
+    fn get_account(&self, pubkey: &Pubkey, source: Option<&Pubkey>) -> Option<AccountSharedData> {
+        let account = self
+            .accounts
+            .read()
+            .unwrap()
+            .get(&(source.copied(), *pubkey))
+            .cloned();
+        account.or_else(|| {
+            self.fallback
+                .as_ref()
+                .and_then(|fallback| fallback.get_account(pubkey, source))
+        })
+    }
+}
+
+/// Remote accounts stored as JSON files, in the format written by `solana account --output json`.
+///
+/// Base layer accounts are read from `<dir>/<pubkey>.json`, and accounts of a hypergrid node
+/// from `<dir>/<node pubkey>/<pubkey>.json`. Files are read on every lookup, so they can be
+/// rewritten to simulate updates on the source.
+pub struct DirectoryRemoteSource {
+    dir: PathBuf,
+}
+
+impl DirectoryRemoteSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl RemoteAccountSource for DirectoryRemoteSource {
+    fn get_account(&self, pubkey: &Pubkey, source: Option<&Pubkey>) -> Option<AccountSharedData> {
+        let mut path = self.dir.clone();
+        if let Some(source) = source {
+            path.push(source.to_string());
+        }
+        path.push(format!("{pubkey}.json"));
+        let file = fs::read_to_string(path).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&file).ok()?;
+        RemoteAccountLoader::deserialize_ui_account(pubkey, &value["account"]).ok()
+    }
+}
+
+/// Remote accounts served by a single RPC node, whatever node they are requested from.
+pub struct RpcRemoteSource {
+    rpc_client: RpcClient,
+}
+
+impl RpcRemoteSource {
+    pub fn new(url: String) -> Self {
+        Self {
+            rpc_client: RpcClient::new_with_timeout_and_commitment(
+                url,
+                Duration::from_secs(30),
+                CommitmentConfig::confirmed(),
+            ),
+        }
+    }
+}
+
+impl RemoteAccountSource for RpcRemoteSource {
+    fn get_account(&self, pubkey: &Pubkey, _source: Option<&Pubkey>) -> Option<AccountSharedData> {
+        self.rpc_client
+            .get_account_with_commitment(pubkey, self.rpc_client.commitment())
+            .ok()?
+            .value
+            .map(AccountSharedData::from)
+    }
+}
+
+/// Build the source named by a `--remote-source` argument: an RPC node if it is an http(s)
+/// URL, a fixture directory otherwise.
+pub fn remote_source_from_arg(arg: &str) -> Result<Arc<dyn RemoteAccountSource>, String> {
+    if arg.starts_with("http://") || arg.starts_with("https://") {
+        Ok(Arc::new(RpcRemoteSource::new(arg.to_string())))
+    } else {
+        let dir = PathBuf::from(arg);
+        if !dir.is_dir() {
+            return Err(format!("{arg} is neither an http(s) URL nor a directory"));
+        }
+        Ok(Arc::new(DirectoryRemoteSource::new(dir)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::ReadableAccount};
+
+    #[test]
+    fn test_static_remote_source() {
+        let pubkey = Pubkey::new_unique();
+        let node_id = Pubkey::new_unique();
+        let base_account = AccountSharedData::new(1, 0, &Pubkey::default());
+        let node_account = AccountSharedData::new(2, 0, &Pubkey::default());
+
+        let fallback = Arc::new(StaticRemoteSource::new());
+        let source = StaticRemoteSource::new_with_fallback(fallback.clone());
+        source.add_account(pubkey, base_account.clone(), None);
+        assert_eq!(source.get_account(&pubkey, None), Some(base_account));
+        assert_eq!(source.get_account(&pubkey, Some(&node_id)), None);
+
+        fallback.add_account(pubkey, node_account.clone(), Some(node_id));
+        assert_eq!(
+            source.get_account(&pubkey, Some(&node_id)),
+            Some(node_account)
+        );
+
+        source.remove_account(&pubkey, None);
+        assert_eq!(source.get_account(&pubkey, None), None);
+    }
+
+    #[test]
+    fn test_directory_remote_source() {
+        let dir = std::env::temp_dir().join(format!("remote-source-{}", Pubkey::new_unique()));
+        let node_id = Pubkey::new_unique();
+        fs::create_dir_all(dir.join(node_id.to_string())).unwrap();
+
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account_json = serde_json::json!({
+            "pubkey": pubkey.to_string(),
+            "account": {
+                "lamports": 42,
+                "data": ["AQID", "base64"],
+                "owner": owner.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": 3,
+            },
+        });
+        fs::write(
+            dir.join(node_id.to_string()).join(format!("{pubkey}.json")),
+            account_json.to_string(),
+        )
+        .unwrap();
+
+        let source = remote_source_from_arg(dir.to_str().unwrap()).unwrap();
+        assert!(source.get_account(&pubkey, None).is_none());
+        let account = source.get_account(&pubkey, Some(&node_id)).unwrap();
+        assert_eq!(account.lamports(), 42);
+        assert_eq!(account.data(), &[1, 2, 3]);
+        assert_eq!(account.owner(), &owner);
+        assert!(account.remote);
+
+        assert!(remote_source_from_arg(dir.join("missing").to_str().unwrap()).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,307 @@
+//! Pooled RPC clients of the nodes remote accounts are loaded from.
+//!
+//! Every node gets a single [`RpcClient`], shared by all the loads, whose requests are limited
+//! by a token bucket. A node failing [`RpcPoolConfig::failure_threshold`] times in a row is
+//! considered down: its circuit opens, and requests fail fast without reaching it for a backoff
+//! period that doubles on every new failure. Once the period elapses, a single request is let
+//! through to probe the node.
+//!
+//! Requests only start if they can before their deadline, waits on the rate limit included. A
+//! request in flight is bounded by [`RpcPoolConfig::timeout_ms`].
+
+use {
+    crate::config::RpcPoolConfig,
+    dashmap::DashMap,
+    solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient},
+    solana_metrics::datapoint_warn,
+    solana_sdk::commitment_config::CommitmentConfig,
+    std::{
+        cmp,
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+/// Token bucket limiting the rate of the requests to a node.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Available tokens, negative when requests wait for tokens already reserved.
+    tokens: f64,
+    updated_at: Instant,
+    rate: f64,
+    burst: f64,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: u32, burst: u32) -> Self {
+        let burst = f64::from(cmp::max(burst, 1));
+        Self {
+            tokens: burst,
+            updated_at: Instant::now(),
+            rate: f64::from(cmp::max(requests_per_second, 1)),
+            burst,
+        }
+    }
+
+    /// Reserve a token, returning how long to wait for it. Nothing is reserved if the wait
+    /// would last past `deadline`.
+    fn acquire(&mut self, now: Instant, deadline: Instant) -> Result<Duration, String> {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.updated_at = now;
+        let wait = Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / self.rate);
+        if now + wait > deadline {
+            return Err(format!("rate limited for {} ms", wait.as_millis()));
+        }
+        self.tokens -= 1.0;
+        Ok(wait)
+    }
+}
+
+/// Circuit breaker of the requests to a node.
+#[derive(Debug)]
+struct CircuitBreaker {
+    consecutive_failures: u32,
+    /// Requests fail fast until then.
+    open_until: Option<Instant>,
+    /// Whether a request probes the node since the circuit reopened.
+    probing: bool,
+    backoff: Duration,
+    failure_threshold: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl CircuitBreaker {
+    fn new(config: &RpcPoolConfig) -> Self {
+        let initial_backoff = Duration::from_millis(config.initial_backoff_ms);
+        Self {
+            consecutive_failures: 0,
+            open_until: None,
+            probing: false,
+            backoff: initial_backoff,
+            failure_threshold: cmp::max(config.failure_threshold, 1),
+            initial_backoff,
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+        }
+    }
+
+    /// Whether a request may be sent. Once the circuit reopens, only one request probes the
+    /// node until it completes.
+    fn check(&mut self, now: Instant) -> Result<(), String> {
+        let Some(open_until) = self.open_until else {
+            return Ok(());
+        };
+        if now < open_until {
+            return Err(format!(
+                "node is down, retrying in {} ms",
+                (open_until - now).as_millis()
+            ));
+        }
+        if self.probing {
+            return Err("node is down, probing it".to_string());
+        }
+        self.probing = true;
+        Ok(())
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+        self.probing = false;
+        self.backoff = self.initial_backoff;
+    }
+
+    /// Record a failed request, returning the backoff period if the circuit opens.
+    fn record_failure(&mut self, now: Instant) -> Option<Duration> {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.probing = false;
+        if self.consecutive_failures < self.failure_threshold {
+            return None;
+        }
+        let backoff = self.backoff;
+        self.open_until = Some(now + backoff);
+        self.backoff = cmp::min(backoff * 2, self.max_backoff);
+        Some(backoff)
+    }
+}
+
+struct RpcNode {
+    rpc_client: RpcClient,
+    limiter: Mutex<TokenBucket>,
+    breaker: Mutex<CircuitBreaker>,
+}
+
+/// RPC clients of the nodes remote accounts are loaded from, keyed by URL.
+pub struct RpcClientPool {
+    nodes: DashMap<String, Arc<RpcNode>>,
+    config: RpcPoolConfig,
+}
+
+impl RpcClientPool {
+    pub fn new(config: RpcPoolConfig) -> Self {
+        Self {
+            nodes: DashMap::default(),
+            config,
+        }
+    }
+
+    fn node(&self, url: &str) -> Arc<RpcNode> {
+        if let Some(node) = self.nodes.get(url) {
+            return node.clone();
+        }
+        self.nodes
+            .entry(url.to_string())
+            .or_insert_with(|| {
+                Arc::new(RpcNode {
+                    rpc_client: RpcClient::new_with_timeout_and_commitment(
+                        url.to_string(),
+                        Duration::from_millis(self.config.timeout_ms),
+                        CommitmentConfig::confirmed(),
+                    ),
+                    limiter: Mutex::new(TokenBucket::new(
+                        self.config.requests_per_second,
+                        self.config.burst,
+                    )),
+                    breaker: Mutex::new(CircuitBreaker::new(&self.config)),
+                })
+            })
+            .clone()
+    }
+
+    /// Send `request` with the client of the node at `url`. Fails without sending it if the
+    /// node is down, or if the request cannot start before `deadline`.
+    pub fn call<T>(
+        &self,
+        url: &str,
+        deadline: Instant,
+        request: impl FnOnce(&RpcClient) -> ClientResult<T>,
+    ) -> Result<T, String> {
+        let node = self.node(url);
+        node.breaker.lock().unwrap().check(Instant::now())?;
+        let wait = node
+            .limiter
+            .lock()
+            .unwrap()
+            .acquire(Instant::now(), deadline);
+        let wait = match wait {
+            Ok(wait) => wait,
+            Err(err) => {
+                // Not a failure of the node, let another request probe it
+                node.breaker.lock().unwrap().probing = false;
+                return Err(err);
+            }
+        };
+        thread::sleep(wait);
+
+        match request(&node.rpc_client) {
+            Ok(value) => {
+                node.breaker.lock().unwrap().record_success();
+                Ok(value)
+            }
+            Err(err) => {
+                let backoff = node.breaker.lock().unwrap().record_failure(Instant::now());
+                if let Some(backoff) = backoff {
+                    datapoint_warn!(
+                        "sonic-rpc-pool-circuit-open",
+                        ("url", url, String),
+                        ("backoff_ms", backoff.as_millis() as i64, i64),
+                        ("error", err.to_string(), String),
+                    );
+                }
+                Err(err.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_client::client_error::ClientError};
+
+    fn config() -> RpcPoolConfig {
+        RpcPoolConfig {
+            timeout_ms: 1_000,
+            requests_per_second: 10,
+            burst: 2,
+            failure_threshold: 2,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 150,
+        }
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let mut bucket = TokenBucket::new(10, 2);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+        assert_eq!(bucket.acquire(now, later), Ok(Duration::ZERO));
+        assert_eq!(bucket.acquire(now, later), Ok(Duration::ZERO));
+        // Out of tokens, the next one is 100 ms away
+        assert!(bucket
+            .acquire(now, now + Duration::from_millis(50))
+            .is_err());
+        let wait = bucket.acquire(now, later).unwrap();
+        assert_eq!(wait.as_millis(), 100);
+        // Refilled, but never above the burst
+        let now = now + Duration::from_secs(10);
+        assert_eq!(bucket.acquire(now, now), Ok(Duration::ZERO));
+        assert_eq!(bucket.acquire(now, now), Ok(Duration::ZERO));
+        assert!(bucket.acquire(now, now).is_err());
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let mut breaker = CircuitBreaker::new(&config());
+        let now = Instant::now();
+        assert_eq!(breaker.record_failure(now), None);
+        assert!(breaker.check(now).is_ok());
+        assert_eq!(
+            breaker.record_failure(now),
+            Some(Duration::from_millis(100))
+        );
+        assert!(breaker.check(now + Duration::from_millis(99)).is_err());
+
+        // A single request probes the node, its failure reopens the circuit for longer
+        let now = now + Duration::from_millis(100);
+        assert!(breaker.check(now).is_ok());
+        assert!(breaker.check(now).is_err());
+        assert_eq!(
+            breaker.record_failure(now),
+            Some(Duration::from_millis(150))
+        );
+        assert!(breaker.check(now + Duration::from_millis(149)).is_err());
+
+        let now = now + Duration::from_millis(150);
+        assert!(breaker.check(now).is_ok());
+        breaker.record_success();
+        assert!(breaker.check(now).is_ok());
+        assert_eq!(breaker.record_failure(now), None);
+    }
+
+    #[test]
+    fn test_rpc_pool_fails_fast() {
+        let pool = RpcClientPool::new(config());
+        let url = "http://127.0.0.1:1";
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let mut calls = 0;
+        let mut request = |_: &RpcClient| -> ClientResult<()> {
+            calls += 1;
+            Err(ClientError::from(std::io::Error::from(
+                std::io::ErrorKind::ConnectionRefused,
+            )))
+        };
+        assert!(pool.call(url, deadline, &mut request).is_err());
+        assert!(pool.call(url, deadline, &mut request).is_err());
+        assert!(pool.call(url, deadline, &mut request).is_err());
+        assert_eq!(calls, 2);
+        assert_eq!(pool.nodes.len(), 1);
+
+        // Other nodes are not affected, but the rate limit applies to each of them
+        let other_url = "http://127.0.0.1:2";
+        assert_eq!(pool.call(other_url, deadline, |_| Ok(1)), Ok(1));
+        assert_eq!(pool.call(other_url, deadline, |_| Ok(2)), Ok(2));
+        assert!(pool.call(other_url, Instant::now(), |_| Ok(3)).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 #![allow(clippy::arithmetic_side_effects)]
 use {
-    crate::{args::*, bigtable::*, blockstore::*, ledger_path::*, ledger_utils::*, program::*},
+    crate::{
+        args::*, bigtable::*, blockstore::*, ledger_path::*, ledger_utils::*, program::*,
+        remote_accounts::*,
+    },
     clap::{
         crate_description, crate_name, value_t, value_t_or_exit, values_t_or_exit, App,
         AppSettings, Arg, ArgMatches, SubCommand,
@@ -92,6 +95,7 @@ mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod remote_accounts;
 
 fn parse_encoding_format(matches: &ArgMatches<'_>) -> UiAccountEncoding {
     match matches.value_of("encoding") {
@@ -886,7 +890,7 @@ fn main() {
                 .possible_values(&["json", "json-compact"])
                 .help(
                     "Return information in specified output format, currently only available for \
-                     bigtable and program subcommands",
+                     bigtable, program and remote-accounts subcommands",
                 ),
         )
         .arg(
@@ -1448,6 +1452,7 @@ fn main() {
                 ),
         )
         .program_subcommand()
+        .remote_accounts_subcommand()
         .get_matches();
 
     info!("{} {}", crate_name!(), solana_version::version!());
@@ -1467,6 +1472,9 @@ fn main() {
         ("bigtable", Some(arg_matches)) => bigtable_process_command(&ledger_path, arg_matches),
         ("blockstore", Some(arg_matches)) => blockstore_process_command(&ledger_path, arg_matches),
         ("program", Some(arg_matches)) => program(&ledger_path, arg_matches),
+        ("remote-accounts", Some(arg_matches)) => {
+            remote_accounts_process_command(&ledger_path, arg_matches)
+        }
         // This match case provides legacy support for commands that were previously top level
         // subcommands of the binary, but have been moved under the blockstore subcommand.
         ("analyze-storage", Some(_))
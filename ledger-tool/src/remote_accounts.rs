@@ -0,0 +1,528 @@
+//! Sonic: the `remote-accounts` subcommand, inspecting the accounts the bank loads from the
+//! remote instead of its own state.
+//!
+//! The remote accounts are not part of the bank state, the loader only learns them while
+//! transactions are processed. The ledger is therefore always replayed from genesis, and the
+//! remote is unplugged before the accounts are looked up, so the output only reflects the
+//! ledger.
+use {
+    crate::{args::*, canonicalize_ledger_path, ledger_utils::*},
+    clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    log::*,
+    serde::Serialize,
+    solana_clap_utils::input_validators::is_slot,
+    solana_cli_output::{
+        CliAccount, CliAccountNewConfig, OutputFormat, QuietDisplay, VerboseDisplay,
+    },
+    solana_ledger::{blockstore_options::AccessType, use_snapshot_archives_at_startup},
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+    },
+    sonic_hypergrid::{
+        config::NamespaceKind,
+        remote_loader::{RemoteNamespace, RemoteOrigin},
+        remote_source::StaticRemoteSource,
+    },
+    std::{
+        fmt::{self, Display, Formatter},
+        fs::{self, File},
+        path::{Path, PathBuf},
+        process::exit,
+        sync::Arc,
+    },
+};
+
+pub trait RemoteAccountsSubCommand {
+    fn remote_accounts_subcommand(self) -> Self;
+}
+
+impl RemoteAccountsSubCommand for App<'_, '_> {
+    fn remote_accounts_subcommand(self) -> Self {
+        let halt_at_slot_arg = Arg::with_name("halt_at_slot")
+            .long("halt-at-slot")
+            .value_name("SLOT")
+            .validator(is_slot)
+            .takes_value(true)
+            .help("Process the ledger up to this slot, and inspect the bank at this slot");
+        let max_genesis_arg = Arg::with_name("max_genesis_archive_unpacked_size")
+            .long("max-genesis-archive-unpacked-size")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .default_value("10485760")
+            .help("maximum total uncompressed size of unpacked genesis archive");
+        let no_snapshot_arg = Arg::with_name("no_snapshot")
+            .long("no-snapshot")
+            .takes_value(false)
+            .help(
+                "Do not start from a local snapshot. Required if the ledger has snapshots, the \
+                 remote accounts are only known when the ledger is replayed from genesis",
+            );
+        let use_snapshot_archives_at_startup =
+            Arg::with_name(use_snapshot_archives_at_startup::cli::NAME)
+                .long(use_snapshot_archives_at_startup::cli::LONG_ARG)
+                .takes_value(true)
+                .possible_values(use_snapshot_archives_at_startup::cli::POSSIBLE_VALUES)
+                .default_value(
+                    use_snapshot_archives_at_startup::cli::default_value_for_ledger_tool(),
+                )
+                .help(use_snapshot_archives_at_startup::cli::HELP)
+                .long_help(use_snapshot_archives_at_startup::cli::LONG_HELP);
+
+        self.subcommand(
+            SubCommand::with_name("remote-accounts")
+                .about(
+                    "Inspect the accounts the bank loads from the remote after replaying the \
+                     ledger from genesis",
+                )
+                .setting(AppSettings::InferSubcommands)
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List every account the bank treats as remote, with its origin")
+                        .arg(&halt_at_slot_arg)
+                        .arg(&max_genesis_arg)
+                        .arg(&no_snapshot_arg)
+                        .arg(&use_snapshot_archives_at_startup),
+                )
+                .subcommand(
+                    SubCommand::with_name("export")
+                        .about(
+                            "Write the remote accounts as JSON files, one per account, loadable \
+                             with `solana-test-validator --account-dir`",
+                        )
+                        .arg(
+                            Arg::with_name("output_dir")
+                                .index(1)
+                                .value_name("DIR")
+                                .takes_value(true)
+                                .required(true)
+                                .help("Directory the account files are written to"),
+                        )
+                        .arg(&halt_at_slot_arg)
+                        .arg(&max_genesis_arg)
+                        .arg(&no_snapshot_arg)
+                        .arg(&use_snapshot_archives_at_startup),
+                )
+                .subcommand(
+                    SubCommand::with_name("diff")
+                        .about(
+                            "Compare the grid copies of the base layer accounts with the \
+                             accounts of the base layer",
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("u")
+                                .long("url")
+                                .value_name("URL")
+                                .takes_value(true)
+                                .help(
+                                    "RPC of the base layer [default: the `baselayer_rpc_url` of \
+                                     the hypergrid config]",
+                                ),
+                        )
+                        .arg(&halt_at_slot_arg)
+                        .arg(&max_genesis_arg)
+                        .arg(&no_snapshot_arg)
+                        .arg(&use_snapshot_archives_at_startup),
+                ),
+        )
+    }
+}
+
+/// An account the bank treats as remote.
+struct RemoteAccount {
+    pubkey: Pubkey,
+    origin: RemoteOrigin,
+    /// Whether it is a synthetic copy of a base layer account, rather than a migrated one.
+    synthetic: bool,
+    /// The copy of the bank, `None` if the remote no longer serves it.
+    account: Option<AccountSharedData>,
+}
+
+/// The accounts migrated or cloned into genesis, then the synthetic copies of base layer
+/// accounts, as the bank sees them.
+fn remote_accounts(bank: &Bank) -> Vec<RemoteAccount> {
+    let accounts_cache = &bank.rc.accounts.accounts_db.accounts_cache;
+    let mut accounts: Vec<_> = accounts_cache
+        .remote_loader
+        .remote_accounts()
+        .into_iter()
+        .map(|(pubkey, origin)| RemoteAccount {
+            pubkey,
+            origin,
+            synthetic: false,
+            account: bank.get_account_with_fixed_root(&pubkey),
+        })
+        .collect();
+    let mut synthetic_accounts: Vec<_> = accounts_cache
+        .synthetic_accounts
+        .iter()
+        .filter(|(pubkey, _)| accounts_cache.synthetic_accounts.is_remote(pubkey))
+        .map(|(pubkey, _)| RemoteAccount {
+            pubkey: *pubkey,
+            origin: RemoteOrigin::default(),
+            synthetic: true,
+            account: bank.get_account_with_fixed_root(pubkey),
+        })
+        .collect();
+    synthetic_accounts.sort_unstable_by_key(|account| account.pubkey);
+    accounts.extend(synthetic_accounts);
+    accounts
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliRemoteAccount {
+    pubkey: String,
+    namespace: NamespaceKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_slot: Option<Slot>,
+    synthetic: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_len: Option<usize>,
+}
+
+impl From<&RemoteAccount> for CliRemoteAccount {
+    fn from(remote_account: &RemoteAccount) -> Self {
+        let RemoteAccount {
+            pubkey,
+            origin,
+            synthetic,
+            account,
+        } = remote_account;
+        Self {
+            pubkey: pubkey.to_string(),
+            namespace: origin.namespace.kind(),
+            source: origin.source.map(|source| source.to_string()),
+            source_slot: origin.source_slot,
+            synthetic: *synthetic,
+            lamports: account.as_ref().map(|account| account.lamports()),
+            owner: account.as_ref().map(|account| account.owner().to_string()),
+            data_len: account.as_ref().map(|account| account.data().len()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliRemoteAccounts {
+    slot: Slot,
+    accounts: Vec<CliRemoteAccount>,
+}
+
+impl QuietDisplay for CliRemoteAccounts {}
+impl VerboseDisplay for CliRemoteAccounts {}
+
+impl Display for CliRemoteAccounts {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Remote accounts at slot {}: {}",
+            self.slot,
+            self.accounts.len()
+        )?;
+        for account in &self.accounts {
+            let mut origin = match (account.namespace, &account.source) {
+                (NamespaceKind::BaseLayer, _) | (_, None) => "base layer".to_string(),
+                (NamespaceKind::L1, Some(source)) => format!("L1 {source}"),
+                (NamespaceKind::Grid, Some(source)) => format!("grid {source}"),
+            };
+            if let Some(source_slot) = account.source_slot {
+                origin.push_str(&format!(" at slot {source_slot}"));
+            }
+            if account.synthetic {
+                origin.push_str(", synthetic");
+            }
+            match (account.lamports, &account.owner, account.data_len) {
+                (Some(lamports), Some(owner), Some(data_len)) => writeln!(
+                    f,
+                    "  {}: {} lamports, owner {}, {} bytes, from {}",
+                    account.pubkey, lamports, owner, data_len, origin
+                )?,
+                _ => writeln!(f, "  {}: not served, from {}", account.pubkey, origin)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliRemoteAccountDiff {
+    pubkey: String,
+    differences: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliRemoteAccountDiffs {
+    slot: Slot,
+    base_layer_slot: Slot,
+    compared: usize,
+    /// Accounts loaded from another chain than the base layer, which are not compared.
+    skipped: usize,
+    accounts: Vec<CliRemoteAccountDiff>,
+}
+
+impl QuietDisplay for CliRemoteAccountDiffs {}
+impl VerboseDisplay for CliRemoteAccountDiffs {}
+
+impl Display for CliRemoteAccountDiffs {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Compared {} remote accounts at slot {} with the base layer at slot {}, {} differ",
+            self.compared,
+            self.slot,
+            self.base_layer_slot,
+            self.accounts.len()
+        )?;
+        if self.skipped > 0 {
+            writeln!(
+                f,
+                "Skipped {} accounts loaded from other chains",
+                self.skipped
+            )?;
+        }
+        for account in &self.accounts {
+            writeln!(f, "  {}:", account.pubkey)?;
+            for difference in &account.differences {
+                writeln!(f, "    {difference}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How the grid copy of an account differs from the base layer one.
+fn account_differences(
+    grid: Option<&AccountSharedData>,
+    base_layer: Option<&AccountSharedData>,
+) -> Vec<String> {
+    let (grid, base_layer) = match (grid, base_layer) {
+        (None, None) => return vec![],
+        (Some(_), None) => return vec!["missing on the base layer".to_string()],
+        (None, Some(_)) => return vec!["missing on the grid".to_string()],
+        (Some(grid), Some(base_layer)) => (grid, base_layer),
+    };
+    let mut differences = vec![];
+    if grid.lamports() != base_layer.lamports() {
+        differences.push(format!(
+            "lamports: {} on the grid, {} on the base layer",
+            grid.lamports(),
+            base_layer.lamports()
+        ));
+    }
+    if grid.owner() != base_layer.owner() {
+        differences.push(format!(
+            "owner: {} on the grid, {} on the base layer",
+            grid.owner(),
+            base_layer.owner()
+        ));
+    }
+    if grid.executable() != base_layer.executable() {
+        differences.push(format!(
+            "executable: {} on the grid, {} on the base layer",
+            grid.executable(),
+            base_layer.executable()
+        ));
+    }
+    if grid.data() != base_layer.data() {
+        differences.push(format!(
+            "data: {} bytes on the grid, {} bytes on the base layer",
+            grid.data().len(),
+            base_layer.data().len()
+        ));
+    }
+    differences
+}
+
+fn export(bank: &Bank, accounts: &[RemoteAccount], output_dir: &Path) {
+    fs::create_dir_all(output_dir).unwrap_or_else(|err| {
+        eprintln!("Failed to create {}: {err}", output_dir.display());
+        exit(1);
+    });
+    let mut exported = 0;
+    for remote_account in accounts {
+        let Some(account) = &remote_account.account else {
+            warn!("{} is not served, skipping it", remote_account.pubkey);
+            continue;
+        };
+        let cli_account = CliAccount::new_with_config(
+            &remote_account.pubkey,
+            account,
+            &CliAccountNewConfig::default(),
+        );
+        let path = output_dir.join(format!("{}.json", remote_account.pubkey));
+        let file = File::create(&path).unwrap_or_else(|err| {
+            eprintln!("Failed to create {}: {err}", path.display());
+            exit(1);
+        });
+        serde_json::to_writer_pretty(file, &cli_account).unwrap_or_else(|err| {
+            eprintln!("Failed to write {}: {err}", path.display());
+            exit(1);
+        });
+        exported += 1;
+    }
+    println!(
+        "Exported {exported} remote accounts at slot {} to {}",
+        bank.slot(),
+        output_dir.display()
+    );
+}
+
+fn diff(bank: &Bank, accounts: Vec<RemoteAccount>, url: &str) -> CliRemoteAccountDiffs {
+    let (accounts, others): (Vec<_>, Vec<_>) = accounts
+        .into_iter()
+        .partition(|account| account.origin.namespace == RemoteNamespace::BaseLayer);
+    let rpc_client = RpcClient::new(url.to_string());
+    let mut base_layer_slot = 0;
+    let mut diffs = vec![];
+    for chunk in accounts.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let pubkeys: Vec<_> = chunk.iter().map(|account| account.pubkey).collect();
+        let response = rpc_client
+            .get_multiple_accounts_with_commitment(&pubkeys, rpc_client.commitment())
+            .unwrap_or_else(|err| {
+                eprintln!("Failed to fetch the base layer accounts from {url}: {err}");
+                exit(1);
+            });
+        base_layer_slot = base_layer_slot.max(response.context.slot);
+        for (remote_account, base_layer_account) in chunk.iter().zip(response.value) {
+            let base_layer_account = base_layer_account.map(AccountSharedData::from);
+            let differences =
+                account_differences(remote_account.account.as_ref(), base_layer_account.as_ref());
+            if !differences.is_empty() {
+                diffs.push(CliRemoteAccountDiff {
+                    pubkey: remote_account.pubkey.to_string(),
+                    differences,
+                });
+            }
+        }
+    }
+    CliRemoteAccountDiffs {
+        slot: bank.slot(),
+        base_layer_slot,
+        compared: accounts.len(),
+        skipped: others.len(),
+        accounts: diffs,
+    }
+}
+
+/// Replay the ledger from genesis, then unplug the remote so that looking the accounts up
+/// never reaches the network.
+fn load_bank(ledger_path: &Path, arg_matches: &ArgMatches<'_>) -> Arc<Bank> {
+    let process_options = parse_process_options(ledger_path, arg_matches);
+    let genesis_config = open_genesis_config_by(ledger_path, arg_matches);
+    info!("genesis hash: {}", genesis_config.hash());
+    let blockstore = open_blockstore(ledger_path, arg_matches, AccessType::Secondary);
+    let (bank_forks, starting_snapshot_hashes) = load_and_process_ledger_or_exit(
+        arg_matches,
+        &genesis_config,
+        Arc::new(blockstore),
+        process_options,
+        None,
+        None,
+    );
+    if starting_snapshot_hashes.is_some() {
+        eprintln!(
+            "The ledger was loaded from a snapshot, which does not hold the remote accounts. \
+             Pass --no-snapshot to replay it from genesis"
+        );
+        exit(1);
+    }
+    let bank = bank_forks.read().unwrap().working_bank();
+    bank.rc
+        .accounts
+        .accounts_db
+        .accounts_cache
+        .remote_loader
+        .set_remote_source(Arc::new(StaticRemoteSource::new()));
+    bank
+}
+
+pub fn remote_accounts_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    let (subcommand, arg_matches) = match matches.subcommand() {
+        (subcommand, Some(arg_matches)) => (subcommand, arg_matches),
+        _ => unreachable!(),
+    };
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let bank = load_bank(&ledger_path, arg_matches);
+    let accounts = remote_accounts(&bank);
+    let output_format = OutputFormat::from_matches(arg_matches, "output_format", false);
+
+    match subcommand {
+        "list" => {
+            let cli_accounts = CliRemoteAccounts {
+                slot: bank.slot(),
+                accounts: accounts.iter().map(CliRemoteAccount::from).collect(),
+            };
+            println!("{}", output_format.formatted_string(&cli_accounts));
+        }
+        "export" => {
+            let output_dir = PathBuf::from(value_t!(arg_matches, "output_dir", String).unwrap());
+            export(&bank, &accounts, &output_dir);
+        }
+        "diff" => {
+            let url = value_t!(arg_matches, "url", String).unwrap_or_else(|_| {
+                bank.rc
+                    .accounts
+                    .accounts_db
+                    .accounts_cache
+                    .remote_loader
+                    .config()
+                    .baselayer_rpc_url
+                    .clone()
+            });
+            let cli_diffs = diff(&bank, accounts, &url);
+            println!("{}", output_format.formatted_string(&cli_diffs));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::WritableAccount};
+
+    #[test]
+    fn test_account_differences() {
+        let owner = Pubkey::new_unique();
+        let account = AccountSharedData::new(42, 3, &owner);
+        assert!(account_differences(None, None).is_empty());
+        assert!(account_differences(Some(&account), Some(&account)).is_empty());
+        assert_eq!(
+            account_differences(Some(&account), None),
+            vec!["missing on the base layer".to_string()]
+        );
+        assert_eq!(
+            account_differences(None, Some(&account)),
+            vec!["missing on the grid".to_string()]
+        );
+
+        // The remote flag of the grid copy is not a difference
+        let mut grid = account.clone();
+        grid.remote = true;
+        assert!(account_differences(Some(&grid), Some(&account)).is_empty());
+
+        grid.set_lamports(43);
+        grid.data_as_mut_slice()[0] = 1;
+        assert_eq!(
+            account_differences(Some(&grid), Some(&account)),
+            vec![
+                "lamports: 43 on the grid, 42 on the base layer".to_string(),
+                "data: 3 bytes on the grid, 3 bytes on the base layer".to_string(),
+            ]
+        );
+    }
+}
@@ -4819,7 +4819,8 @@ pub mod tests {
         },
         solana_storage_proto::convert::generated,
         solana_transaction_status::{
-            InnerInstruction, InnerInstructions, Reward, Rewards, TransactionTokenBalance,
+            InnerInstruction, InnerInstructions, Reward, Rewards, TransactionRemoteAccount,
+            TransactionTokenBalance,
         },
         std::{cmp::Ordering, thread::Builder, time::Duration},
         test_case::test_case,
@@ -7805,6 +7806,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    remote_accounts: None,
                 }
                 .into();
                 blockstore
@@ -7824,6 +7826,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    remote_accounts: None,
                 }
                 .into();
                 blockstore
@@ -7843,6 +7846,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    remote_accounts: None,
                 }
                 .into();
                 blockstore
@@ -7864,6 +7868,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data: Some(TransactionReturnData::default()),
                         compute_units_consumed,
+                        remote_accounts: None,
                     },
                 }
             })
@@ -7985,6 +7990,11 @@ pub mod tests {
         };
         let compute_units_consumed_1 = Some(3812649u64);
         let compute_units_consumed_2 = Some(42u64);
+        let test_remote_accounts = vec![TransactionRemoteAccount {
+            pubkey: Pubkey::new_unique(),
+            source: Some(Pubkey::new_unique()),
+            source_slot: Some(7),
+        }];
 
         // result not found
         assert!(transaction_status_cf
@@ -8006,6 +8016,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_1,
+            remote_accounts: Some(test_remote_accounts.clone()),
         }
         .into();
         assert!(transaction_status_cf
@@ -8026,6 +8037,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         } = transaction_status_cf
             .get_protobuf((Signature::default(), 0))
             .unwrap()
@@ -8044,6 +8056,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_1);
+        assert_eq!(remote_accounts.unwrap(), test_remote_accounts);
 
         // insert value
         let status = TransactionStatusMeta {
@@ -8059,6 +8072,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_2,
+            remote_accounts: None,
         }
         .into();
         assert!(transaction_status_cf
@@ -8079,6 +8093,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         } = transaction_status_cf
             .get_protobuf((Signature::from([2u8; 64]), 9))
             .unwrap()
@@ -8099,6 +8114,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_2);
+        assert_eq!(remote_accounts, None);
     }
 
     #[test]
@@ -8192,6 +8208,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            remote_accounts: None,
         }
         .into();
 
@@ -8368,6 +8385,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            remote_accounts: None,
         }
         .into();
 
@@ -8495,6 +8513,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            remote_accounts: None,
         }
         .into();
 
@@ -8666,6 +8685,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42),
+                    remote_accounts: None,
                 }
                 .into();
                 blockstore
@@ -8687,6 +8707,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42),
+                        remote_accounts: None,
                     },
                 }
             })
@@ -8788,6 +8809,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42u64),
+                    remote_accounts: None,
                 }
                 .into();
                 blockstore
@@ -8809,6 +8831,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42u64),
+                        remote_accounts: None,
                     },
                 }
             })
@@ -9483,6 +9506,7 @@ pub mod tests {
                 loaded_addresses: LoadedAddresses::default(),
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: None,
+                remote_accounts: None,
             }
             .into();
             transaction_status_cf
@@ -10245,6 +10269,7 @@ pub mod tests {
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(23456),
+            remote_accounts: None,
         };
         let deprecated_status: StoredTransactionStatusMeta = status.clone().try_into().unwrap();
         let protobuf_status: generated::TransactionStatusMeta = status.into();
@@ -10,7 +10,13 @@ use {
 
 /// Return the leader schedule for the given epoch.
 pub fn leader_schedule(epoch: Epoch, bank: &Bank) -> Option<LeaderSchedule> {
+    let sequencer_config = bank.sequencer_config();
     bank.epoch_staked_nodes(epoch).map(|stakes| {
+        // Sonic: the sequencer leads every slot, whatever the stakes
+        if let Some(config) = sequencer_config {
+            let slots_in_epoch = bank.get_slots_in_epoch(epoch) as usize;
+            return LeaderSchedule::new_from_schedule(vec![config.sequencer; slots_in_epoch]);
+        }
         let mut seed = [0u8; 32];
         seed[0..8].copy_from_slice(&epoch.to_le_bytes());
         let mut stakes: Vec<_> = stakes
@@ -86,7 +92,8 @@ mod tests {
     use {
         super::*,
         solana_runtime::genesis_utils::{
-            bootstrap_validator_stake_lamports, create_genesis_config_with_leader,
+            activate_sequencer_mode, bootstrap_validator_stake_lamports,
+            create_genesis_config_with_leader,
         },
     };
 
@@ -116,6 +123,28 @@ mod tests {
         assert_eq!(leader_schedule[2], pubkey);
     }
 
+    #[test]
+    fn test_leader_schedule_sequencer_mode() {
+        let leader = solana_sdk::pubkey::new_rand();
+        let sequencer = solana_sdk::pubkey::new_rand();
+        let mut genesis_config =
+            create_genesis_config_with_leader(0, &leader, bootstrap_validator_stake_lamports())
+                .genesis_config;
+        activate_sequencer_mode(&mut genesis_config, sequencer);
+        let bank = Bank::new_for_tests(&genesis_config);
+
+        let leader_schedule = leader_schedule(0, &bank).unwrap();
+        assert_eq!(
+            leader_schedule.num_slots() as u64,
+            genesis_config.epoch_schedule.slots_per_epoch
+        );
+        assert!(leader_schedule
+            .get_slot_leaders()
+            .iter()
+            .all(|slot_leader| *slot_leader == sequencer));
+        assert_eq!(slot_leader_at(1, &bank), Some(sequencer));
+    }
+
     #[test]
     fn test_leader_scheduler1_basic() {
         let pubkey = solana_sdk::pubkey::new_rand();
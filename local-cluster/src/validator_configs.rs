@@ -70,6 +70,8 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         generator_config: config.generator_config.clone(),
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         wen_restart_proto_path: config.wen_restart_proto_path.clone(),
+        fee_bill_config: config.fee_bill_config.clone(),
+        remote_source: config.remote_source.clone(),
     }
 }
 
@@ -75,7 +75,9 @@ use {
         poh_config::PohConfig,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
+        sonic_account_migrater::instruction::migrate_source_accounts,
         system_program, system_transaction,
+        transaction::Transaction,
         vote::state::VoteStateUpdate,
     },
     solana_streamer::socket::SocketAddrSpace,
@@ -85,6 +87,8 @@ use {
     },
     solana_vote::vote_parser,
     solana_vote_program::{vote_state::MAX_LOCKOUT_HISTORY, vote_transaction},
+    sonic_hypergrid::{config::CONFIG_FILE_ENV, remote_loader::NODE_TYPE_GRID},
+    sonic_mock_hssn::{HssnFixtures, HssnTransaction, MockHssn},
     std::{
         collections::{BTreeSet, HashMap, HashSet},
         fs,
@@ -1575,6 +1579,71 @@ fn test_wait_for_max_stake() {
     assert!(client.get_slot().unwrap() > 10);
 }
 
+#[test]
+#[serial]
+fn test_migrate_source_accounts_with_mock_hssn() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let hssn = MockHssn::start(HssnFixtures::default()).unwrap();
+    let config_dir = tempfile::tempdir().unwrap();
+    let config_file = config_dir.path().join("hypergrid.yml");
+    // Nothing listens on the base layer, all accounts come through HSSN
+    hssn.hypergrid_config("http://127.0.0.1:1")
+        .save(config_file.to_str().unwrap())
+        .unwrap();
+    std::env::set_var(CONFIG_FILE_ENV, &config_file);
+
+    let num_nodes = 2;
+    let cluster = LocalCluster::new_with_equal_stakes(
+        num_nodes,
+        DEFAULT_CLUSTER_LAMPORTS,
+        DEFAULT_NODE_STAKE,
+        SocketAddrSpace::Unspecified,
+    );
+    let client = RpcClient::new_socket(cluster.entry_point_info.rpc().unwrap());
+
+    // A grid node served by the cluster itself
+    let node_id = Keypair::new().pubkey();
+    hssn.add_node(
+        &node_id,
+        "grid",
+        &format!("http://{}", cluster.entry_point_info.rpc().unwrap()),
+        NODE_TYPE_GRID,
+    );
+    let recorded = Keypair::new().pubkey();
+    let owner = Pubkey::new_unique();
+    hssn.add_account(
+        &recorded,
+        &AccountSharedData::new(42, 3, &owner),
+        Some(&node_id),
+    );
+    let unrecorded = Keypair::new().pubkey();
+    cluster.transfer(&cluster.funding_keypair, &unrecorded, 100);
+
+    let payer = &cluster.funding_keypair;
+    let transaction = Transaction::new_signed_with_payer(
+        &[migrate_source_accounts(
+            payer.pubkey(),
+            node_id,
+            false,
+            vec![recorded, unrecorded],
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        client.get_latest_blockhash().unwrap(),
+    );
+    client.send_and_confirm_transaction(&transaction).unwrap();
+
+    let account = client.get_account(&recorded).unwrap();
+    assert_eq!(account.lamports, 42);
+    assert_eq!(account.owner, owner);
+    // The account HSSN had no record of is created there
+    assert!(hssn.transactions().contains(&HssnTransaction {
+        command: "create-solana-account".to_string(),
+        args: vec![unrecorded.to_string(), "0".to_string(), node_id.to_string()],
+    }));
+    std::env::remove_var(CONFIG_FILE_ENV);
+}
+
 #[test]
 // Test that when a leader is leader for banks B_i..B_{i+n}, and B_i is not
 // votable, then B_{i+1} still chains to B_i
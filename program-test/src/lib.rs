@@ -43,6 +43,7 @@ use {
         pubkey::Pubkey,
         rent::Rent,
         signature::{Keypair, Signer},
+        sonic_remote_account::RemoteAccountMeta,
         stable_layout::stable_instruction::StableInstruction,
         sysvar::{Sysvar, SysvarId},
     },
@@ -61,6 +62,7 @@ use {
         },
         time::{Duration, Instant},
     },
+    sonic_hypergrid::remote_source::{RemoteAccountSource, StaticRemoteSource},
     thiserror::Error,
     tokio::task::JoinHandle,
 };
@@ -423,6 +425,22 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
         let invoke_context = get_invoke_context();
         invoke_context.get_stack_height().try_into().unwrap()
     }
+
+    fn sol_get_remote_account_meta(&self, account_index: usize) -> RemoteAccountMeta {
+        let invoke_context = get_invoke_context();
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .unwrap();
+        let index_in_transaction = instruction_context
+            .get_index_of_instruction_account_in_transaction(
+                IndexOfAccount::try_from(account_index).unwrap(),
+            )
+            .unwrap();
+        transaction_context
+            .get_remote_account_meta(index_in_transaction)
+            .unwrap()
+    }
 }
 
 pub fn find_file(filename: &str) -> Option<PathBuf> {
@@ -468,6 +486,8 @@ pub struct ProgramTest {
     prefer_bpf: bool,
     deactivate_feature_set: HashSet<Pubkey>,
     transaction_account_lock_limit: Option<usize>,
+    remote_accounts: Vec<(Pubkey, AccountSharedData, Option<Pubkey>)>,
+    remote_source: Option<Arc<dyn RemoteAccountSource>>,
 }
 
 impl Default for ProgramTest {
@@ -500,6 +520,8 @@ impl Default for ProgramTest {
             prefer_bpf,
             deactivate_feature_set: HashSet::default(),
             transaction_account_lock_limit: None,
+            remote_accounts: vec![],
+            remote_source: None,
         }
     }
 }
@@ -554,6 +576,23 @@ impl ProgramTest {
             .push((address, AccountSharedData::from(account)));
     }
 
+    /// Add an account held by the remote `source` node, or by the base layer if `source` is
+    /// `None`
+    ///
+    /// The account only becomes part of the test environment once it is migrated by a
+    /// `MigrateRemoteAccounts` or `MigrateSourceAccounts` instruction.
+    pub fn add_remote_account(&mut self, address: Pubkey, account: Account, source: Option<Pubkey>) {
+        self.remote_accounts
+            .push((address, AccountSharedData::from(account), source));
+    }
+
+    /// Migrate remote accounts from `remote_source` instead of the base layer and HSSN
+    ///
+    /// Accounts added with `add_remote_account` take precedence over the ones of `remote_source`.
+    pub fn set_remote_source(&mut self, remote_source: Arc<dyn RemoteAccountSource>) {
+        self.remote_source = Some(remote_source);
+    }
+
     /// Add an account to the test environment with the account data in the provided `filename`
     pub fn add_account_with_file_data(
         &mut self,
@@ -840,6 +879,22 @@ impl ProgramTest {
             bank.add_builtin(program_id, name, builtin);
         }
 
+        if !self.remote_accounts.is_empty() || self.remote_source.is_some() {
+            let remote_source = match self.remote_source.take() {
+                Some(remote_source) => StaticRemoteSource::new_with_fallback(remote_source),
+                None => StaticRemoteSource::new(),
+            };
+            for (address, account, source) in self.remote_accounts.drain(..) {
+                remote_source.add_account(address, account, source);
+            }
+            bank.rc
+                .accounts
+                .accounts_db
+                .accounts_cache
+                .remote_loader
+                .set_remote_source(Arc::new(remote_source));
+        }
+
         for (address, account) in self.accounts.iter() {
             if bank.get_account(address).is_some() {
                 info!("Overriding account at {}", address);
@@ -0,0 +1,166 @@
+use {
+    solana_program_test::{ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sonic_account_migrater::instruction::{
+            deactivate_remote_accounts, migrate_remote_accounts, migrate_source_accounts,
+        },
+        transaction::Transaction,
+    },
+    sonic_hypergrid::remote_source::StaticRemoteSource,
+    std::sync::Arc,
+};
+
+#[tokio::test]
+async fn migrate_remote_accounts_from_source() {
+    let base_address = Keypair::new().pubkey();
+    let node_address = Keypair::new().pubkey();
+    let node_id = Keypair::new().pubkey();
+    let owner = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::default();
+    program_test.add_remote_account(
+        base_address,
+        Account {
+            lamports: 42,
+            data: vec![1, 2, 3],
+            owner,
+            ..Account::default()
+        },
+        None,
+    );
+    program_test.add_remote_account(
+        node_address,
+        Account {
+            lamports: 43,
+            owner,
+            ..Account::default()
+        },
+        Some(node_id),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    assert_eq!(
+        context
+            .banks_client
+            .get_account(base_address)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            migrate_remote_accounts(context.payer.pubkey(), vec![base_address]),
+            migrate_source_accounts(context.payer.pubkey(), node_id, false, vec![node_address]),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(base_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.lamports, 42);
+    assert_eq!(account.data, vec![1, 2, 3]);
+    assert_eq!(account.owner, owner);
+
+    let account = context
+        .banks_client
+        .get_account(node_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.lamports, 43);
+}
+
+async fn process(context: &mut ProgramTestContext, instruction: Instruction) {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> Option<u64> {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .map(|account| account.lamports)
+}
+
+#[tokio::test]
+async fn refresh_and_deactivate_remote_accounts() {
+    let base_address = Keypair::new().pubkey();
+    let node_address = Keypair::new().pubkey();
+    let node_id = Keypair::new().pubkey();
+    let account = |lamports| AccountSharedData::new(lamports, 0, &Pubkey::new_unique());
+
+    let remote_source = Arc::new(StaticRemoteSource::new());
+    remote_source.add_account(base_address, account(42), None);
+    remote_source.add_account(node_address, account(43), Some(node_id));
+    let mut program_test = ProgramTest::default();
+    program_test.set_remote_source(remote_source.clone());
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+    process(
+        &mut context,
+        migrate_remote_accounts(payer, vec![base_address]),
+    )
+    .await;
+    process(
+        &mut context,
+        migrate_source_accounts(payer, node_id, false, vec![node_address]),
+    )
+    .await;
+    assert_eq!(lamports(&mut context, base_address).await, Some(42));
+    assert_eq!(lamports(&mut context, node_address).await, Some(43));
+
+    // The accounts change on their source
+    remote_source.add_account(base_address, account(50), None);
+    remote_source.add_account(node_address, account(51), Some(node_id));
+
+    process(
+        &mut context,
+        migrate_source_accounts(payer, node_id, true, vec![node_address]),
+    )
+    .await;
+    assert_eq!(lamports(&mut context, node_address).await, Some(51));
+
+    process(
+        &mut context,
+        deactivate_remote_accounts(payer, vec![base_address]),
+    )
+    .await;
+    assert_eq!(lamports(&mut context, base_address).await, None);
+
+    // A new migration reads the current account
+    context.get_new_latest_blockhash().await.unwrap();
+    process(
+        &mut context,
+        migrate_remote_accounts(payer, vec![base_address]),
+    )
+    .await;
+    assert_eq!(lamports(&mut context, base_address).await, Some(50));
+}
@@ -4,6 +4,7 @@ pub use self::{
         SyscallLog, SyscallLogBpfComputeUnits, SyscallLogData, SyscallLogPubkey, SyscallLogU64,
     },
     mem_ops::{SyscallMemcmp, SyscallMemcpy, SyscallMemmove, SyscallMemset},
+    remote_account::SyscallGetRemoteAccountMeta,
     sysvar::{
         SyscallGetClockSysvar, SyscallGetEpochRewardsSysvar, SyscallGetEpochScheduleSysvar,
         SyscallGetFeesSysvar, SyscallGetLastRestartSlotSysvar, SyscallGetRentSysvar,
@@ -39,7 +40,8 @@ use {
             enable_alt_bn128_compression_syscall, enable_alt_bn128_syscall,
             enable_big_mod_exp_syscall, enable_partitioned_epoch_reward, enable_poseidon_syscall,
             error_on_syscall_bpf_function_hash_collisions, last_restart_slot_sysvar,
-            reject_callx_r10, remaining_compute_units_syscall_enabled, switch_to_new_elf_parser,
+            reject_callx_r10, remaining_compute_units_syscall_enabled,
+            remote_account_meta_syscall_enabled, switch_to_new_elf_parser,
         },
         hash::{Hash, Hasher},
         instruction::{AccountMeta, InstructionError, ProcessedSiblingInstruction},
@@ -67,6 +69,7 @@ use {
 mod cpi;
 mod logging;
 mod mem_ops;
+mod remote_account;
 mod sysvar;
 
 /// Maximum signers
@@ -278,6 +281,8 @@ pub fn create_program_runtime_environment_v1<'a>(
     let enable_poseidon_syscall = feature_set.is_active(&enable_poseidon_syscall::id());
     let remaining_compute_units_syscall_enabled =
         feature_set.is_active(&remaining_compute_units_syscall_enabled::id());
+    let remote_account_meta_syscall_enabled =
+        feature_set.is_active(&remote_account_meta_syscall_enabled::id());
     // !!! ATTENTION !!!
     // When adding new features for RBPF here,
     // also add them to `Bank::apply_builtin_program_feature_transitions()`.
@@ -467,6 +472,14 @@ pub fn create_program_runtime_environment_v1<'a>(
     // Log data
     result.register_function_hashed(*b"sol_log_data", SyscallLogData::vm)?;
 
+    // Sonic: origin of the remote accounts
+    register_feature_gated_function!(
+        result,
+        remote_account_meta_syscall_enabled,
+        *b"sol_get_remote_account_meta",
+        SyscallGetRemoteAccountMeta::vm,
+    )?;
+
     Ok(BuiltinProgram::new_loader(config, result))
 }
 
@@ -3827,6 +3840,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_get_remote_account_meta() {
+        use solana_sdk::sonic_remote_account::{RemoteAccountMeta, RemoteNamespace};
+
+        let mut remote_account = AccountSharedData::new(42, 0, &bpf_loader::id());
+        remote_account.remote = true;
+        let transaction_accounts = vec![
+            (
+                bpf_loader::id(),
+                AccountSharedData::new(0, 0, &native_loader::id()),
+            ),
+            (
+                Pubkey::new_unique(),
+                AccountSharedData::new(42, 0, &bpf_loader::id()),
+            ),
+            (Pubkey::new_unique(), remote_account),
+        ];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let remote_meta = RemoteAccountMeta {
+            remote: true,
+            namespace: RemoteNamespace::Grid,
+            source: Pubkey::new_unique(),
+            source_slot: 7,
+        };
+        invoke_context
+            .transaction_context
+            .set_remote_account_meta(2, remote_meta);
+        let instruction_accounts = [1, 2].map(|index_in_transaction| InstructionAccount {
+            index_in_transaction,
+            index_in_caller: index_in_transaction,
+            index_in_callee: index_in_transaction.saturating_sub(1),
+            is_signer: false,
+            is_writable: false,
+        });
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&[0], &instruction_accounts, &[]);
+        invoke_context.push().unwrap();
+
+        let syscall_base_cost = invoke_context.get_compute_budget().syscall_base_cost;
+        const META_VA: u64 = 0x100000000;
+        let mut meta = RemoteAccountMeta::default();
+        let config = Config::default();
+        let mut memory_mapping = MemoryMapping::new(
+            vec![MemoryRegion::new_writable(bytes_of_mut(&mut meta), META_VA)],
+            &config,
+            &SBPFVersion::V2,
+        )
+        .unwrap();
+
+        for (account_index, expected) in [(0, RemoteAccountMeta::default()), (1, remote_meta)] {
+            invoke_context.mock_set_remaining(syscall_base_cost);
+            let result = SyscallGetRemoteAccountMeta::rust(
+                &mut invoke_context,
+                account_index,
+                META_VA,
+                0,
+                0,
+                0,
+                &mut memory_mapping,
+            );
+            assert_eq!(result.unwrap(), SUCCESS);
+            let meta = translate_type::<RemoteAccountMeta>(&memory_mapping, META_VA, true).unwrap();
+            assert_eq!(*meta, expected);
+        }
+
+        // There is no third instruction account
+        invoke_context.mock_set_remaining(syscall_base_cost);
+        let result = SyscallGetRemoteAccountMeta::rust(
+            &mut invoke_context,
+            2,
+            META_VA,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::NotEnoughAccountKeys
+        );
+    }
+
     #[test]
     fn test_create_program_address() {
         // These tests duplicate the direct tests in solana_program::pubkey
@@ -0,0 +1,36 @@
+use {super::*, solana_sdk::sonic_remote_account::RemoteAccountMeta};
+
+declare_builtin_function!(
+    /// Sonic: get where an instruction account comes from
+    SyscallGetRemoteAccountMeta,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        account_index: u64,
+        meta_addr: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        let budget = invoke_context.get_compute_budget();
+
+        consume_compute_meter(invoke_context, budget.syscall_base_cost)?;
+
+        let meta = translate_type_mut::<RemoteAccountMeta>(
+            memory_mapping,
+            meta_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let index_in_transaction = instruction_context
+            .get_index_of_instruction_account_in_transaction(
+                IndexOfAccount::try_from(account_index)
+                    .map_err(|_| InstructionError::NotEnoughAccountKeys)?,
+            )?;
+        *meta = transaction_context.get_remote_account_meta(index_in_transaction)?;
+
+        Ok(SUCCESS)
+    }
+);
@@ -221,6 +221,7 @@ fn execute_transactions(
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(executed_units),
+                        remote_accounts: None,
                     };
 
                     Ok(ConfirmedTransactionWithStatusMeta {
@@ -0,0 +1,519 @@
+use {
+    solana_program_runtime::{declare_process_instruction, ic_msg, invoke_context::InvokeContext},
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_utils::limited_deserialize,
+        pubkey::Pubkey,
+        sonic_bridge::{
+            instruction::{ProgramInstruction, SPL_TOKEN_PROGRAM_ID},
+            program::{check_id, id},
+            state::{
+                create_deposit_record_address, create_outbox_message_address,
+                derive_bridge_config_address, derive_bridge_mint_authority_address,
+                derive_bridge_vault_address, derive_outbox_address, BridgeConfig, BridgeState,
+                DepositRecord, L1Signature, OutboxMessage, OutboxPayload, OutboxReceipt,
+                DEPOSIT_RECORD_SIZE,
+            },
+        },
+        system_instruction,
+    },
+};
+
+pub const DEFAULT_COMPUTE_UNITS: u64 = 750;
+
+/// Instruction index of `MintTo` in the SPL Token program
+const SPL_TOKEN_MINT_TO: u8 = 7;
+
+declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context| {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let instruction_data = instruction_context.get_instruction_data();
+    match limited_deserialize(instruction_data)? {
+        ProgramInstruction::CreditDeposit {
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+        } => Processor::credit_deposit(
+            invoke_context,
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+            false,
+        ),
+        ProgramInstruction::CreditTokenDeposit {
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+        } => Processor::credit_deposit(
+            invoke_context,
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+            true,
+        ),
+        ProgramInstruction::SendMessage {
+            message_id,
+            payload,
+            bump_seed,
+        } => Processor::send_message(invoke_context, message_id, payload, bump_seed),
+        ProgramInstruction::WriteReceipt {
+            message_id,
+            receipt,
+        } => Processor::write_receipt(invoke_context, message_id, receipt),
+    }
+});
+
+/// The SPL Token `MintTo` instruction, encoded by hand to keep spl-token out of the runtime.
+fn spl_token_mint_to(
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![SPL_TOKEN_MINT_TO];
+    data.extend_from_slice(&amount.to_le_bytes());
+    Instruction::new_with_bytes(
+        SPL_TOKEN_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    )
+}
+
+pub struct Processor;
+impl Processor {
+    /// Allocate `space` bytes to the derived address `account_key`, holding `lamports`, and
+    /// assign it to the program. `payer_key` tops it up to the rent exempt minimum.
+    fn create_program_account(
+        invoke_context: &mut InvokeContext,
+        payer_key: &Pubkey,
+        account_key: &Pubkey,
+        lamports: u64,
+        space: usize,
+    ) -> Result<(), InstructionError> {
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        let required_lamports = rent.minimum_balance(space).max(1).saturating_sub(lamports);
+
+        if required_lamports > 0 {
+            invoke_context.native_invoke(
+                system_instruction::transfer(payer_key, account_key, required_lamports).into(),
+                &[*payer_key],
+            )?;
+        }
+
+        invoke_context.native_invoke(
+            system_instruction::allocate(account_key, space as u64).into(),
+            &[*account_key],
+        )?;
+
+        invoke_context.native_invoke(
+            system_instruction::assign(account_key, &id()).into(),
+            &[*account_key],
+        )
+    }
+
+    /// Load the bridge config at instruction account 0 and check that
+    /// instruction account 1 is its authority and has signed.
+    fn checked_bridge_config(
+        invoke_context: &InvokeContext,
+    ) -> Result<BridgeConfig, InstructionError> {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n < 2 {
+            ic_msg!(invoke_context, "Config and authority accounts are required");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let config_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        if *config_account.get_key() != derive_bridge_config_address().0 {
+            ic_msg!(
+                invoke_context,
+                "Account {} is not the bridge config.",
+                config_account.get_key()
+            );
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !check_id(config_account.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let config = match config_account.get_state()? {
+            BridgeState::Config(config) => config,
+            _ => {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not a bridge config.",
+                    config_account.get_key()
+                );
+                return Err(InstructionError::InvalidAccountData);
+            }
+        };
+        drop(config_account);
+
+        let authority_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        if !authority_account.is_signer() {
+            ic_msg!(invoke_context, "Bridge authority must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        if *authority_account.get_key() != config.authority {
+            ic_msg!(
+                invoke_context,
+                "Account {} is not the bridge authority.",
+                authority_account.get_key()
+            );
+            return Err(InstructionError::IncorrectAuthority);
+        }
+
+        Ok(config)
+    }
+
+    /// Credit a deposit made on L1, once: the deposit record derived from the L1 transaction
+    /// is created on the first credit, so that any other credit of the deposit fails.
+    fn credit_deposit(
+        invoke_context: &mut InvokeContext,
+        l1_signature: L1Signature,
+        event_index: u8,
+        amount: u64,
+        bump_seed: u8,
+        token: bool,
+    ) -> Result<(), InstructionError> {
+        let mut config = Self::checked_bridge_config(invoke_context)?;
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n < if token { 8 } else { 6 } {
+            ic_msg!(invoke_context, "Not enough accounts to credit the deposit");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let authority_key = *instruction_context
+            .try_borrow_instruction_account(transaction_context, 1)?
+            .get_key();
+        let record_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+        let record_key = *record_account.get_key();
+        let record_lamports = record_account.get_lamports();
+        if create_deposit_record_address(&l1_signature, event_index, bump_seed) != Some(record_key)
+        {
+            ic_msg!(
+                invoke_context,
+                "Deposit record address must match derived address"
+            );
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !record_account.get_data().is_empty() {
+            ic_msg!(invoke_context, "Deposit {} is already credited", record_key);
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+        drop(record_account);
+        let recipient_key = *instruction_context
+            .try_borrow_instruction_account(transaction_context, 3)?
+            .get_key();
+
+        let mint_key = if token {
+            let mint_key = *instruction_context
+                .try_borrow_instruction_account(transaction_context, 5)?
+                .get_key();
+            let mint_authority_key = *instruction_context
+                .try_borrow_instruction_account(transaction_context, 6)?
+                .get_key();
+            if mint_authority_key != derive_bridge_mint_authority_address().0 {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not the bridge mint authority.",
+                    mint_authority_key
+                );
+                return Err(InstructionError::InvalidArgument);
+            }
+            let token_program_key = *instruction_context
+                .try_borrow_instruction_account(transaction_context, 7)?
+                .get_key();
+            if token_program_key != SPL_TOKEN_PROGRAM_ID {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not the SPL Token program.",
+                    token_program_key
+                );
+                return Err(InstructionError::IncorrectProgramId);
+            }
+            Some(mint_key)
+        } else {
+            let vault_key = *instruction_context
+                .try_borrow_instruction_account(transaction_context, 5)?
+                .get_key();
+            if vault_key != derive_bridge_vault_address().0 {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not the bridge vault.",
+                    vault_key
+                );
+                return Err(InstructionError::InvalidArgument);
+            }
+            None
+        };
+
+        Self::create_program_account(
+            invoke_context,
+            &authority_key,
+            &record_key,
+            record_lamports,
+            DEPOSIT_RECORD_SIZE,
+        )?;
+
+        if let Some(mint_key) = mint_key {
+            let mint_authority_key = derive_bridge_mint_authority_address().0;
+            invoke_context.native_invoke(
+                spl_token_mint_to(&mint_key, &recipient_key, &mint_authority_key, amount).into(),
+                &[mint_authority_key],
+            )?;
+        }
+
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        if mint_key.is_none() {
+            let mut vault_account =
+                instruction_context.try_borrow_instruction_account(transaction_context, 5)?;
+            if vault_account.get_lamports() < amount {
+                ic_msg!(
+                    invoke_context,
+                    "Bridge vault holds {} lamports, {} needed",
+                    vault_account.get_lamports(),
+                    amount
+                );
+                return Err(InstructionError::InsufficientFunds);
+            }
+            vault_account.checked_sub_lamports(amount)?;
+            drop(vault_account);
+            let mut recipient_account =
+                instruction_context.try_borrow_instruction_account(transaction_context, 3)?;
+            recipient_account.checked_add_lamports(amount)?;
+            drop(recipient_account);
+            config.deposited_lamports = config.deposited_lamports.saturating_add(amount);
+        }
+
+        let slot = invoke_context.get_sysvar_cache().get_clock()?.slot;
+        let mut record_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+        record_account.set_state(&BridgeState::Deposit(DepositRecord {
+            l1_signature,
+            event_index,
+            recipient: recipient_key,
+            mint: mint_key,
+            amount,
+            slot,
+        }))?;
+        drop(record_account);
+
+        config.deposit_count = config.deposit_count.saturating_add(1);
+        let mut config_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        config_account.set_state(&BridgeState::Config(config))?;
+        ic_msg!(
+            invoke_context,
+            "Credited deposit {} of {} to {}: {}.",
+            event_index,
+            record_key,
+            recipient_key,
+            amount
+        );
+
+        Ok(())
+    }
+
+    /// Send a message to L1, with the next id of the outbox. The lamports of a withdrawal are
+    /// paid into the bridge vault until they are released on L1.
+    fn send_message(
+        invoke_context: &mut InvokeContext,
+        message_id: u64,
+        payload: OutboxPayload,
+        bump_seed: u8,
+    ) -> Result<(), InstructionError> {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let withdraw_amount = match payload {
+            OutboxPayload::Withdraw { amount, .. } => Some(amount),
+            OutboxPayload::Call(_) => None,
+        };
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n < if withdraw_amount.is_some() { 5 } else { 4 } {
+            ic_msg!(invoke_context, "Not enough accounts to send the message");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let outbox_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        if *outbox_account.get_key() != derive_outbox_address().0 {
+            ic_msg!(
+                invoke_context,
+                "Account {} is not the outbox.",
+                outbox_account.get_key()
+            );
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !check_id(outbox_account.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let mut outbox = match outbox_account.get_state()? {
+            BridgeState::Outbox(outbox) => outbox,
+            _ => {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not an outbox.",
+                    outbox_account.get_key()
+                );
+                return Err(InstructionError::InvalidAccountData);
+            }
+        };
+        drop(outbox_account);
+        if message_id != outbox.next_message_id {
+            ic_msg!(
+                invoke_context,
+                "Message {} can't be sent, the outbox expects message {}",
+                message_id,
+                outbox.next_message_id
+            );
+            return Err(InstructionError::InvalidArgument);
+        }
+
+        let sender_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        let sender_key = *sender_account.get_key();
+        if !sender_account.is_signer() {
+            ic_msg!(invoke_context, "Sender account must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        drop(sender_account);
+
+        let message_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+        let message_key = *message_account.get_key();
+        let message_lamports = message_account.get_lamports();
+        if create_outbox_message_address(message_id, bump_seed) != Some(message_key) {
+            ic_msg!(invoke_context, "Message address must match derived address");
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !message_account.get_data().is_empty() {
+            ic_msg!(invoke_context, "Message {} is already sent", message_key);
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+        drop(message_account);
+
+        if let Some(amount) = withdraw_amount {
+            let vault_key = *instruction_context
+                .try_borrow_instruction_account(transaction_context, 4)?
+                .get_key();
+            if vault_key != derive_bridge_vault_address().0 {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not the bridge vault.",
+                    vault_key
+                );
+                return Err(InstructionError::InvalidArgument);
+            }
+            invoke_context.native_invoke(
+                system_instruction::transfer(&sender_key, &vault_key, amount).into(),
+                &[sender_key],
+            )?;
+        }
+
+        Self::create_program_account(
+            invoke_context,
+            &sender_key,
+            &message_key,
+            message_lamports,
+            OutboxMessage::space(&payload),
+        )?;
+
+        let slot = invoke_context.get_sysvar_cache().get_clock()?.slot;
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let mut message_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+        message_account.set_state(&BridgeState::Message(OutboxMessage {
+            id: message_id,
+            sender: sender_key,
+            payload,
+            slot,
+            receipt: None,
+        }))?;
+        drop(message_account);
+
+        outbox.next_message_id = outbox.next_message_id.saturating_add(1);
+        let mut outbox_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        outbox_account.set_state(&BridgeState::Outbox(outbox))?;
+        ic_msg!(
+            invoke_context,
+            "Sent message {} from {}.",
+            message_id,
+            sender_key
+        );
+
+        Ok(())
+    }
+
+    /// Write the receipt of a message, once.
+    fn write_receipt(
+        invoke_context: &mut InvokeContext,
+        message_id: u64,
+        receipt: OutboxReceipt,
+    ) -> Result<(), InstructionError> {
+        Self::checked_bridge_config(invoke_context)?;
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n < 3 {
+            ic_msg!(invoke_context, "Message account is required");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let mut message_account =
+            instruction_context.try_borrow_instruction_account(transaction_context, 2)?;
+        if !check_id(message_account.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let mut message = match message_account.get_state()? {
+            BridgeState::Message(message) if message.id == message_id => message,
+            _ => {
+                ic_msg!(
+                    invoke_context,
+                    "Account {} is not message {}.",
+                    message_account.get_key(),
+                    message_id
+                );
+                return Err(InstructionError::InvalidAccountData);
+            }
+        };
+        if message.receipt.is_some() {
+            ic_msg!(
+                invoke_context,
+                "Message {} already has a receipt",
+                message_id
+            );
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+        message.receipt = Some(receipt);
+        message_account.set_state(&BridgeState::Message(message))?;
+        ic_msg!(
+            invoke_context,
+            "Message {} handled on L1: {:?}.",
+            message_id,
+            receipt
+        );
+
+        Ok(())
+    }
+}
@@ -1,13 +1,17 @@
 use {
     solana_program_runtime::{declare_process_instruction, ic_msg, invoke_context::InvokeContext},
     solana_sdk::{
-        instruction::InstructionError, program_utils::limited_deserialize, pubkey::Pubkey, sonic_fee_settlement::{
-            instruction::{ProgramInstruction, SettlementBillParam},
-            program::check_id,
+        feature_set, instruction::InstructionError, program_utils::limited_deserialize, pubkey::Pubkey, sonic_fee_settlement::{
+            instruction::{derive_fee_multiplier_address, ProgramInstruction, SettlementBillParam},
+            program::{check_id, id},
             state::{
-                SettlementAccount, SettlementState, SettlementAccountType
+                DynamicFeeMultiplier, FeeMultiplierConfig, SettledRange, SettlementAccount,
+                SettlementAccountType, SettlementCursor, SettlementState, SETTLEMENT_CURSOR_SEED,
+                SETTLEMENT_CURSOR_SIZE,
             },
-        }
+        },
+        system_instruction,
+        transaction_context::IndexOfAccount,
     },
 };
 
@@ -34,6 +38,16 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
             address,
             amount,
         } => Processor::withdraw_fee_bill(invoke_context, address, amount),
+        ProgramInstruction::InitializeSettlementCursor {
+            bump_seed,
+        } => Processor::initialize_settlement_cursor(invoke_context, bump_seed),
+        ProgramInstruction::SetSettler {
+            settler,
+        } => Processor::set_settler(invoke_context, settler),
+        ProgramInstruction::SetFeeMultiplier {
+            multiplier,
+            dynamic,
+        } => Processor::set_fee_multiplier(invoke_context, multiplier, dynamic),
     }
 });
 
@@ -80,24 +94,228 @@ impl Processor {
         Ok(())
     }
     
+    fn initialize_settlement_cursor(
+        invoke_context: &mut InvokeContext,
+        bump_seed: u8,
+    ) -> Result<(), InstructionError> {
+        // Only the grid authority may appoint the first settler, otherwise whoever lands
+        // the initialization first would control the settlement of every fee bill.
+        Self::checked_fee_multiplier_config(invoke_context, 3, 4)?;
+
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let cursor_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        let cursor_key = *cursor_account.get_key();
+        let cursor_lamports = cursor_account.get_lamports();
+        if !cursor_account.get_data().is_empty() {
+            ic_msg!(invoke_context, "Cursor account must not be allocated");
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+        drop(cursor_account);
+
+        let settler_account = instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        let settler_key = *settler_account.get_key();
+        if !settler_account.is_signer() {
+            ic_msg!(invoke_context, "Settler account must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        drop(settler_account);
+
+        // Use a derived address to ensure that the grid has a single settlement cursor.
+        let derived_cursor_key = Pubkey::create_program_address(&[SETTLEMENT_CURSOR_SEED, &[bump_seed]], &id())?;
+        if cursor_key != derived_cursor_key {
+            ic_msg!(invoke_context, "Cursor address must match derived address: {}", derived_cursor_key);
+            return Err(InstructionError::InvalidArgument);
+        }
+
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        let required_lamports = rent
+            .minimum_balance(SETTLEMENT_CURSOR_SIZE)
+            .max(1)
+            .saturating_sub(cursor_lamports);
+
+        if required_lamports > 0 {
+            invoke_context.native_invoke(
+                system_instruction::transfer(&settler_key, &cursor_key, required_lamports).into(),
+                &[settler_key],
+            )?;
+        }
+
+        invoke_context.native_invoke(
+            system_instruction::allocate(&cursor_key, SETTLEMENT_CURSOR_SIZE as u64).into(),
+            &[cursor_key],
+        )?;
+
+        invoke_context.native_invoke(
+            system_instruction::assign(&cursor_key, &id()).into(),
+            &[cursor_key],
+        )?;
+
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+        let mut cursor_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        cursor_account.set_state(&SettlementState::SettlementCursor(SettlementCursor::new(settler_key)))?;
+        ic_msg!(invoke_context, "Initialized settlement cursor {} with settler {}.", cursor_key, settler_key);
+
+        Ok(())
+    }
+
+    /// Load the settlement cursor at instruction account 0 and check that
+    /// instruction account 1 is its settler and has signed.
+    fn checked_settlement_cursor(invoke_context: &InvokeContext) -> Result<SettlementCursor, InstructionError> {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n < 2 {
+            ic_msg!(invoke_context, "Cursor and settler accounts are required");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let cursor_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        if !check_id(cursor_account.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let cursor = match cursor_account.get_state()? {
+            SettlementState::SettlementCursor(cursor) => cursor,
+            _ => {
+                ic_msg!(invoke_context, "Account {} is not a settlement cursor.", cursor_account.get_key());
+                return Err(InstructionError::InvalidAccountData);
+            }
+        };
+        drop(cursor_account);
+
+        let settler_account = instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        if !settler_account.is_signer() {
+            ic_msg!(invoke_context, "Settler account must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        if *settler_account.get_key() != cursor.settler {
+            ic_msg!(invoke_context, "Account {} is not the settler.", settler_account.get_key());
+            return Err(InstructionError::IncorrectAuthority);
+        }
+
+        Ok(cursor)
+    }
+
+    fn set_settler(invoke_context: &mut InvokeContext, settler: Pubkey) -> Result<(), InstructionError> {
+        let mut cursor = Self::checked_settlement_cursor(invoke_context)?;
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        ic_msg!(invoke_context, "Settler changed from {} to {}.", cursor.settler, settler);
+        cursor.settler = settler;
+        let mut cursor_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        cursor_account.set_state(&SettlementState::SettlementCursor(cursor))?;
+
+        Ok(())
+    }
+
+    /// Load the fee multiplier config at instruction account `config_index` and check
+    /// that instruction account `authority_index` is its authority and has signed.
+    fn checked_fee_multiplier_config(
+        invoke_context: &InvokeContext,
+        config_index: IndexOfAccount,
+        authority_index: IndexOfAccount,
+    ) -> Result<FeeMultiplierConfig, InstructionError> {
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let n = instruction_context.get_number_of_instruction_accounts();
+        if n <= config_index.max(authority_index) {
+            ic_msg!(invoke_context, "Config and authority accounts are required");
+            return Err(InstructionError::NotEnoughAccountKeys);
+        }
+
+        let config_account = instruction_context.try_borrow_instruction_account(transaction_context, config_index)?;
+        if *config_account.get_key() != derive_fee_multiplier_address().0 {
+            ic_msg!(invoke_context, "Account {} is not the fee multiplier config.", config_account.get_key());
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !check_id(config_account.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let config = match config_account.get_state()? {
+            SettlementState::FeeMultiplier(config) => config,
+            _ => {
+                ic_msg!(invoke_context, "Account {} is not a fee multiplier config.", config_account.get_key());
+                return Err(InstructionError::InvalidAccountData);
+            }
+        };
+        drop(config_account);
+
+        let authority_account = instruction_context.try_borrow_instruction_account(transaction_context, authority_index)?;
+        if !authority_account.is_signer() {
+            ic_msg!(invoke_context, "Fee multiplier authority must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        if *authority_account.get_key() != config.authority {
+            ic_msg!(invoke_context, "Account {} is not the fee multiplier authority.", authority_account.get_key());
+            return Err(InstructionError::IncorrectAuthority);
+        }
+
+        Ok(config)
+    }
+
+    fn set_fee_multiplier(
+        invoke_context: &mut InvokeContext,
+        multiplier: u32,
+        dynamic: Option<DynamicFeeMultiplier>,
+    ) -> Result<(), InstructionError> {
+        if !invoke_context
+            .feature_set
+            .is_active(&feature_set::sonic_fee_multiplier_governance::id())
+        {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+
+        let config = Self::checked_fee_multiplier_config(invoke_context, 0, 1)?;
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context.get_current_instruction_context()?;
+
+        let mut new_config = FeeMultiplierConfig::new(config.authority, multiplier, dynamic);
+        if let Some(dynamic) = dynamic {
+            new_config.active_multiplier = multiplier.clamp(dynamic.min_multiplier, dynamic.max_multiplier);
+        }
+        if !new_config.is_valid() {
+            ic_msg!(invoke_context, "Fee multiplier {} or its dynamic bounds {:?} are out of range.", multiplier, dynamic);
+            return Err(InstructionError::InvalidArgument);
+        }
+
+        ic_msg!(invoke_context, "Fee multiplier changed from {} to {}.", config.active_multiplier, new_config.active_multiplier);
+        let mut config_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        config_account.set_state(&SettlementState::FeeMultiplier(new_config))?;
+
+        Ok(())
+    }
+
     fn settle_fee_bill(
         invoke_context: &mut InvokeContext,
         from_id: u64,
         end_id: u64,
         bills: Vec<SettlementBillParam>
     ) -> Result<(), InstructionError> {
+        let mut cursor = Self::checked_settlement_cursor(invoke_context)?;
+        if !cursor.is_next_range(from_id, end_id) {
+            ic_msg!(
+                invoke_context,
+                "Bill range {}..={} does not follow the settled bills, expected to start at {}.",
+                from_id, end_id, cursor.next_bill_id
+            );
+            return Err(InstructionError::InvalidInstructionData);
+        }
+
         let transaction_context = &invoke_context.transaction_context;
         let instruction_context = transaction_context.get_current_instruction_context()?;
-
         let n = instruction_context.get_number_of_instruction_accounts();
-        if n < 1 {
-            ic_msg!(invoke_context, "No accounts provided");
-            return Err(InstructionError::NotEnoughAccountKeys);
-        }
 
+        let mut total_amount: u64 = 0;
         for bill in &bills {
             ic_msg!(invoke_context, "bill: {:?} {:?}", bill.key, bill.amount);
-            for i in 0..n {
+            total_amount = total_amount.checked_add(bill.amount).ok_or(InstructionError::ArithmeticOverflow)?;
+            // Skip the cursor and settler accounts.
+            for i in 2..n {
                 let mut account = instruction_context.try_borrow_instruction_account(transaction_context, i)?;
                 let key = *account.get_key();
                 if !account.is_signer() && account.is_writable() && check_id(account.get_owner()){
@@ -127,8 +345,8 @@ impl Processor {
                             },
                         }
                         if amount > 0 {
-                            state.amount += amount;
-                            state.withdrawable += amount;
+                            state.amount = state.amount.checked_add(amount).ok_or(InstructionError::ArithmeticOverflow)?;
+                            state.withdrawable = state.withdrawable.checked_add(amount).ok_or(InstructionError::ArithmeticOverflow)?;
                             account.set_state(&SettlementState::FeeBillSettled(state))?;
                         }
                     } else {
@@ -137,8 +355,27 @@ impl Processor {
                 }
             }
         };
-        
-        ic_msg!(invoke_context, "Sonic SettleFeeBill from {} to {}.", from_id, end_id);
+
+        let bill_count = end_id
+            .checked_sub(from_id)
+            .and_then(|count| count.checked_add(1))
+            .ok_or(InstructionError::InvalidArgument)?;
+        let clock = invoke_context.get_sysvar_cache().get_clock()?;
+        let range = SettledRange {
+            from_id,
+            end_id,
+            bill_count,
+            amount: total_amount,
+            slot: clock.slot,
+        };
+        ic_msg!(invoke_context, "Sonic SettleFeeBill from {} to {}: {:?}.", from_id, end_id, range);
+
+        if cursor.record(range).is_none() {
+            ic_msg!(invoke_context, "Bill range ending at {} can't be followed by another bill.", end_id);
+            return Err(InstructionError::InvalidArgument);
+        }
+        let mut cursor_account = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        cursor_account.set_state(&SettlementState::SettlementCursor(cursor))?;
 
         Ok(())
     }
@@ -146,47 +383,61 @@ impl Processor {
     fn withdraw_fee_bill(invoke_context: &mut InvokeContext, address: Pubkey, amount: u64) -> Result<(), InstructionError> {
         let transaction_context = &invoke_context.transaction_context;
         let instruction_context = transaction_context.get_current_instruction_context()?;
-        
+
         let n = instruction_context.get_number_of_instruction_accounts();
-        if n < 1 {
-            ic_msg!(invoke_context, "No accounts provided");
+        if n < 2 {
+            ic_msg!(invoke_context, "Settlement and owner accounts are required");
             return Err(InstructionError::NotEnoughAccountKeys);
         }
 
-        let data_acount = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
-        if !check_id(&(*data_acount.get_owner())) {
-            return Err(InstructionError::InvalidAccountOwner);
+        let owner_account = instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        if !owner_account.is_signer() {
+            ic_msg!(invoke_context, "Owner account must be a signer");
+            return Err(InstructionError::MissingRequiredSignature);
         }
+        if *owner_account.get_key() != address {
+            ic_msg!(invoke_context, "Account {} does not match the owner {}.", owner_account.get_key(), address);
+            return Err(InstructionError::InvalidArgument);
+        }
+        drop(owner_account);
 
-        // let mut signer: Option<Pubkey> = None;
-        // for i in 0..n {
-        //     let account = instruction_context.try_borrow_instruction_account(transaction_context, i)?;
-        //     let key = *account.get_key();
-        //     if account.is_signer() {
-        //         signer = Some(key);
-        //         break;
-        //     }
-        // }
-        // let address = signer.unwrap();
-
-        if let SettlementState::FeeBillSettled(mut state) = data_acount.get_state()? {
-            ic_msg!(invoke_context, "data account is initialized.");
-            if address.eq(&state.owner) {
-                if amount > state.withdrawable {
-                    ic_msg!(invoke_context, "Account {:?} withdrawed {}.", address, amount);
-                    return Err(InstructionError::InvalidInstructionData);
-                }
-                state.withdrawed += amount;
-                state.withdrawable -= amount;
-                ic_msg!(invoke_context, "Account {:?} withdrawed {}.", address, amount);
-            } else {
-                ic_msg!(invoke_context, "Account {:?} is not the owner.", address);
+        let mut data_acount = instruction_context.try_borrow_instruction_account(transaction_context, 0)?;
+        if !check_id(data_acount.get_owner()) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let mut state = match data_acount.get_state()? {
+            SettlementState::FeeBillSettled(state) => state,
+            _ => {
+                ic_msg!(invoke_context, "data account is not initialized.");
                 return Err(InstructionError::InvalidAccountData);
             }
-        } else {
-            ic_msg!(invoke_context, "data account is not initialized.");
-            return Err(InstructionError::InvalidAccountData);
+        };
+        if address != state.owner {
+            ic_msg!(invoke_context, "Account {} is not the owner.", address);
+            return Err(InstructionError::IncorrectAuthority);
         }
+        if amount > state.withdrawable {
+            ic_msg!(invoke_context, "Account {} has only {} withdrawable.", data_acount.get_key(), state.withdrawable);
+            return Err(InstructionError::InsufficientFunds);
+        }
+
+        // The settlement account must stay rent exempt.
+        let rent = invoke_context.get_sysvar_cache().get_rent()?;
+        let minimum_balance = rent.minimum_balance(data_acount.get_data().len());
+        if data_acount.get_lamports().saturating_sub(minimum_balance) < amount {
+            ic_msg!(invoke_context, "Account {} does not hold {} lamports above its rent exempt minimum.", data_acount.get_key(), amount);
+            return Err(InstructionError::InsufficientFunds);
+        }
+
+        state.withdrawable -= amount;
+        state.withdrawed = state.withdrawed.checked_add(amount).ok_or(InstructionError::ArithmeticOverflow)?;
+        data_acount.set_state(&SettlementState::FeeBillSettled(state))?;
+        data_acount.checked_sub_lamports(amount)?;
+        drop(data_acount);
+
+        let mut owner_account = instruction_context.try_borrow_instruction_account(transaction_context, 1)?;
+        owner_account.checked_add_lamports(amount)?;
+        ic_msg!(invoke_context, "Account {} withdrew {}.", address, amount);
 
         Ok(())
     }
@@ -48,6 +48,7 @@ pub enum RpcRequest {
     )]
     GetFeeCalculatorForBlockhash,
     GetFeeForMessage,
+    GetFeeMultiplier,
     #[deprecated(
         since = "1.9.0",
         note = "Please do not use, will no longer be available in the future"
@@ -140,6 +141,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetEpochSchedule => "getEpochSchedule",
             RpcRequest::GetFeeCalculatorForBlockhash => "getFeeCalculatorForBlockhash",
             RpcRequest::GetFeeForMessage => "getFeeForMessage",
+            RpcRequest::GetFeeMultiplier => "getFeeMultiplier",
             RpcRequest::GetFeeRateGovernor => "getFeeRateGovernor",
             RpcRequest::GetFees => "getFees",
             RpcRequest::GetFirstAvailableBlock => "getFirstAvailableBlock",
@@ -156,6 +156,24 @@ pub struct RpcFeeRateGovernor {
     pub fee_rate_governor: FeeRateGovernor,
 }
 
+/// Sonic: the fee multiplier applied by a bank, in units of `1 / denominator`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeMultiplier {
+    pub multiplier: u32,
+    pub denominator: u32,
+    /// Bounds of the multiplier, if it follows the block utilization
+    pub dynamic: Option<RpcDynamicFeeMultiplier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDynamicFeeMultiplier {
+    pub min_multiplier: u32,
+    pub max_multiplier: u32,
+    pub target_utilization_percent: u8,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcInflationGovernor {
@@ -12,11 +12,11 @@ use {
         request::RpcRequest,
         response::{
             Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
-            RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcFees, RpcIdentity,
-            RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcStakeActivation, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus, StakeActivationState,
+            RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcFeeMultiplier, RpcFees,
+            RpcIdentity, RpcInflationGovernor, RpcInflationRate, RpcInflationReward,
+            RpcKeyedAccount, RpcPerfSample, RpcPrioritizationFee, RpcResponseContext,
+            RpcSimulateTransactionResult, RpcSnapshotSlotInfo, RpcStakeActivation, RpcSupply,
+            RpcVersionInfo, RpcVoteAccountInfo, RpcVoteAccountStatus, StakeActivationState,
         },
     },
     solana_sdk::{
@@ -234,6 +234,7 @@ impl RpcSender for MockSender {
                             loaded_addresses: OptionSerializer::Skip,
                             return_data: OptionSerializer::Skip,
                             compute_units_consumed: OptionSerializer::Skip,
+                            remote_accounts: OptionSerializer::Skip,
                         }),
                 },
                 block_time: Some(1628633791),
@@ -292,6 +293,14 @@ impl RpcSender for MockSender {
                 active: 123,
                 inactive: 12,
             }),
+            "getFeeMultiplier" => json!(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: RpcFeeMultiplier {
+                    multiplier: 10_000,
+                    denominator: 10_000,
+                    dynamic: None,
+                },
+            }),
             "getStakeMinimumDelegation" => json!(Response {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: 123_456_789,
@@ -5349,6 +5349,26 @@ impl RpcClient {
             .ok_or_else(|| ClientErrorKind::Custom("Invalid blockhash".to_string()).into())
     }
 
+    /// Sonic: returns the fee multiplier applied to transaction fees.
+    pub async fn get_fee_multiplier(&self) -> ClientResult<RpcFeeMultiplier> {
+        self.get_fee_multiplier_with_commitment(self.commitment())
+            .await
+    }
+
+    /// Sonic: returns the fee multiplier applied to transaction fees, based on the commitment level.
+    pub async fn get_fee_multiplier_with_commitment(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> ClientResult<RpcFeeMultiplier> {
+        Ok(self
+            .send::<Response<RpcFeeMultiplier>>(
+                RpcRequest::GetFeeMultiplier,
+                json!([self.maybe_map_commitment(commitment_config).await?]),
+            )
+            .await?
+            .value)
+    }
+
     pub async fn get_new_latest_blockhash(&self, blockhash: &Hash) -> ClientResult<Hash> {
         let mut num_retries = 0;
         let start = Instant::now();
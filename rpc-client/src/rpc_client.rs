@@ -4016,6 +4016,21 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_fee_for_message(message))
     }
 
+    /// Sonic: returns the fee multiplier applied to transaction fees.
+    pub fn get_fee_multiplier(&self) -> ClientResult<RpcFeeMultiplier> {
+        self.invoke((self.rpc_client.as_ref()).get_fee_multiplier())
+    }
+
+    /// Sonic: returns the fee multiplier applied to transaction fees, based on the commitment level.
+    pub fn get_fee_multiplier_with_commitment(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> ClientResult<RpcFeeMultiplier> {
+        self.invoke(
+            (self.rpc_client.as_ref()).get_fee_multiplier_with_commitment(commitment_config),
+        )
+    }
+
     pub fn get_new_latest_blockhash(&self, blockhash: &Hash) -> ClientResult<Hash> {
         self.invoke((self.rpc_client.as_ref()).get_new_latest_blockhash(blockhash))
     }
@@ -71,6 +71,7 @@ use {
         message::SanitizedMessage,
         pubkey::{Pubkey, PUBKEY_BYTES},
         signature::{Keypair, Signature, Signer},
+        sonic_fee_settlement::state::FEE_MULTIPLIER_DENOMINATOR,
         stake::state::{StakeActivationStatus, StakeStateV2},
         stake_history::StakeHistory,
         system_instruction,
@@ -2279,6 +2280,27 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, is_valid))
     }
 
+    fn get_fee_multiplier(
+        &self,
+        config: RpcContextConfig,
+    ) -> Result<RpcResponse<RpcFeeMultiplier>> {
+        let bank = self.get_bank_with_config(config)?;
+        let dynamic = bank
+            .fee_multiplier_config()
+            .and_then(|config| config.dynamic)
+            .map(|dynamic| RpcDynamicFeeMultiplier {
+                min_multiplier: dynamic.min_multiplier,
+                max_multiplier: dynamic.max_multiplier,
+                target_utilization_percent: dynamic.target_utilization_percent,
+            });
+        let fee_multiplier = RpcFeeMultiplier {
+            multiplier: bank.fee_multiplier(),
+            denominator: FEE_MULTIPLIER_DENOMINATOR,
+            dynamic,
+        };
+        Ok(new_response(&bank, fee_multiplier))
+    }
+
     fn get_stake_minimum_delegation(&self, config: RpcContextConfig) -> Result<RpcResponse<u64>> {
         let bank = self.get_bank_with_config(config)?;
         let stake_minimum_delegation =
@@ -3615,6 +3637,13 @@ pub mod rpc_full {
             config: Option<RpcContextConfig>,
         ) -> Result<RpcResponse<Option<u64>>>;
 
+        #[rpc(meta, name = "getFeeMultiplier")]
+        fn get_fee_multiplier(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcResponse<RpcFeeMultiplier>>;
+
         #[rpc(meta, name = "getStakeMinimumDelegation")]
         fn get_stake_minimum_delegation(
             &self,
@@ -4224,6 +4253,15 @@ pub mod rpc_full {
             Ok(new_response(bank, fee))
         }
 
+        fn get_fee_multiplier(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcResponse<RpcFeeMultiplier>> {
+            debug!("get_fee_multiplier rpc request received");
+            meta.get_fee_multiplier(config.unwrap_or_default())
+        }
+
         fn get_stake_minimum_delegation(
             &self,
             meta: Self::Metadata,
@@ -9239,6 +9277,24 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_fee_multiplier() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let request = create_test_request("getFeeMultiplier", None);
+        let response: RpcResponse<RpcFeeMultiplier> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            response.value,
+            RpcFeeMultiplier {
+                multiplier: bank.fee_multiplier(),
+                denominator: FEE_MULTIPLIER_DENOMINATOR,
+                dynamic: None,
+            }
+        );
+    }
+
     #[test]
     fn test_get_fee_for_message() {
         let rpc = RpcHandler::start();
@@ -8,7 +8,8 @@ use {
         blockstore_processor::{TransactionStatusBatch, TransactionStatusMessage},
     },
     solana_transaction_status::{
-        extract_and_fmt_memos, map_inner_instructions, Reward, TransactionStatusMeta,
+        extract_and_fmt_memos, map_inner_instructions, Reward, TransactionRemoteAccount,
+        TransactionStatusMeta,
     },
     std::{
         sync::{
@@ -102,6 +103,7 @@ impl TransactionStatusService {
                             durable_nonce_fee,
                             return_data,
                             executed_units,
+                            remote_accounts,
                             ..
                         } = details;
                         let lamports_per_signature = match durable_nonce_fee {
@@ -139,6 +141,16 @@ impl TransactionStatusService {
                                 .collect(),
                         );
                         let loaded_addresses = transaction.get_loaded_addresses();
+                        let remote_accounts = (!remote_accounts.is_empty()).then(|| {
+                            remote_accounts
+                                .into_iter()
+                                .map(|(pubkey, origin)| TransactionRemoteAccount {
+                                    pubkey,
+                                    source: origin.source,
+                                    source_slot: origin.source_slot,
+                                })
+                                .collect()
+                        });
                         let mut transaction_status_meta = TransactionStatusMeta {
                             status,
                             fee,
@@ -152,6 +164,7 @@ impl TransactionStatusService {
                             loaded_addresses,
                             return_data,
                             compute_units_consumed: Some(executed_units),
+                            remote_accounts,
                         };
 
                         if let Some(transaction_notifier) = transaction_notifier.as_ref() {
@@ -375,6 +388,7 @@ pub(crate) mod tests {
             return_data: None,
             executed_units: 0,
             accounts_data_len_delta: 0,
+            remote_accounts: vec![],
         });
 
         let balances = TransactionBalancesSet {
@@ -107,7 +107,10 @@ use {
         },
     },
     solana_bpf_loader_program::syscalls::create_program_runtime_environment_v1,
-    solana_cost_model::cost_tracker::CostTracker,
+    solana_cost_model::{
+        block_cost_limits::{MAX_BLOCK_UNITS, MAX_WRITABLE_ACCOUNT_UNITS},
+        cost_tracker::CostTracker,
+    },
     solana_loader_v4_program::create_program_runtime_environment_v2,
     solana_measure::{measure, measure::Measure, measure_us},
     solana_perf::perf_libs,
@@ -179,6 +182,13 @@ use {
             ExecutionRecord, TransactionAccount, TransactionContext, TransactionReturnData,
         },
         program_utils::limited_deserialize,
+        sonic_account_migrater::state::{derive_genesis_clone_record_address, GenesisCloneRecord},
+        sonic_fee_settlement::{
+            self,
+            instruction::derive_fee_multiplier_address,
+            state::{FeeMultiplierConfig, SettlementState},
+        },
+        sonic_sequencer::{self, SequencerConfig},
     },
     solana_stake_program::stake_state::{
         self, InflationPointCalculationEvent, PointValue, StakeStateV2,
@@ -186,6 +196,7 @@ use {
     solana_system_program::{get_system_account_kind, SystemAccountKind},
     solana_vote::vote_account::{VoteAccount, VoteAccounts, VoteAccountsHashMap},
     solana_vote_program::vote_state::VoteState,
+    sonic_hypergrid::remote_loader::{RemoteNamespace, RemoteOrigin, RemoteProgramVersion},
     std::{
         borrow::Cow,
         cell::RefCell,
@@ -203,7 +214,7 @@ use {
             },
             Arc, LockResult, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
         },
-        thread::Builder,
+        thread::{sleep, Builder},
         time::{Duration, Instant},
     },
 };
@@ -229,10 +240,19 @@ mod sysvar_cache;
 pub(crate) mod tests;
 mod transaction_account_state_info;
 
+pub use fee_distribution::{CollectedFeeDetails, CollectedFeeDetailsSender, SlotFeeDetails};
+
 pub const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
 
 pub const MAX_LEADER_SCHEDULE_STAKES: Epoch = 5;
 
+/// Sonic: bounds of the backoff between the retries of a remote load failing during replay
+const REMOTE_LOAD_MIN_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+const REMOTE_LOAD_MAX_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// Sonic: number of slots the loads of a replayed transaction are retried for, as long as its
+/// blockhash stays valid
+const REMOTE_LOAD_MAX_RETRY_SLOTS: u32 = MAX_PROCESSING_AGE as u32;
+
 #[derive(Default)]
 struct RentMetrics {
     hold_range_us: AtomicU64,
@@ -460,6 +480,8 @@ pub struct BankFieldsToDeserialize {
     pub(crate) incremental_snapshot_persistence: Option<BankIncrementalSnapshotPersistence>,
     pub(crate) epoch_accounts_hash: Option<Hash>,
     pub(crate) epoch_reward_status: EpochRewardStatus,
+    /// Sonic: versions of the remote programs migrated on this fork
+    pub(crate) remote_programs: HashMap<Pubkey, RemoteProgramVersion>,
 }
 
 /// Bank's common fields shared by all supported snapshot versions for serialization.
@@ -573,6 +595,11 @@ impl PartialEq for Bank {
             loaded_programs_cache: _,
             check_program_modification_slot: _,
             epoch_reward_status: _,
+            collected_fee_details: _,
+            collected_fee_details_sender: _,
+            remote_programs: _,
+            sequencer_mode: _,
+            executed_compute_units: _,
             // Ignore new fields explicitly if they do not impact PartialEq.
             // Adding ".." will remove compile-time checks that if a new field
             // is added to the struct, this PartialEq is accordingly updated.
@@ -713,6 +740,9 @@ pub struct Bank {
     /// The number of signatures from valid transactions in this slot
     signature_count: AtomicU64,
 
+    /// Sonic: the compute units executed by the transactions of this slot
+    executed_compute_units: AtomicU64,
+
     /// Total capitalization, used to calculate inflation
     capitalization: AtomicU64,
 
@@ -835,6 +865,19 @@ pub struct Bank {
     pub check_program_modification_slot: bool,
 
     epoch_reward_status: EpochRewardStatus,
+
+    /// Sonic: transaction fees collected in this slot, by fee payer and by program
+    collected_fee_details: RwLock<CollectedFeeDetails>,
+
+    /// Sonic: receives the collected fee details of every bank derived from this one once frozen
+    pub collected_fee_details_sender: RwLock<Option<CollectedFeeDetailsSender>>,
+
+    /// Sonic: versions of the remote programs migrated on this fork, with the slot each
+    /// version is deployed at
+    remote_programs: RwLock<HashMap<Pubkey, RemoteProgramVersion>>,
+
+    /// Sonic: whether the grid is in sequencer mode, read from the sequencer config once per bank
+    sequencer_mode: bool,
 }
 
 struct VoteWithStakeDelegations {
@@ -975,6 +1018,7 @@ impl Bank {
             transactions_per_entry_max: AtomicU64::default(),
             tick_height: AtomicU64::default(),
             signature_count: AtomicU64::default(),
+            executed_compute_units: AtomicU64::default(),
             capitalization: AtomicU64::default(),
             max_tick_height: u64::default(),
             hashes_per_tick: Option::<u64>::default(),
@@ -1022,6 +1066,10 @@ impl Bank {
             ))),
             check_program_modification_slot: false,
             epoch_reward_status: EpochRewardStatus::default(),
+            collected_fee_details: RwLock::<CollectedFeeDetails>::default(),
+            collected_fee_details_sender: RwLock::new(None),
+            remote_programs: RwLock::<HashMap<Pubkey, RemoteProgramVersion>>::default(),
+            sequencer_mode: false,
         };
 
         let accounts_data_size_initial = bank.get_total_accounts_stats().unwrap().data_len as u64;
@@ -1303,6 +1351,7 @@ impl Bank {
             builtin_programs,
             tick_height: AtomicU64::new(parent.tick_height.load(Relaxed)),
             signature_count: AtomicU64::new(0),
+            executed_compute_units: AtomicU64::new(0),
             runtime_config: parent.runtime_config.clone(),
             hard_forks: parent.hard_forks.clone(),
             rewards: RwLock::new(vec![]),
@@ -1332,6 +1381,12 @@ impl Bank {
             loaded_programs_cache: parent.loaded_programs_cache.clone(),
             check_program_modification_slot: false,
             epoch_reward_status: parent.epoch_reward_status.clone(),
+            collected_fee_details: RwLock::<CollectedFeeDetails>::default(),
+            collected_fee_details_sender: RwLock::new(
+                parent.collected_fee_details_sender.read().unwrap().clone(),
+            ),
+            remote_programs: RwLock::new(parent.remote_programs.read().unwrap().clone()),
+            sequencer_mode: false,
         };
 
         let (_, ancestors_time_us) = measure_us!({
@@ -1433,6 +1488,8 @@ impl Bank {
             new.update_fees();
             new.update_last_restart_slot()
         });
+        new.apply_fee_multiplier();
+        new.apply_sequencer_mode();
 
         let (_, fill_sysvar_cache_time_us) = measure_us!(new.fill_missing_sysvar_cache_entries());
         time.stop();
@@ -1687,6 +1744,16 @@ impl Bank {
         *self.drop_callback.write().unwrap() = OptionalDropCallback(callback);
     }
 
+    /// Sonic: send the collected fee details of this bank and its descendants to `sender` once frozen
+    pub fn set_collected_fee_details_sender(&self, sender: Option<CollectedFeeDetailsSender>) {
+        *self.collected_fee_details_sender.write().unwrap() = sender;
+    }
+
+    /// Sonic: transaction fees collected in this slot, by fee payer and by program
+    pub fn collected_fee_details(&self) -> CollectedFeeDetails {
+        self.collected_fee_details.read().unwrap().clone()
+    }
+
     pub fn vote_only_bank(&self) -> bool {
         self.vote_only_bank
     }
@@ -1786,6 +1853,7 @@ impl Bank {
             transactions_per_entry_max: AtomicU64::default(),
             tick_height: AtomicU64::new(fields.tick_height),
             signature_count: AtomicU64::new(fields.signature_count),
+            executed_compute_units: AtomicU64::default(),
             capitalization: AtomicU64::new(fields.capitalization),
             max_tick_height: fields.max_tick_height,
             hashes_per_tick: fields.hashes_per_tick,
@@ -1834,6 +1902,10 @@ impl Bank {
             ))),
             check_program_modification_slot: false,
             epoch_reward_status: fields.epoch_reward_status,
+            collected_fee_details: RwLock::<CollectedFeeDetails>::default(),
+            collected_fee_details_sender: RwLock::new(None),
+            remote_programs: RwLock::new(fields.remote_programs),
+            sequencer_mode: false,
         };
         bank.finish_init(
             genesis_config,
@@ -2228,6 +2300,179 @@ impl Bank {
         }
     }
 
+    /// Sonic: the fee multiplier config of the grid, if one was created in genesis
+    pub fn fee_multiplier_config(&self) -> Option<FeeMultiplierConfig> {
+        self.fee_multiplier_config_account()
+            .map(|(_, _, config)| config)
+    }
+
+    /// Sonic: the fee multiplier config with its address and account
+    fn fee_multiplier_config_account(
+        &self,
+    ) -> Option<(Pubkey, AccountSharedData, FeeMultiplierConfig)> {
+        let (address, _) = derive_fee_multiplier_address();
+        let account = self.get_account_with_fixed_root(&address)?;
+        if !sonic_fee_settlement::program::check_id(account.owner()) {
+            return None;
+        }
+        match bincode::deserialize(account.data()) {
+            Ok(SettlementState::FeeMultiplier(config)) => Some((address, account, config)),
+            _ => None,
+        }
+    }
+
+    /// Sonic: the record of the accounts cloned into genesis from the base layer, if any
+    pub fn genesis_clone_record(&self) -> Option<GenesisCloneRecord> {
+        let (address, _) = derive_genesis_clone_record_address();
+        let account = self.get_account_with_fixed_root(&address)?;
+        if !sonic_account_migrater_program::check_id(account.owner()) {
+            return None;
+        }
+        bincode::deserialize(account.data()).ok()
+    }
+
+    /// Sonic: mark the accounts cloned into genesis as remote-origin, read at their source slot
+    fn apply_genesis_clone_record(&self) {
+        let Some(record) = self.genesis_clone_record() else {
+            return;
+        };
+        let remote_loader = &self.rc.accounts.accounts_db.accounts_cache.remote_loader;
+        for cloned in record.accounts {
+            remote_loader.set_genesis_origin(
+                cloned.pubkey,
+                RemoteOrigin {
+                    source: None,
+                    source_slot: cloned.source_slot,
+                    namespace: RemoteNamespace::BaseLayer,
+                },
+            );
+        }
+    }
+
+    /// Sonic: record where the accounts of the transaction loaded from a remote, or cloned from one
+    /// into genesis, come from
+    fn set_remote_account_metas(&self, transaction_context: &mut TransactionContext) {
+        for index in 0..transaction_context.get_number_of_accounts() {
+            let (Ok(pubkey), Ok(account)) = (
+                transaction_context.get_key_of_account_at_index(index),
+                transaction_context.get_account_at_index(index),
+            ) else {
+                continue;
+            };
+            let remote = account.borrow().remote;
+            let Some(origin) = self.remote_origin(pubkey, remote) else {
+                continue;
+            };
+            transaction_context.set_remote_account_meta(index, origin.account_meta(remote));
+        }
+    }
+
+    /// Sonic: the accounts of a transaction served from the remote cache, and where they were
+    /// loaded from
+    fn transaction_remote_accounts(
+        &self,
+        transaction_accounts: &[TransactionAccount],
+    ) -> Vec<(Pubkey, RemoteOrigin)> {
+        transaction_accounts
+            .iter()
+            .filter(|(_, account)| account.remote)
+            .filter_map(|(pubkey, _)| {
+                self.remote_origin(pubkey, true).map(|origin| (*pubkey, origin))
+            })
+            .collect()
+    }
+
+    /// Sonic: where an account was loaded from, `None` for the accounts of this chain
+    fn remote_origin(&self, pubkey: &Pubkey, remote: bool) -> Option<RemoteOrigin> {
+        let remote_loader = &self.rc.accounts.accounts_db.accounts_cache.remote_loader;
+        match remote_loader.get_remote_origin(pubkey) {
+            Some(origin) => Some(origin),
+            // The synthetic copies of the base layer accounts
+            None if remote => Some(RemoteOrigin::default()),
+            None => None,
+        }
+    }
+
+    /// Sonic: the sequencer of the grid, if it was created in sequencer mode
+    pub fn sequencer_config(&self) -> Option<SequencerConfig> {
+        let account = self.get_account_with_fixed_root(&sonic_sequencer::config::id())?;
+        if !solana_sdk::config::program::check_id(account.owner()) {
+            return None;
+        }
+        bincode::deserialize(account.data()).ok()
+    }
+
+    /// Sonic: whether the grid has a single leader rooting its blocks without votes
+    pub fn is_sequencer_mode(&self) -> bool {
+        self.sequencer_mode
+    }
+
+    /// Sonic: no vote can land in a sequencer grid, leave its blocks to user transactions.
+    /// The vote limit is a share of the block limit rather than an addition to it, so the
+    /// block limit is kept: user transactions can already fill the whole block.
+    fn apply_sequencer_mode(&mut self) {
+        self.sequencer_mode = self.sequencer_config().is_some();
+        if self.sequencer_mode {
+            self.cost_tracker.write().unwrap().set_limits(
+                MAX_WRITABLE_ACCOUNT_UNITS,
+                MAX_BLOCK_UNITS,
+                0,
+            );
+        }
+    }
+
+    /// Sonic: charge fees with the active multiplier of the fee multiplier config
+    fn apply_fee_multiplier(&mut self) {
+        if let Some(config) = self.fee_multiplier_config() {
+            self.fee_structure.set_fee_multiplier(config.active_multiplier);
+        }
+    }
+
+    /// Sonic: in dynamic mode, move the multiplier applied by the next bank with
+    /// the compute units executed in this one. Executed units are used rather
+    /// than the `CostTracker` block cost, which is only tracked by the leader.
+    fn update_fee_multiplier(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::sonic_fee_multiplier_governance::id())
+        {
+            return;
+        }
+        let Some((address, mut account, mut config)) = self.fee_multiplier_config_account() else {
+            return;
+        };
+        if config.dynamic.is_none() {
+            return;
+        }
+        let next_multiplier = config.next_active_multiplier(
+            self.executed_compute_units.load(Relaxed),
+            MAX_BLOCK_UNITS,
+        );
+        if next_multiplier == config.active_multiplier {
+            return;
+        }
+        config.active_multiplier = next_multiplier;
+
+        if let Err(err) = account.serialize_data(&SettlementState::FeeMultiplier(config)) {
+            warn!(
+                "Bank.update_fee_multiplier(): failed to store the multiplier at slot {}: {}",
+                self.slot, err
+            );
+            return;
+        }
+        self.store_account(&address, &account);
+    }
+
+    /// Sonic: the fee multiplier applied to the fees of this bank
+    pub fn fee_multiplier(&self) -> u32 {
+        self.fee_structure.fee_multiplier
+    }
+
+    /// Sonic: the compute units executed by the transactions of this bank
+    pub fn executed_compute_units(&self) -> u64 {
+        self.executed_compute_units.load(Relaxed)
+    }
+
     fn update_rent(&self) {
         self.update_sysvar_account(&sysvar::rent::id(), |account| {
             create_account(
@@ -3674,6 +3919,7 @@ impl Bank {
             self.collect_rent_eagerly();
             self.distribute_transaction_fees();
             self.distribute_rent_fees();
+            self.update_fee_multiplier();
             self.update_slot_history();
             self.run_incinerator();
 
@@ -4548,10 +4794,9 @@ impl Bank {
                     upgrade_authority_address: _,
                 }) = programdata.state()
                 {
-                    //Sonic: if program is remote, set slot to 0.
+                    //Sonic: remote programs are deployed at the slot they were migrated at.
                     if programdata.remote { 
-                        
-                        return Ok(0);
+                        return Ok(self.remote_program_deployment_slot(pubkey));
                     }
                     return Ok(slot);
                 }
@@ -4692,52 +4937,59 @@ impl Bank {
                 program_account,
                 programdata_account,
                 slot,
-            ) => programdata_account
-                .data()
-                .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
-                .ok_or(Box::new(InstructionError::InvalidAccountData).into())
-                .and_then(|programdata| {
-                    //Sonic: if program account is remote, set slot to 0.
-                    let mut dep_slot = slot;
-                    if program_account.remote {
-                        dep_slot = 0;
-                    }
-                    Self::load_program_from_bytes(
-                        &mut load_program_metrics,
-                        programdata,
-                        program_account.owner(),
-                        program_account
-                            .data()
-                            .len()
-                            .saturating_add(programdata_account.data().len()),
-                        dep_slot,
-                        environments.program_runtime_v1.clone(),
-                        reload,
-                    )
-                })
-                .map_err(|_| (slot, environments.program_runtime_v1.clone())),
-
-            ProgramAccountLoadResult::ProgramOfLoaderV4(program_account, slot) => program_account
-                .data()
-                .get(LoaderV4State::program_data_offset()..)
-                .ok_or(Box::new(InstructionError::InvalidAccountData).into())
-                .and_then(|elf_bytes| {
-                    //Sonic: if program account is remote, set slot to 0.
-                    let mut dep_slot = slot;
-                    if program_account.remote {
-                        dep_slot = 0;
-                    }
-                    Self::load_program_from_bytes(
-                        &mut load_program_metrics,
-                        elf_bytes,
-                        &loader_v4::id(),
-                        program_account.data().len(),
-                        dep_slot,
-                        environments.program_runtime_v2.clone(),
-                        reload,
-                    )
-                })
-                .map_err(|_| (slot, environments.program_runtime_v2.clone())),
+            ) => {
+                //Sonic: remote programs are deployed at the slot they were migrated at, not
+                //at the programdata slot of their source.
+                let slot = if program_account.remote {
+                    self.remote_program_deployment_slot(pubkey)
+                } else {
+                    slot
+                };
+                programdata_account
+                    .data()
+                    .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+                    .ok_or(Box::new(InstructionError::InvalidAccountData).into())
+                    .and_then(|programdata| {
+                        Self::load_program_from_bytes(
+                            &mut load_program_metrics,
+                            programdata,
+                            program_account.owner(),
+                            program_account
+                                .data()
+                                .len()
+                                .saturating_add(programdata_account.data().len()),
+                            slot,
+                            environments.program_runtime_v1.clone(),
+                            reload,
+                        )
+                    })
+                    .map_err(|_| (slot, environments.program_runtime_v1.clone()))
+            }
+
+            ProgramAccountLoadResult::ProgramOfLoaderV4(program_account, slot) => {
+                //Sonic: remote programs are deployed at the slot they were migrated at.
+                let slot = if program_account.remote {
+                    self.remote_program_deployment_slot(pubkey)
+                } else {
+                    slot
+                };
+                program_account
+                    .data()
+                    .get(LoaderV4State::program_data_offset()..)
+                    .ok_or(Box::new(InstructionError::InvalidAccountData).into())
+                    .and_then(|elf_bytes| {
+                        Self::load_program_from_bytes(
+                            &mut load_program_metrics,
+                            elf_bytes,
+                            &loader_v4::id(),
+                            program_account.data().len(),
+                            slot,
+                            environments.program_runtime_v2.clone(),
+                            reload,
+                        )
+                    })
+                    .map_err(|_| (slot, environments.program_runtime_v2.clone()))
+            }
         }
         .unwrap_or_else(|(slot, env)| {
             LoadedProgram::new_tombstone(slot, LoadedProgramType::FailedVerification(env))
@@ -4818,6 +5070,7 @@ impl Bank {
 
         let lamports_before_tx =
             transaction_accounts_lamports_sum(&transaction_accounts, tx.message()).unwrap_or(0);
+        let remote_accounts = self.transaction_remote_accounts(&transaction_accounts);
 
         let mut transaction_context = TransactionContext::new(
             transaction_accounts,
@@ -4827,6 +5080,13 @@ impl Bank {
         );
         #[cfg(debug_assertions)]
         transaction_context.set_signature(tx.signature());
+        // Sonic: tell the programs where the remote accounts come from
+        if self
+            .feature_set
+            .is_active(&feature_set::remote_account_meta_syscall_enabled::id())
+        {
+            self.set_remote_account_metas(&mut transaction_context);
+        }
 
         let pre_account_state_info =
             self.get_transaction_account_state_info(&transaction_context, tx.message());
@@ -4958,6 +5218,7 @@ impl Bank {
                 return_data,
                 executed_units,
                 accounts_data_len_delta,
+                remote_accounts,
             },
             programs_modified_by_tx: Box::new(programs_modified_by_tx),
         }
@@ -5055,53 +5316,154 @@ impl Bank {
         loaded_programs_for_txs.unwrap()
     }
 
-    ///Sonic: check remote accounts in transaction
-    fn check_remote_accounts(&self, tx: &SanitizedTransaction) {
+    ///Sonic: load the accounts migrated by the transaction from the remote.
+    ///
+    ///When producing a block (`retry_until_loaded` unset), fails with `RemoteAccountUnavailable`
+    ///if one of them cannot be loaded in time, which keeps the transaction out of the block. When
+    ///replaying, the leader did load them, so a failure is most likely local to this node: the
+    ///loads are retried for `REMOTE_LOAD_MAX_RETRY_SLOTS` slots before failing the same way,
+    ///which marks the replayed slot dead rather than hanging the replay.
+    fn check_remote_accounts(&self, tx: &SanitizedTransaction, retry_until_loaded: bool) -> Result<()> {
         if tx.is_simple_vote_transaction() {
-            return;
+            return Ok(());
         }
         let msg = tx.message();
         let account_keys = msg.account_keys();
+        let accounts_cache = &self.rc.accounts.accounts_db.accounts_cache;
         // println!("Bank.check_remote_accounts():{:?}", msg.instructions());
-        msg.instructions().iter().for_each(|ix| {
-            if let Some(program_id) = account_keys.get(ix.program_id_index.into()) {
-
-                if !sonic_account_migrater_program::check_id(program_id) { 
-                    return;
+        for ix in msg.instructions() {
+            let Some(program_id) = account_keys.get(ix.program_id_index.into()) else {
+                continue;
+            };
+            if !sonic_account_migrater_program::check_id(program_id) {
+                continue;
+            }
+            let Ok(instruction) = limited_deserialize(&ix.data) else {
+                continue;
+            };
+            for account_index in &ix.accounts {
+                if msg.is_signer((*account_index).into()) || *account_index == ix.program_id_index {
+                    continue;
                 }
-                match limited_deserialize(&ix.data) {
-                    Err(_) => {
-                        return;
-                    },
-                    Ok(instruction) => {
-                        ix.accounts.iter().for_each(|account_index| {
-                            if msg.is_signer((*account_index).into()) || *account_index == ix.program_id_index {
-                                return;
-                            }
-                            let mut accounts:Vec<Pubkey> = vec![];
-                            if let Some(account_key) = account_keys.get((*account_index).into()) {
-                                accounts.push(*account_key);
-                            }
-                            match instruction {
-                                sonic_account_migrater_program::instruction::ProgramInstruction::MigrateRemoteAccounts => {
-                                    //load remote account...
-                                    self.rc.accounts.accounts_db.accounts_cache.load_accounts_from_remote(accounts, None, false);
-                                },
-                                sonic_account_migrater_program::instruction::ProgramInstruction::DeactivateRemoteAccounts => {
-                                    //deactivate remote account...
-                                    self.rc.accounts.accounts_db.accounts_cache.deactivate_remote_accounts(accounts);
-                                },
-                                sonic_account_migrater_program::instruction::ProgramInstruction::MigrateSourceAccounts { node_id, refresh } => {
-                                    //load remote account from source...
-                                    info!("Bank.check_remote_accounts():MigrateSourceAccounts node_id: {:?} refresh: {:?}", node_id, refresh);
-                                    self.rc.accounts.accounts_db.accounts_cache.load_accounts_from_remote(accounts, Some(node_id), refresh);
-                                },
-                            }
+                let mut accounts: Vec<Pubkey> = vec![];
+                if let Some(account_key) = account_keys.get((*account_index).into()) {
+                    accounts.push(*account_key);
+                }
+                let (source, refresh) = match instruction {
+                    sonic_account_migrater_program::instruction::ProgramInstruction::MigrateRemoteAccounts => {
+                        //load remote account...
+                        (None, false)
+                    }
+                    sonic_account_migrater_program::instruction::ProgramInstruction::DeactivateRemoteAccounts => {
+                        //deactivate remote account...
+                        accounts_cache.deactivate_remote_accounts(accounts);
+                        continue;
+                    }
+                    sonic_account_migrater_program::instruction::ProgramInstruction::MigrateSourceAccounts { node_id, refresh } => {
+                        //load remote account from source...
+                        info!("Bank.check_remote_accounts():MigrateSourceAccounts node_id: {:?} refresh: {:?}", node_id, refresh);
+                        (Some(node_id), refresh)
+                    }
+                };
+                let mut retry_interval = REMOTE_LOAD_MIN_RETRY_INTERVAL;
+                let retry_deadline = Instant::now()
+                    + Duration::from_nanos(self.ns_per_slot as u64) * REMOTE_LOAD_MAX_RETRY_SLOTS;
+                loop {
+                    // The loads of a transaction must not hold up the slot
+                    let deadline = Instant::now() + Duration::from_nanos(self.ns_per_slot as u64);
+                    let Err(err) = accounts_cache.load_accounts_from_remote(accounts.clone(), source, refresh, deadline) else {
+                        break;
+                    };
+                    if !retry_until_loaded || Instant::now() >= retry_deadline {
+                        warn!("Bank.check_remote_accounts(): {} failed: {}", tx.signature(), err);
+                        return Err(TransactionError::RemoteAccountUnavailable {
+                            account_index: *account_index,
+                            reason: (&err).into(),
                         });
-                    },
+                    }
+                    warn!(
+                        "Bank.check_remote_accounts(): {} failed while replaying slot {}, retrying in {:?}: {}",
+                        tx.signature(), self.slot, retry_interval, err
+                    );
+                    datapoint_warn!(
+                        "sonic-remote-replay-load-retry",
+                        ("slot", self.slot, i64),
+                        ("pubkey", accounts[0].to_string(), String),
+                    );
+                    sleep(retry_interval);
+                    retry_interval = (retry_interval * 2).min(REMOTE_LOAD_MAX_RETRY_INTERVAL);
                 }
+                self.update_remote_programs(&accounts);
             }
-        });
+        }
+        Ok(())
+    }
+
+    ///Sonic: redeploy the migrated remote programs whose version changed on this fork. Like a
+    ///local upgrade, the new executable is effective from the next slot.
+    fn update_remote_programs(&self, pubkeys: &[Pubkey]) {
+        let remote_loader = &self.rc.accounts.accounts_db.accounts_cache.remote_loader;
+        for pubkey in pubkeys {
+            let Some((programdata_slot, data_hash)) = remote_loader.cached_program_version(pubkey)
+            else {
+                continue;
+            };
+            let mut remote_programs = self.remote_programs.write().unwrap();
+            let deployment_slot = match remote_programs.get(pubkey) {
+                // The first version of a program is deployed at slot 0
+                None => 0,
+                Some(version)
+                    if version.programdata_slot == programdata_slot
+                        && version.data_hash == data_hash =>
+                {
+                    continue;
+                }
+                Some(_) => self.slot,
+            };
+            remote_programs.insert(
+                *pubkey,
+                RemoteProgramVersion {
+                    programdata_slot,
+                    data_hash,
+                    deployment_slot,
+                },
+            );
+            drop(remote_programs);
+            if deployment_slot == 0 {
+                continue;
+            }
+            info!(
+                "Bank.update_remote_programs(): {} redeployed at slot {}, source slot {}",
+                pubkey, deployment_slot, programdata_slot
+            );
+            if let Some(program) = self.load_program(pubkey, false, None) {
+                // Entries are keyed by deployment slot, so the redeployment only shadows the
+                // previous version on the forks descending from this bank.
+                let mut loaded_programs_cache = self.loaded_programs_cache.write().unwrap();
+                let mut programs_modified = LoadedProgramsForTxBatch::new_from_cache(
+                    self.slot,
+                    self.epoch,
+                    &loaded_programs_cache,
+                );
+                programs_modified.replenish(*pubkey, program);
+                loaded_programs_cache.merge(&programs_modified);
+            }
+        }
+    }
+
+    ///Sonic: remote programs are deployed at slot 0, or at the slot they were last refreshed at
+    ///on this fork.
+    fn remote_program_deployment_slot(&self, pubkey: &Pubkey) -> Slot {
+        self.remote_programs
+            .read()
+            .unwrap()
+            .get(pubkey)
+            .map_or(0, |version| version.deployment_slot)
+    }
+
+    ///Sonic: the version of the remote program `pubkey` migrated on this fork, if any.
+    pub fn remote_program_version(&self, pubkey: &Pubkey) -> Option<RemoteProgramVersion> {
+        self.remote_programs.read().unwrap().get(pubkey).copied()
     }
 
     /// Returns a hash map of executable program accounts (program accounts that are not writable
@@ -5114,6 +5476,7 @@ impl Bank {
         lock_results: &mut [TransactionCheckResult],
         program_owners: &'a [Pubkey],
         hash_queue: &BlockhashQueue,
+        retry_remote_loads: bool,
     ) -> HashMap<Pubkey, (&'a Pubkey, u64)> {
         let mut result: HashMap<Pubkey, (&'a Pubkey, u64)> = HashMap::new();
         // println!("Bank.filter_executable_program_accounts(): {:?}\n{:?}\n{:?}", txs, ancestors, lock_results);
@@ -5127,7 +5490,11 @@ impl Bank {
                     })
                     .is_some()
                 {
-                    self.check_remote_accounts(tx); //Socnic: check remote accounts
+                    //Sonic: check remote accounts
+                    if let Err(err) = self.check_remote_accounts(tx, retry_remote_loads) {
+                        *etx.0 = (Err(err), None);
+                        return;
+                    }
                     tx.message()
                         .account_keys()
                         .iter()
@@ -5228,6 +5595,8 @@ impl Bank {
             &mut check_results,
             PROGRAM_OWNERS,
             &self.blockhash_queue.read().unwrap(),
+            // Only the leader may leave transactions out, replay must load what it loaded
+            !limit_to_load_programs,
         );
         let native_loader = native_loader::id();
         for builtin_program in self.builtin_programs.iter() {
@@ -5553,6 +5922,7 @@ impl Bank {
     ) -> Vec<Result<()>> {
         let hash_queue = self.blockhash_queue.read().unwrap();
         let mut fees = 0;
+        let mut fee_details = CollectedFeeDetails::default();
 
         let results = txs
             .iter()
@@ -5594,11 +5964,13 @@ impl Bank {
                 }
 
                 fees += fee;
+                fee_details.record(tx.message(), fee);
                 Ok(())
             })
             .collect();
 
         self.collector_fees.fetch_add(fees, Relaxed);
+        self.collected_fee_details.write().unwrap().merge(fee_details);
         results
     }
 
@@ -5633,6 +6005,12 @@ impl Bank {
             committed_non_vote_transactions_count,
         );
         self.increment_signature_count(signature_count);
+        let executed_units = execution_results
+            .iter()
+            .filter_map(|result| result.details())
+            .map(|details| details.executed_units)
+            .sum::<u64>();
+        self.executed_compute_units.fetch_add(executed_units, Relaxed);
 
         if committed_with_failure_result_count > 0 {
             self.transaction_error_count
@@ -6695,6 +7073,9 @@ impl Bank {
     ) {
         self.rewards_pool_pubkeys =
             Arc::new(genesis_config.rewards_pools.keys().cloned().collect());
+        self.apply_fee_multiplier();
+        self.apply_genesis_clone_record();
+        self.apply_sequencer_mode();
 
         self.apply_feature_activations(
             ApplyFeatureActivationsCaller::FinishInit,
@@ -6702,10 +7083,15 @@ impl Bank {
         );
 
         if !debug_do_not_add_builtins {
+            let sequencer_mode = self.is_sequencer_mode();
             for builtin in BUILTINS
                 .iter()
                 .chain(additional_builtins.unwrap_or(&[]).iter())
             {
+                // Sonic: sequencer grids root without votes
+                if sequencer_mode && builtin.program_id == solana_vote_program::id() {
+                    continue;
+                }
                 if builtin.feature_id.is_none() {
                     self.add_builtin(
                         builtin.program_id,
@@ -8174,6 +8560,15 @@ impl Bank {
             .then_some(&self.epoch_reward_status)
     }
 
+    /// Sonic: return the versions of the remote programs to serialize.
+    /// Returns none if no remote program was migrated on this fork.
+    pub(crate) fn get_remote_programs_to_serialize(
+        &self,
+    ) -> Option<HashMap<Pubkey, RemoteProgramVersion>> {
+        let remote_programs = self.remote_programs.read().unwrap();
+        (!remote_programs.is_empty()).then(|| remote_programs.clone())
+    }
+
     /// Convenience fn to get the Epoch Accounts Hash
     pub fn epoch_accounts_hash(&self) -> Option<EpochAccountsHash> {
         self.rc
@@ -1,19 +1,74 @@
 use {
     super::Bank,
     crate::accounts::account_rent_state::RentState,
+    crossbeam_channel::Sender,
     log::{debug, warn},
     solana_accounts_db::stake_rewards::RewardInfo,
     solana_sdk::{
         account::{ReadableAccount, WritableAccount},
+        clock::Slot,
+        compute_budget,
+        message::SanitizedMessage,
         pubkey::Pubkey,
         reward_type::RewardType,
         system_program,
     },
     solana_vote::vote_account::VoteAccountsHashMap,
-    std::{result::Result, sync::atomic::Ordering::Relaxed},
+    std::{collections::HashMap, result::Result, sync::atomic::Ordering::Relaxed},
     thiserror::Error,
 };
 
+/// Sonic: transaction fees collected by a bank, by fee payer and by program
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CollectedFeeDetails {
+    pub by_fee_payer: HashMap<Pubkey, u64>,
+    /// Fees are attributed to the first program invoked by a transaction,
+    /// not counting the compute budget program.
+    pub by_program: HashMap<Pubkey, u64>,
+}
+
+impl CollectedFeeDetails {
+    pub(super) fn record(&mut self, message: &SanitizedMessage, fee: u64) {
+        if fee == 0 {
+            return;
+        }
+        let fee_payer = self.by_fee_payer.entry(*message.fee_payer()).or_default();
+        *fee_payer = fee_payer.saturating_add(fee);
+        if let Some((program_id, _)) = message
+            .program_instructions_iter()
+            .find(|(program_id, _)| !compute_budget::check_id(program_id))
+        {
+            let program = self.by_program.entry(*program_id).or_default();
+            *program = program.saturating_add(fee);
+        }
+    }
+
+    pub(super) fn merge(&mut self, other: CollectedFeeDetails) {
+        for (pubkey, fee) in other.by_fee_payer {
+            let entry = self.by_fee_payer.entry(pubkey).or_default();
+            *entry = entry.saturating_add(fee);
+        }
+        for (pubkey, fee) in other.by_program {
+            let entry = self.by_program.entry(pubkey).or_default();
+            *entry = entry.saturating_add(fee);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_fee_payer.is_empty() && self.by_program.is_empty()
+    }
+}
+
+/// Sonic: the collected fee details of a frozen bank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotFeeDetails {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub details: CollectedFeeDetails,
+}
+
+pub type CollectedFeeDetailsSender = Sender<SlotFeeDetails>;
+
 #[derive(Debug)]
 struct DepositFeeOptions {
     check_account_owner: bool,
@@ -87,6 +142,22 @@ impl Bank {
             }
             self.capitalization.fetch_sub(burn, Relaxed);
         }
+        self.send_collected_fee_details();
+    }
+
+    // Sonic: hand the fees collected in this slot to the fee bill generator, if any.
+    // Empty slots are sent as well so the receiver can follow the fork structure.
+    fn send_collected_fee_details(&self) {
+        if let Some(sender) = self.collected_fee_details_sender.read().unwrap().as_ref() {
+            let slot_fee_details = SlotFeeDetails {
+                slot: self.slot(),
+                parent_slot: self.parent_slot(),
+                details: self.collected_fee_details(),
+            };
+            if let Err(err) = sender.send(slot_fee_details) {
+                warn!("collected_fee_details_sender failed: {:?}", err);
+            }
+        }
     }
 
     // Deposits fees into a specified account and if successful, returns the new balance of that account
@@ -818,4 +889,38 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_send_collected_fee_details() {
+        let genesis = create_genesis_config(0);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        bank.set_collected_fee_details_sender(Some(sender));
+
+        let fee_payer = solana_sdk::pubkey::new_rand();
+        let program_id = solana_sdk::pubkey::new_rand();
+        let mut details = CollectedFeeDetails::default();
+        details.by_fee_payer.insert(fee_payer, 10);
+        details.by_program.insert(program_id, 10);
+        bank.collected_fee_details
+            .write()
+            .unwrap()
+            .merge(details.clone());
+        bank.collected_fee_details.write().unwrap().merge(details);
+        bank.collector_fees.fetch_add(20, Relaxed);
+        bank.freeze();
+
+        let slot_fee_details = receiver.try_recv().unwrap();
+        assert_eq!(slot_fee_details.slot, bank.slot());
+        assert_eq!(slot_fee_details.details.by_fee_payer[&fee_payer], 20);
+        assert_eq!(slot_fee_details.details.by_program[&program_id], 20);
+
+        // Child banks inherit the sender
+        let child = Bank::new_from_parent(std::sync::Arc::new(bank), &Pubkey::default(), 1);
+        child.freeze();
+        let slot_fee_details = receiver.try_recv().unwrap();
+        assert_eq!(slot_fee_details.slot, 1);
+        assert_eq!(slot_fee_details.parent_slot, 0);
+        assert!(slot_fee_details.details.is_empty());
+    }
 }
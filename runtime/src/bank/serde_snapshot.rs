@@ -39,6 +39,7 @@ mod tests {
             pubkey::Pubkey,
             signature::{Keypair, Signer},
         },
+        sonic_hypergrid::remote_loader::RemoteProgramVersion,
         std::{
             io::{Cursor, Read, Write},
             num::NonZeroUsize,
@@ -432,6 +433,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remote_programs_extra_field() {
+        solana_logger::setup();
+        for epoch_reward_status_active in [None, Some(vec![])] {
+            let (genesis_config, _) = create_genesis_config(500);
+
+            let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+            bank0.squash();
+            let mut bank = Bank::new_from_parent(bank0.clone(), &Pubkey::default(), 1);
+
+            add_root_and_flush_write_cache(&bank0);
+            bank.rc
+                .accounts
+                .accounts_db
+                .set_accounts_delta_hash(bank.slot(), AccountsDeltaHash(Hash::new_unique()));
+            bank.rc.accounts.accounts_db.set_accounts_hash(
+                bank.slot(),
+                (AccountsHash(Hash::new_unique()), u64::default()),
+            );
+
+            // A remote program refreshed at slot 1
+            let program_id = Pubkey::new_unique();
+            let version = RemoteProgramVersion {
+                programdata_slot: 42,
+                data_hash: Hash::new_unique(),
+                deployment_slot: 1,
+            };
+            bank.remote_programs
+                .write()
+                .unwrap()
+                .insert(program_id, version);
+            if let Some(rewards) = epoch_reward_status_active.as_ref() {
+                bank.set_epoch_reward_status_active(rewards.clone());
+            }
+
+            // Serialize
+            let snapshot_storages = bank.get_snapshot_storages(None);
+            let mut buf = vec![];
+            let mut writer = Cursor::new(&mut buf);
+
+            crate::serde_snapshot::bank_to_stream(
+                SerdeStyle::Newer,
+                &mut std::io::BufWriter::new(&mut writer),
+                &bank,
+                &get_storages_to_serialize(&snapshot_storages),
+            )
+            .unwrap();
+
+            // Deserialize
+            let rdr = Cursor::new(&buf[..]);
+            let mut reader = std::io::BufReader::new(&buf[rdr.position() as usize..]);
+            let mut snapshot_streams = SnapshotStreams {
+                full_snapshot_stream: &mut reader,
+                incremental_snapshot_stream: None,
+            };
+            let (_accounts_dir, dbank_paths) = get_temp_accounts_paths(4).unwrap();
+            let copied_accounts = TempDir::new().unwrap();
+            let storage_and_next_append_vec_id =
+                copy_append_vecs(&bank.rc.accounts.accounts_db, copied_accounts.path()).unwrap();
+            let dbank = crate::serde_snapshot::bank_from_streams(
+                SerdeStyle::Newer,
+                &mut snapshot_streams,
+                &dbank_paths,
+                storage_and_next_append_vec_id,
+                &genesis_config,
+                &RuntimeConfig::default(),
+                None,
+                None,
+                AccountSecondaryIndexes::default(),
+                None,
+                AccountShrinkThreshold::default(),
+                false,
+                Some(solana_accounts_db::accounts_db::ACCOUNTS_DB_CONFIG_FOR_TESTING),
+                None,
+                Arc::default(),
+            )
+            .unwrap();
+
+            assert_eq!(dbank.remote_program_version(&program_id), Some(version));
+            assert_eq!(dbank.remote_program_deployment_slot(&program_id), 1);
+            assert_eq!(
+                dbank.get_epoch_reward_status_to_serialize().is_some(),
+                epoch_reward_status_active.is_some()
+            );
+        }
+    }
+
     #[test]
     fn test_extra_fields_full_snapshot_archive() {
         solana_logger::setup();
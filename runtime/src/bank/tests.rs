@@ -87,6 +87,7 @@ use {
         reward_type::RewardType,
         secp256k1_program,
         signature::{keypair_from_seed, Keypair, Signature, Signer},
+        sonic_account_migrater::instruction::migrate_remote_accounts,
         stake::{
             instruction as stake_instruction,
             state::{Authorized, Delegation, Lockup, Stake},
@@ -98,8 +99,8 @@ use {
         system_program, system_transaction, sysvar,
         timing::{duration_as_s, years_as_slots},
         transaction::{
-            Result, SanitizedTransaction, Transaction, TransactionError,
-            TransactionVerificationMode,
+            RemoteAccountUnavailableReason, Result, SanitizedTransaction, Transaction,
+            TransactionError, TransactionVerificationMode,
         },
         transaction_context::{TransactionAccount, TransactionContext},
     },
@@ -111,6 +112,7 @@ use {
         },
         vote_transaction,
     },
+    sonic_hypergrid::remote_source::StaticRemoteSource,
     std::{
         collections::{HashMap, HashSet},
         convert::{TryFrom, TryInto},
@@ -239,6 +241,7 @@ fn new_execution_result(
             return_data: None,
             executed_units: 0,
             accounts_data_len_delta: 0,
+            remote_accounts: vec![],
         },
         programs_modified_by_tx: Box::<LoadedProgramsForTxBatch>::default(),
     }
@@ -4204,6 +4207,629 @@ fn test_bank_inherit_fee_rate_governor() {
     );
 }
 
+fn add_fee_multiplier_config(genesis_config: &mut GenesisConfig, config: FeeMultiplierConfig) {
+    use solana_sdk::sonic_fee_settlement::state::FEE_MULTIPLIER_CONFIG_SIZE;
+
+    let mut account = AccountSharedData::new(
+        genesis_config
+            .rent
+            .minimum_balance(FEE_MULTIPLIER_CONFIG_SIZE),
+        FEE_MULTIPLIER_CONFIG_SIZE,
+        &sonic_fee_settlement::program::id(),
+    );
+    account
+        .serialize_data(&SettlementState::FeeMultiplier(config))
+        .unwrap();
+    genesis_config.add_account(derive_fee_multiplier_address().0, account);
+}
+
+#[test]
+fn test_bank_dynamic_fee_multiplier() {
+    use solana_sdk::sonic_fee_settlement::state::DynamicFeeMultiplier;
+
+    let (mut genesis_config, _mint_keypair) = create_genesis_config(500);
+    let config = FeeMultiplierConfig::new(
+        Pubkey::new_unique(),
+        20_000,
+        Some(DynamicFeeMultiplier {
+            min_multiplier: 15_000,
+            max_multiplier: 30_000,
+            target_utilization_percent: 50,
+        }),
+    );
+    add_fee_multiplier_config(&mut genesis_config, config);
+
+    // The multiplier only moves once governance is activated
+    let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert_eq!(bank0.fee_multiplier(), 20_000);
+    let bank1 = new_from_parent(bank0);
+    assert_eq!(bank1.fee_multiplier(), 20_000);
+
+    activate_feature(
+        &mut genesis_config,
+        feature_set::sonic_fee_multiplier_governance::id(),
+    );
+    let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert_eq!(bank0.fee_multiplier(), 20_000);
+
+    // Empty blocks lower the multiplier by 1/8 per slot, down to its minimum
+    let bank1 = Arc::new(new_from_parent(bank0));
+    assert_eq!(bank1.fee_multiplier(), 17_500);
+    let bank2 = Arc::new(new_from_parent(bank1));
+    assert_eq!(bank2.fee_multiplier(), 15_313);
+    let bank3 = new_from_parent(bank2);
+    assert_eq!(bank3.fee_multiplier(), 15_000);
+    assert_eq!(
+        bank3.fee_multiplier_config().unwrap().active_multiplier,
+        15_000
+    );
+}
+
+#[test]
+fn test_initialize_settlement_cursor_requires_authority() {
+    use solana_sdk::sonic_fee_settlement::{
+        instruction::initialize_settlement_cursor, state::SettlementCursor,
+    };
+
+    let (mut genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let authority = Keypair::new();
+    add_fee_multiplier_config(
+        &mut genesis_config,
+        FeeMultiplierConfig::new(authority.pubkey(), 10_000, None),
+    );
+    let bank = Bank::new_for_tests(&genesis_config);
+
+    // A settler appointing itself can't take over the cursor
+    let (instruction, cursor_address) =
+        initialize_settlement_cursor(mint_keypair.pubkey(), mint_keypair.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair],
+        bank.last_blockhash(),
+    );
+    assert_eq!(
+        bank.process_transaction(&tx),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectAuthority
+        ))
+    );
+    assert!(bank.get_account(&cursor_address).is_none());
+
+    let (instruction, cursor_address) =
+        initialize_settlement_cursor(mint_keypair.pubkey(), authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair, &authority],
+        bank.last_blockhash(),
+    );
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    let cursor = bank.get_account(&cursor_address).unwrap();
+    assert_eq!(
+        cursor.state(),
+        Ok(SettlementState::SettlementCursor(SettlementCursor::new(
+            mint_keypair.pubkey()
+        )))
+    );
+}
+
+#[test]
+fn test_withdraw_fee_bill() {
+    use solana_sdk::sonic_fee_settlement::{
+        instruction::withdraw_fee_bill,
+        state::{SettlementAccount, SettlementAccountType, SETTLEMENT_ACCOUNT_SIZE},
+    };
+
+    let (mut genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let owner = Keypair::new();
+    let settlement_address = Pubkey::new_unique();
+    let minimum_balance = genesis_config
+        .rent
+        .minimum_balance(SETTLEMENT_ACCOUNT_SIZE);
+    let mut account = AccountSharedData::new(
+        minimum_balance + 1_000,
+        SETTLEMENT_ACCOUNT_SIZE,
+        &sonic_fee_settlement::program::id(),
+    );
+    account
+        .serialize_data(&SettlementState::FeeBillSettled(SettlementAccount {
+            owner: owner.pubkey(),
+            account_type: SettlementAccountType::HSSNAccount,
+            amount: 600,
+            withdrawable: 600,
+            withdrawed: 0,
+        }))
+        .unwrap();
+    genesis_config.add_account(settlement_address, account);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let owner_balance = genesis_config.rent.minimum_balance(0);
+    bank.transfer(owner_balance, &mint_keypair, &owner.pubkey())
+        .unwrap();
+    let withdraw = |owner: &Keypair, amount| {
+        bank.process_transaction(&Transaction::new_signed_with_payer(
+            &[withdraw_fee_bill(
+                settlement_address,
+                owner.pubkey(),
+                amount,
+            )],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair, owner],
+            bank.last_blockhash(),
+        ))
+    };
+
+    assert_eq!(
+        withdraw(&Keypair::new(), 100),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectAuthority
+        ))
+    );
+    assert_eq!(
+        withdraw(&owner, 601),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InsufficientFunds
+        ))
+    );
+    assert_eq!(withdraw(&owner, 400), Ok(()));
+    assert_eq!(bank.get_balance(&owner.pubkey()), owner_balance + 400);
+    assert_eq!(bank.get_balance(&settlement_address), minimum_balance + 600);
+    assert_eq!(
+        bank.get_account(&settlement_address).unwrap().state(),
+        Ok(SettlementState::FeeBillSettled(SettlementAccount {
+            owner: owner.pubkey(),
+            account_type: SettlementAccountType::HSSNAccount,
+            amount: 600,
+            withdrawable: 200,
+            withdrawed: 400,
+        }))
+    );
+}
+
+#[test]
+fn test_bank_genesis_clone_record() {
+    use solana_sdk::sonic_account_migrater::state::{CloneSource, ClonedAccount};
+
+    let (mut genesis_config, _mint_keypair) = create_genesis_config(500);
+    let cloned = Keypair::new().pubkey();
+    genesis_config.add_account(cloned, AccountSharedData::new(42, 0, &Pubkey::new_unique()));
+    let record = GenesisCloneRecord {
+        source: CloneSource::BaseLayer,
+        url: "http://127.0.0.1:8899".to_string(),
+        slot: 100,
+        accounts: vec![ClonedAccount {
+            pubkey: cloned,
+            source_slot: Some(105),
+        }],
+    };
+    let data = bincode::serialize(&record).unwrap();
+    let mut account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(data.len()),
+        data.len(),
+        &sonic_account_migrater_program::id(),
+    );
+    account.set_data_from_slice(&data);
+    genesis_config.add_account(derive_genesis_clone_record_address().0, account);
+
+    let bank = Bank::new_for_tests(&genesis_config);
+    assert_eq!(bank.genesis_clone_record(), Some(record));
+    let remote_loader = &bank.rc.accounts.accounts_db.accounts_cache.remote_loader;
+    assert_eq!(
+        remote_loader.get_remote_origin(&cloned),
+        Some(RemoteOrigin {
+            source: None,
+            source_slot: Some(105),
+            namespace: RemoteNamespace::BaseLayer,
+        })
+    );
+    // Cloned accounts are part of the local state, not served from the remote cache
+    assert!(!remote_loader.has_account(&cloned));
+    assert_eq!(bank.get_balance(&cloned), 42);
+}
+
+#[test]
+fn test_bank_genesis_cloned_program() {
+    let (mut genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let elf = include_bytes!("../../../programs/bpf_loader/test_elfs/out/noop_aligned.so");
+    let program_id = Keypair::new().pubkey();
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let mut program_account = AccountSharedData::new_data(
+        genesis_config
+            .rent
+            .minimum_balance(UpgradeableLoaderState::size_of_program()),
+        &UpgradeableLoaderState::Program {
+            programdata_address,
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    program_account.set_executable(true);
+    let programdata_data_offset = UpgradeableLoaderState::size_of_programdata_metadata();
+    let mut programdata_account = AccountSharedData::new(
+        genesis_config
+            .rent
+            .minimum_balance(programdata_data_offset + elf.len()),
+        programdata_data_offset + elf.len(),
+        &bpf_loader_upgradeable::id(),
+    );
+    // Deployed at a base layer slot the grid is far from reaching
+    programdata_account
+        .set_state(&UpgradeableLoaderState::ProgramData {
+            slot: 250_000_000,
+            upgrade_authority_address: None,
+        })
+        .unwrap();
+    programdata_account.data_as_mut_slice()[programdata_data_offset..].copy_from_slice(elf);
+    sonic_hypergrid::genesis_clone::reset_programdata_slot(
+        &programdata_address,
+        &mut programdata_account,
+    )
+    .unwrap();
+    genesis_config.add_account(program_id, program_account);
+    genesis_config.add_account(programdata_address, programdata_account);
+
+    let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    goto_end_of_slot(bank0.clone());
+    let bank1 = new_from_parent_with_fork_next_slot(bank0, bank_forks.as_ref());
+    assert_eq!(bank1.slot(), 1);
+
+    let instruction = Instruction::new_with_bytes(program_id, &[], Vec::new());
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair],
+        bank1.last_blockhash(),
+    );
+    assert_eq!(bank1.process_transaction(&transaction), Ok(()));
+}
+
+#[test]
+fn test_bank_sequencer_mode() {
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(500, &solana_sdk::pubkey::new_rand(), 1);
+    let sequencer = Pubkey::new_unique();
+    genesis_utils::activate_sequencer_mode(&mut genesis_config, sequencer);
+    let bank = Bank::new_for_tests(&genesis_config);
+    assert_eq!(bank.sequencer_config(), Some(SequencerConfig { sequencer }));
+    assert!(bank.is_sequencer_mode());
+
+    // No vote can land without the vote program
+    assert!(bank.get_account(&solana_vote_program::id()).is_none());
+    let vote_keypair = Keypair::new();
+    let instructions = vote_instruction::create_account_with_config(
+        &mint_keypair.pubkey(),
+        &vote_keypair.pubkey(),
+        &VoteInit {
+            node_pubkey: mint_keypair.pubkey(),
+            authorized_voter: vote_keypair.pubkey(),
+            authorized_withdrawer: vote_keypair.pubkey(),
+            commission: 0,
+        },
+        10,
+        vote_instruction::CreateVoteAccountConfig {
+            space: VoteStateVersions::vote_state_size_of(true) as u64,
+            ..vote_instruction::CreateVoteAccountConfig::default()
+        },
+    );
+    let message = Message::new(&instructions, Some(&mint_keypair.pubkey()));
+    let transaction = Transaction::new(
+        &[&mint_keypair, &vote_keypair],
+        message,
+        bank.last_blockhash(),
+    );
+    assert!(bank.process_transaction(&transaction).is_err());
+    assert!(bank.get_account(&vote_keypair.pubkey()).is_none());
+
+    let bank = new_from_parent(Arc::new(bank));
+    assert_eq!(bank.sequencer_config(), Some(SequencerConfig { sequencer }));
+    assert!(bank.is_sequencer_mode());
+}
+
+#[test]
+fn test_bank_set_remote_account_metas() {
+    use solana_sdk::sonic_remote_account::{RemoteAccountMeta, RemoteNamespace as MetaNamespace};
+
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (local, cloned, mirror) = (
+        Keypair::new().pubkey(),
+        Keypair::new().pubkey(),
+        Keypair::new().pubkey(),
+    );
+    let remote_loader = &bank.rc.accounts.accounts_db.accounts_cache.remote_loader;
+    remote_loader.set_genesis_origin(
+        cloned,
+        RemoteOrigin {
+            source: None,
+            source_slot: Some(105),
+            namespace: RemoteNamespace::BaseLayer,
+        },
+    );
+    let mut mirror_account = AccountSharedData::new(42, 0, &Pubkey::new_unique());
+    mirror_account.remote = true;
+    let mut transaction_context = TransactionContext::new(
+        vec![
+            (local, AccountSharedData::new(42, 0, &Pubkey::new_unique())),
+            (cloned, AccountSharedData::new(42, 0, &Pubkey::new_unique())),
+            (mirror, mirror_account),
+        ],
+        Rent::default(),
+        1,
+        1,
+    );
+    bank.set_remote_account_metas(&mut transaction_context);
+
+    assert_eq!(
+        transaction_context.get_remote_account_meta(0).unwrap(),
+        RemoteAccountMeta::default()
+    );
+    // Cloned accounts keep their origin, but are not remote
+    assert_eq!(
+        transaction_context.get_remote_account_meta(1).unwrap(),
+        RemoteAccountMeta {
+            remote: false,
+            namespace: MetaNamespace::BaseLayer,
+            source: Pubkey::default(),
+            source_slot: 105,
+        }
+    );
+    // Remote accounts of unknown origin are the synthetic copies of the base layer accounts
+    assert_eq!(
+        transaction_context.get_remote_account_meta(2).unwrap(),
+        RemoteAccountMeta {
+            remote: true,
+            namespace: MetaNamespace::BaseLayer,
+            source: Pubkey::default(),
+            source_slot: 0,
+        }
+    );
+}
+
+/// Add the bridge config, a vault holding 5 SOL and the outbox to genesis, returning the
+/// lamports of the vault.
+fn add_bridge_accounts(genesis_config: &mut GenesisConfig, authority: Pubkey) -> u64 {
+    use solana_sdk::sonic_bridge::{
+        self,
+        state::{
+            derive_bridge_config_address, derive_bridge_vault_address, derive_outbox_address,
+            BridgeConfig, BridgeState, Outbox, BRIDGE_CONFIG_SIZE, OUTBOX_SIZE,
+        },
+    };
+
+    let mut config_account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(BRIDGE_CONFIG_SIZE),
+        BRIDGE_CONFIG_SIZE,
+        &sonic_bridge::program::id(),
+    );
+    config_account
+        .serialize_data(&BridgeState::Config(BridgeConfig {
+            authority,
+            deposit_count: 0,
+            deposited_lamports: 0,
+        }))
+        .unwrap();
+    genesis_config.add_account(derive_bridge_config_address().0, config_account);
+    let vault_lamports = genesis_config.rent.minimum_balance(0) + 5 * LAMPORTS_PER_SOL;
+    genesis_config.add_account(
+        derive_bridge_vault_address().0,
+        AccountSharedData::new(vault_lamports, 0, &sonic_bridge::program::id()),
+    );
+    let mut outbox_account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(OUTBOX_SIZE),
+        OUTBOX_SIZE,
+        &sonic_bridge::program::id(),
+    );
+    outbox_account
+        .serialize_data(&BridgeState::Outbox(Outbox { next_message_id: 0 }))
+        .unwrap();
+    genesis_config.add_account(derive_outbox_address().0, outbox_account);
+    vault_lamports
+}
+
+#[test]
+fn test_bank_bridge_credit_deposit() {
+    use solana_sdk::sonic_bridge::{
+        instruction::credit_deposit,
+        state::{
+            derive_bridge_config_address, derive_bridge_vault_address,
+            derive_deposit_record_address, BridgeConfig, BridgeState, L1Signature,
+        },
+    };
+
+    let (mut genesis_config, mint_keypair) = create_genesis_config(10 * LAMPORTS_PER_SOL);
+    let authority = mint_keypair.pubkey();
+    let vault_lamports = add_bridge_accounts(&mut genesis_config, authority);
+
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let recipient = Keypair::new().pubkey();
+    let l1_signature = L1Signature::new([7; 64]);
+    let credit = |signer: &Keypair, event_index, amount| {
+        let instruction =
+            credit_deposit(signer.pubkey(), recipient, l1_signature, event_index, amount);
+        bank.process_transaction(&Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            bank.last_blockhash(),
+        ))
+    };
+
+    assert_eq!(credit(&mint_keypair, 0, LAMPORTS_PER_SOL), Ok(()));
+    assert_eq!(bank.get_balance(&recipient), LAMPORTS_PER_SOL);
+    let (record_address, _) = derive_deposit_record_address(&l1_signature, 0);
+    match bincode::deserialize(bank.get_account(&record_address).unwrap().data()).unwrap() {
+        BridgeState::Deposit(record) => {
+            assert_eq!(record.recipient, recipient);
+            assert_eq!(record.amount, LAMPORTS_PER_SOL);
+        }
+        state => panic!("unexpected deposit record {state:?}"),
+    }
+
+    // A deposit can't be credited twice, even with another amount
+    assert_eq!(
+        credit(&mint_keypair, 0, 2 * LAMPORTS_PER_SOL),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::AccountAlreadyInitialized
+        ))
+    );
+    assert_eq!(credit(&mint_keypair, 1, LAMPORTS_PER_SOL), Ok(()));
+    assert_eq!(bank.get_balance(&recipient), 2 * LAMPORTS_PER_SOL);
+    assert_eq!(
+        bank.get_balance(&derive_bridge_vault_address().0),
+        vault_lamports - 2 * LAMPORTS_PER_SOL
+    );
+
+    // Only the bridge authority credits deposits
+    let other = Keypair::new();
+    bank.transfer(LAMPORTS_PER_SOL, &mint_keypair, &other.pubkey())
+        .unwrap();
+    assert_eq!(
+        credit(&other, 2, LAMPORTS_PER_SOL),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectAuthority
+        ))
+    );
+
+    let config = bank
+        .get_account(&derive_bridge_config_address().0)
+        .unwrap();
+    assert_eq!(
+        bincode::deserialize::<BridgeState>(config.data()).unwrap(),
+        BridgeState::Config(BridgeConfig {
+            authority,
+            deposit_count: 2,
+            deposited_lamports: 2 * LAMPORTS_PER_SOL,
+        })
+    );
+}
+
+#[test]
+fn test_bank_bridge_outbox() {
+    use solana_sdk::sonic_bridge::{
+        instruction::{send_message, write_receipt},
+        state::{
+            derive_bridge_vault_address, derive_outbox_message_address, BridgeState,
+            L1AccountMeta, L1Instruction, L1Signature, OutboxMessage, OutboxPayload,
+            OutboxReceipt,
+        },
+    };
+
+    let (mut genesis_config, mint_keypair) = create_genesis_config(10 * LAMPORTS_PER_SOL);
+    let authority = Keypair::new();
+    let vault_lamports = add_bridge_accounts(&mut genesis_config, authority.pubkey());
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    bank.transfer(LAMPORTS_PER_SOL, &mint_keypair, &authority.pubkey())
+        .unwrap();
+    let process = |instruction, signer: &Keypair| {
+        bank.process_transaction(&Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            bank.last_blockhash(),
+        ))
+    };
+    let message = |message_id| {
+        let account = bank
+            .get_account(&derive_outbox_message_address(message_id).0)
+            .unwrap();
+        match bincode::deserialize(account.data()).unwrap() {
+            BridgeState::Message(message) => message,
+            state => panic!("unexpected message {state:?}"),
+        }
+    };
+
+    let withdraw = OutboxPayload::Withdraw {
+        recipient: Pubkey::new_unique(),
+        amount: LAMPORTS_PER_SOL,
+    };
+    let sender = mint_keypair.pubkey();
+    assert_eq!(
+        process(send_message(sender, 0, withdraw.clone()), &mint_keypair),
+        Ok(())
+    );
+    assert_eq!(
+        bank.get_balance(&derive_bridge_vault_address().0),
+        vault_lamports + LAMPORTS_PER_SOL
+    );
+    assert_eq!(
+        message(0),
+        OutboxMessage {
+            id: 0,
+            sender,
+            payload: withdraw.clone(),
+            slot: bank.slot(),
+            receipt: None,
+        }
+    );
+
+    // Messages take the next id of the outbox
+    let call = OutboxPayload::Call(L1Instruction {
+        program_id: Pubkey::new_unique(),
+        accounts: vec![L1AccountMeta {
+            pubkey: Pubkey::new_unique(),
+            is_writable: false,
+        }],
+        data: vec![1, 2, 3],
+    });
+    for message_id in [0, 2] {
+        assert_eq!(
+            process(send_message(sender, message_id, call.clone()), &mint_keypair),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidArgument
+            ))
+        );
+    }
+    assert_eq!(
+        process(send_message(sender, 1, call.clone()), &mint_keypair),
+        Ok(())
+    );
+    assert_eq!(message(1).payload, call);
+
+    // Receipts are written once, by the bridge authority
+    let receipt = OutboxReceipt {
+        l1_signature: Some(L1Signature::new([9; 64])),
+        l1_slot: 42,
+        success: true,
+    };
+    assert_eq!(
+        process(write_receipt(sender, 0, receipt), &mint_keypair),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectAuthority
+        ))
+    );
+    assert_eq!(
+        process(write_receipt(authority.pubkey(), 0, receipt), &authority),
+        Ok(())
+    );
+    assert_eq!(message(0).receipt, Some(receipt));
+    let other_receipt = OutboxReceipt {
+        success: false,
+        ..receipt
+    };
+    assert_eq!(
+        process(
+            write_receipt(authority.pubkey(), 0, other_receipt),
+            &authority
+        ),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::AccountAlreadyInitialized
+        ))
+    );
+    assert_eq!(message(1).receipt, None);
+}
+
 #[test]
 fn test_bank_vote_accounts() {
     let GenesisConfigInfo {
@@ -7179,6 +7805,256 @@ fn test_bank_load_program() {
     );
 }
 
+#[test]
+fn test_refresh_remote_program() {
+    solana_logger::setup();
+
+    let (genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let remote_source = Arc::new(StaticRemoteSource::new());
+    // Remote accounts are shared by all the banks of the accounts db
+    let accounts_db = bank.rc.accounts.accounts_db.clone();
+    let remote_loader = &accounts_db.accounts_cache.remote_loader;
+    remote_loader.set_remote_source(remote_source.clone());
+
+    let program_id = Keypair::new().pubkey();
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let mut program_account = AccountSharedData::new_data(
+        40,
+        &UpgradeableLoaderState::Program {
+            programdata_address,
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap();
+    program_account.set_executable(true);
+    remote_source.add_account(program_id, program_account, None);
+    let mut file = File::open("../programs/bpf_loader/test_elfs/out/noop_aligned.so").unwrap();
+    let mut elf = Vec::new();
+    file.read_to_end(&mut elf).unwrap();
+    let set_remote_programdata = |source_slot: Slot, elf: &[u8]| {
+        let programdata_data_offset = UpgradeableLoaderState::size_of_programdata_metadata();
+        let mut programdata_account = AccountSharedData::new(
+            40,
+            programdata_data_offset + elf.len(),
+            &bpf_loader_upgradeable::id(),
+        );
+        programdata_account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: source_slot,
+                upgrade_authority_address: None,
+            })
+            .unwrap();
+        programdata_account.data_as_mut_slice()[programdata_data_offset..].copy_from_slice(elf);
+        remote_source.add_account(programdata_address, programdata_account, None);
+    };
+    set_remote_programdata(42, &elf);
+
+    let migrate = |bank: &Bank| {
+        let transaction = Transaction::new_signed_with_payer(
+            &[migrate_remote_accounts(
+                mint_keypair.pubkey(),
+                vec![program_id],
+            )],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            bank.last_blockhash(),
+        );
+        bank.process_transaction(&transaction).unwrap();
+    };
+    let invoke = |bank: &Bank| {
+        let instruction = Instruction::new_with_bytes(program_id, &[], Vec::new());
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            bank.last_blockhash(),
+        );
+        bank.process_transaction(&transaction)
+    };
+
+    // The first migration is deployed at slot 0, usable right away
+    goto_end_of_slot(bank.clone());
+    let bank = new_from_parent_with_fork_next_slot(bank, bank_forks.as_ref());
+    migrate(&bank);
+    let version = bank.remote_program_version(&program_id).unwrap();
+    assert_eq!(version.programdata_slot, 42);
+    assert_eq!(version.deployment_slot, 0);
+    assert_eq!(invoke(&bank), Ok(()));
+
+    // Migrating an unchanged program keeps its deployment
+    goto_end_of_slot(bank.clone());
+    let bank = new_from_parent_with_fork_next_slot(bank, bank_forks.as_ref());
+    remote_loader.deactivate_account(&program_id);
+    migrate(&bank);
+    assert_eq!(bank.remote_program_version(&program_id), Some(version));
+
+    // A newer program is redeployed at the slot it is migrated at, like a local upgrade
+    goto_end_of_slot(bank.clone());
+    let parent = bank;
+    let bank = new_from_parent_with_fork_next_slot(parent.clone(), bank_forks.as_ref());
+    let mut new_elf = elf.clone();
+    new_elf.extend_from_slice(&[0; 8]);
+    set_remote_programdata(43, &new_elf);
+    remote_loader.deactivate_account(&program_id);
+    migrate(&bank);
+    let new_version = bank.remote_program_version(&program_id).unwrap();
+    assert_eq!(new_version.programdata_slot, 43);
+    assert_ne!(new_version.data_hash, version.data_hash);
+    assert_eq!(new_version.deployment_slot, bank.slot());
+    {
+        let program_cache = bank.loaded_programs_cache.read().unwrap();
+        let slot_versions = program_cache.get_slot_versions_for_tests(&program_id);
+        let redeployed = slot_versions.last().unwrap();
+        assert_eq!(redeployed.deployment_slot, bank.slot());
+        assert_eq!(
+            redeployed.effective_slot,
+            bank.slot() + DELAY_VISIBILITY_SLOT_OFFSET
+        );
+    }
+    assert!(invoke(&bank).is_err());
+
+    // The redeployment is confined to the fork it was migrated on
+    let sibling = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        parent.clone(),
+        &Pubkey::default(),
+        bank.slot() + 1,
+    );
+    assert_eq!(sibling.remote_program_version(&program_id), Some(version));
+    assert_eq!(invoke(&sibling), Ok(()));
+    assert_eq!(
+        bank.loaded_programs_cache
+            .read()
+            .unwrap()
+            .get_slot_versions_for_tests(&program_id)
+            .first()
+            .unwrap()
+            .deployment_slot,
+        0
+    );
+
+    goto_end_of_slot(bank.clone());
+    let bank = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank.clone(),
+        &Pubkey::default(),
+        bank.slot() + 2,
+    );
+    assert_eq!(invoke(&bank), Ok(()));
+}
+
+#[test]
+fn test_migrate_unavailable_remote_account() {
+    let (genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let remote_source = Arc::new(StaticRemoteSource::new());
+    bank.rc
+        .accounts
+        .accounts_db
+        .accounts_cache
+        .remote_loader
+        .set_remote_source(remote_source.clone());
+
+    let missing = Keypair::new().pubkey();
+    let transaction = Transaction::new_signed_with_payer(
+        &[migrate_remote_accounts(
+            mint_keypair.pubkey(),
+            vec![missing],
+        )],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair],
+        bank.last_blockhash(),
+    );
+    let account_index = transaction
+        .message
+        .account_keys
+        .iter()
+        .position(|key| *key == missing)
+        .unwrap() as u8;
+
+    // The leader leaves the transaction out of its block
+    let batch = bank.prepare_batch_for_tests(vec![transaction.clone()]);
+    let output = bank.load_and_execute_transactions(
+        &batch,
+        MAX_PROCESSING_AGE,
+        false,
+        false,
+        false,
+        &mut ExecuteTimings::default(),
+        None,
+        None,
+        true,
+    );
+    assert_matches!(
+        &output.execution_results[0],
+        TransactionExecutionResult::NotExecuted(TransactionError::RemoteAccountUnavailable {
+            account_index: index,
+            reason: RemoteAccountUnavailableReason::NotFound,
+        }) if *index == account_index
+    );
+    drop(batch);
+    assert_eq!(
+        bank.get_balance(&mint_keypair.pubkey()),
+        1_000_000 * LAMPORTS_PER_SOL
+    );
+
+    // Replay retries the load until it succeeds instead of failing the leader's block
+    let add_account = {
+        let remote_source = remote_source.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            remote_source.add_account(
+                missing,
+                AccountSharedData::new(42, 0, &Pubkey::default()),
+                None,
+            );
+        })
+    };
+    assert_eq!(bank.process_transaction(&transaction), Ok(()));
+    add_account.join().unwrap();
+    assert_eq!(bank.get_balance(&missing), 42);
+}
+
+#[test]
+fn test_replay_gives_up_on_unavailable_remote_account() {
+    let (mut genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+    // Short slots, so that the loads are only retried for a few milliseconds
+    genesis_config.ticks_per_slot = 1;
+    genesis_config.poh_config.target_tick_duration = Duration::from_micros(100);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    bank.rc
+        .accounts
+        .accounts_db
+        .accounts_cache
+        .remote_loader
+        .set_remote_source(Arc::new(StaticRemoteSource::new()));
+
+    // The account never becomes available, so the replay fails instead of hanging
+    let missing = Keypair::new().pubkey();
+    let transaction = Transaction::new_signed_with_payer(
+        &[migrate_remote_accounts(
+            mint_keypair.pubkey(),
+            vec![missing],
+        )],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair],
+        bank.last_blockhash(),
+    );
+    assert_matches!(
+        bank.process_transaction(&transaction),
+        Err(TransactionError::RemoteAccountUnavailable {
+            reason: RemoteAccountUnavailableReason::NotFound,
+            ..
+        })
+    );
+    assert_eq!(
+        bank.get_balance(&mint_keypair.pubkey()),
+        1_000_000 * LAMPORTS_PER_SOL
+    );
+}
+
 #[test]
 fn test_bpf_loader_upgradeable_deploy_with_max_len() {
     let (genesis_config, mint_keypair) = create_genesis_config_no_tx_fee(1_000_000_000);
@@ -13834,6 +14710,7 @@ fn test_filter_executable_program_accounts() {
         &mut [(Ok(()), None), (Ok(()), None)],
         owners,
         &hash_queue,
+        false,
     );
 
     // The result should contain only account3_pubkey, and account4_pubkey as the program accounts
@@ -13934,6 +14811,7 @@ fn test_filter_executable_program_accounts_invalid_blockhash() {
         &mut lock_results,
         owners,
         &hash_queue,
+        false,
     );
 
     // The result should contain only account3_pubkey as the program accounts
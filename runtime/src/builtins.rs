@@ -113,6 +113,12 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         name: "sonic_account_migrater_program",
         entrypoint: sonic_account_migrater_program::processor::Entrypoint::vm,
     },
+    BuiltinPrototype {
+        feature_id: None,
+        program_id: solana_sdk::sonic_bridge::program::id(),
+        name: "sonic_bridge_program",
+        entrypoint: sonic_bridge_program::processor::Entrypoint::vm,
+    },
     BuiltinPrototype {
         feature_id: None,
         program_id: solana_sdk::sonic_fee_settlement::program::id(),
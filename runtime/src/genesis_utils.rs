@@ -10,6 +10,7 @@ use {
         pubkey::Pubkey,
         rent::Rent,
         signature::{Keypair, Signer},
+        sonic_sequencer::{self, SequencerConfig, SEQUENCER_CONFIG_SIZE},
         stake::state::StakeStateV2,
         system_program,
     },
@@ -215,6 +216,19 @@ pub fn activate_feature(genesis_config: &mut GenesisConfig, feature_id: Pubkey)
     );
 }
 
+/// Sonic: make `sequencer` the only leader of the grid, rooting its blocks without votes
+pub fn activate_sequencer_mode(genesis_config: &mut GenesisConfig, sequencer: Pubkey) {
+    let mut account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(SEQUENCER_CONFIG_SIZE),
+        SEQUENCER_CONFIG_SIZE,
+        &solana_sdk::config::program::id(),
+    );
+    account
+        .serialize_data(&SequencerConfig { sequencer })
+        .unwrap();
+    genesis_config.add_account(sonic_sequencer::config::id(), account);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_genesis_config_with_leader_ex(
     mint_lamports: u64,
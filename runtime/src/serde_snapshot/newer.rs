@@ -11,6 +11,7 @@ use {
     solana_accounts_db::{accounts_hash::AccountsHash, ancestors::AncestorsForSerialization},
     solana_measure::measure::Measure,
     solana_sdk::{deserialize_utils::ignore_eof_error, stake::state::Delegation},
+    sonic_hypergrid::remote_loader::RemoteProgramVersion,
     std::{cell::RefCell, collections::HashSet, sync::RwLock},
 };
 
@@ -100,6 +101,7 @@ impl From<DeserializableVersionedBank> for BankFieldsToDeserialize {
             incremental_snapshot_persistence: None,
             epoch_accounts_hash: None,
             epoch_reward_status: EpochRewardStatus::Inactive,
+            remote_programs: HashMap::default(),
         }
     }
 }
@@ -204,6 +206,7 @@ impl<'a> TypeContext<'a> for Context {
         let epoch_reward_status = serializable_bank
             .bank
             .get_epoch_reward_status_to_serialize();
+        let remote_programs = serializable_bank.bank.get_remote_programs_to_serialize();
         match get_serialize_bank_fields(
             SerializableVersionedBank::from(fields),
             SerializableAccountsDb::<'a, Self> {
@@ -222,9 +225,11 @@ impl<'a> TypeContext<'a> for Context {
                 .get_epoch_accounts_hash_to_serialize()
                 .map(|epoch_accounts_hash| *epoch_accounts_hash.as_ref()),
             epoch_reward_status,
+            remote_programs,
         ) {
             BankFieldsToSerialize::WithoutEpochRewardStatus(data) => data.serialize(serializer),
             BankFieldsToSerialize::WithEpochRewardStatus(data) => data.serialize(serializer),
+            BankFieldsToSerialize::WithRemotePrograms(data) => data.serialize(serializer),
         }
     }
 
@@ -347,6 +352,9 @@ impl<'a> TypeContext<'a> for Context {
         let epoch_reward_status = ignore_eof_error(deserialize_from(&mut stream))?;
         bank_fields.epoch_reward_status = epoch_reward_status;
 
+        let remote_programs = ignore_eof_error(deserialize_from(&mut stream))?;
+        bank_fields.remote_programs = remote_programs;
+
         Ok((bank_fields, accounts_db_fields))
     }
 
@@ -381,6 +389,7 @@ impl<'a> TypeContext<'a> for Context {
         let lamports_per_signature = rhs.fee_rate_governor.lamports_per_signature;
         let epoch_accounts_hash = rhs.epoch_accounts_hash.as_ref();
         let epoch_reward_status = rhs.epoch_reward_status;
+        let remote_programs = std::mem::take(&mut rhs.remote_programs);
 
         let bank = SerializableVersionedBank {
             blockhash_queue: &blockhash_queue,
@@ -425,6 +434,7 @@ impl<'a> TypeContext<'a> for Context {
             epoch_accounts_hash.copied(),
             matches!(epoch_reward_status, EpochRewardStatus::Active(_))
                 .then_some(&epoch_reward_status),
+            (!remote_programs.is_empty()).then_some(remote_programs),
         ) {
             BankFieldsToSerialize::WithoutEpochRewardStatus(data) => {
                 bincode::serialize_into(stream_writer, &data)
@@ -432,6 +442,9 @@ impl<'a> TypeContext<'a> for Context {
             BankFieldsToSerialize::WithEpochRewardStatus(data) => {
                 bincode::serialize_into(stream_writer, &data)
             }
+            BankFieldsToSerialize::WithRemotePrograms(data) => {
+                bincode::serialize_into(stream_writer, &data)
+            }
         }
     }
 }
@@ -460,6 +473,19 @@ enum BankFieldsToSerialize<'a, T: Serialize> {
             &'a EpochRewardStatus,
         ),
     ),
+    // Sonic: serialize this if remote programs were migrated on the fork, the epoch reward
+    // status is then always serialized, so the remote programs can follow it.
+    WithRemotePrograms(
+        (
+            SerializableVersionedBank<'a>,
+            T,
+            u64,
+            Option<BankIncrementalSnapshotPersistence>,
+            Option<Hash>,
+            &'a EpochRewardStatus,
+            HashMap<Pubkey, RemoteProgramVersion>,
+        ),
+    ),
 }
 
 /// serializing involves building these fields into a tuple
@@ -471,7 +497,19 @@ fn get_serialize_bank_fields<'a, T: Serialize>(
     incremental_snapshot_persistence: Option<BankIncrementalSnapshotPersistence>,
     epoch_accounts_hash: Option<Hash>,
     epoch_reward_status: Option<&'a EpochRewardStatus>,
+    remote_programs: Option<HashMap<Pubkey, RemoteProgramVersion>>,
 ) -> BankFieldsToSerialize<'a, T> {
+    if let Some(remote_programs) = remote_programs {
+        return BankFieldsToSerialize::WithRemotePrograms((
+            bank,
+            accounts_db_fields,
+            lamports_per_signature,
+            incremental_snapshot_persistence,
+            epoch_accounts_hash,
+            epoch_reward_status.unwrap_or(&EpochRewardStatus::Inactive),
+            remote_programs,
+        ));
+    }
     match epoch_reward_status {
         Some(epoch_reward_status) => BankFieldsToSerialize::WithEpochRewardStatus((
             bank,
@@ -536,7 +536,10 @@ pub mod sysvar;
 pub mod vote;
 pub mod wasm;
 pub mod sonic_account_migrater;
+pub mod sonic_bridge;
 pub mod sonic_fee_settlement;
+pub mod sonic_remote_account;
+pub mod sonic_sequencer;
 
 #[deprecated(
     since = "1.17.0",
@@ -570,7 +573,7 @@ pub mod sdk_ids {
             address_lookup_table, bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable,
             config, ed25519_program, feature, incinerator, loader_v4, secp256k1_program,
             solana_program::pubkey::Pubkey, stake, system_program, sysvar, vote,
-            sonic_account_migrater, sonic_fee_settlement,
+            sonic_account_migrater, sonic_bridge, sonic_fee_settlement, sonic_sequencer,
         },
         lazy_static::lazy_static,
     };
@@ -595,7 +598,9 @@ pub mod sdk_ids {
                 #[allow(deprecated)]
                 stake::config::id(),
                 sonic_account_migrater::program::id(),
+                sonic_bridge::program::id(),
                 sonic_fee_settlement::program::id(),
+                sonic_sequencer::config::id(),
             ];
             sdk_ids.extend(sysvar::ALL_IDS.iter());
             sdk_ids
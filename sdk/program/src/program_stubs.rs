@@ -5,7 +5,7 @@
 use {
     crate::{
         account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
-        program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey,
+        program_error::UNSUPPORTED_SYSVAR, pubkey::Pubkey, sonic_remote_account::RemoteAccountMeta,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     itertools::Itertools,
@@ -111,6 +111,9 @@ pub trait SyscallStubs: Sync + Send {
     fn sol_get_stack_height(&self) -> u64 {
         0
     }
+    fn sol_get_remote_account_meta(&self, _account_index: usize) -> RemoteAccountMeta {
+        RemoteAccountMeta::default()
+    }
 }
 
 struct DefaultSyscallStubs {}
@@ -218,6 +221,13 @@ pub(crate) fn sol_get_stack_height() -> u64 {
     SYSCALL_STUBS.read().unwrap().sol_get_stack_height()
 }
 
+pub(crate) fn sol_get_remote_account_meta(account_index: usize) -> RemoteAccountMeta {
+    SYSCALL_STUBS
+        .read()
+        .unwrap()
+        .sol_get_remote_account_meta(account_index)
+}
+
 pub(crate) fn sol_get_epoch_rewards_sysvar(var_addr: *mut u8) -> u64 {
     SYSCALL_STUBS
         .read()
@@ -60,4 +60,28 @@ pub fn deactivate_remote_accounts(
         &ProgramInstruction::DeactivateRemoteAccounts,
         accounts,
     )
+}
+
+/// Constructs an instruction which migrates remote accounts from the hypergrid
+/// node `node_id` to local accounts cache, reloading them from the node if `refresh` is set.
+pub fn migrate_source_accounts(
+    payer_address: Pubkey,
+    node_id: Pubkey,
+    refresh: bool,
+    addresses: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(payer_address, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    for address in addresses {
+        accounts.push(AccountMeta::new_readonly(address, false));
+    }
+
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::MigrateSourceAccounts { node_id, refresh },
+        accounts,
+    )
 }
\ No newline at end of file
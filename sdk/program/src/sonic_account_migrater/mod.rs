@@ -3,6 +3,7 @@
 //! [np]: 
 
 pub mod instruction;
+pub mod state;
 
 
 pub mod program {
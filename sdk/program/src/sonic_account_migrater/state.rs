@@ -0,0 +1,42 @@
+//! State of the Sonic account migrater program.
+
+use {
+    crate::{clock::Slot, pubkey::Pubkey, sonic_account_migrater::program::id},
+    serde::{Deserialize, Serialize},
+};
+
+/// Seed used to derive the genesis clone record address
+pub const GENESIS_CLONE_RECORD_SEED: &[u8] = b"genesis_clone_record";
+
+/// Where the accounts cloned into genesis were read from
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CloneSource {
+    /// A base layer RPC node
+    BaseLayer,
+    /// The HSSN records of the base layer accounts
+    Hssn,
+}
+
+/// An account cloned into genesis
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ClonedAccount {
+    pub pubkey: Pubkey,
+    /// Slot the account was read at on the source, if known
+    pub source_slot: Option<Slot>,
+}
+
+/// Record of the accounts cloned into genesis, the baseline the grid started from
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GenesisCloneRecord {
+    pub source: CloneSource,
+    /// URL of the source
+    pub url: String,
+    /// Slot the accounts were requested at
+    pub slot: Slot,
+    pub accounts: Vec<ClonedAccount>,
+}
+
+/// The address genesis keeps its clone record at
+pub fn derive_genesis_clone_record_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GENESIS_CLONE_RECORD_SEED], &id())
+}
@@ -0,0 +1,242 @@
+use {
+    super::state::{
+        derive_bridge_config_address, derive_bridge_mint_authority_address,
+        derive_bridge_vault_address, derive_deposit_record_address, derive_outbox_address,
+        derive_outbox_message_address, L1Signature, OutboxPayload, OutboxReceipt,
+    },
+    crate::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sonic_bridge::program::id,
+        system_program,
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// First bytes of the data of the instruction releasing a withdrawal from the L1 escrow
+/// program, followed by the bincode serialized [`EscrowRelease`]
+pub const ESCROW_RELEASE_DISCRIMINATOR: &[u8] = b"sncrlese";
+
+/// Seed the L1 escrow program derives the address of the vault it locks deposits in from
+pub const ESCROW_VAULT_SEED: &[u8] = b"escrow_vault";
+
+/// The SPL Token program, which mints the tokens credited for token deposits
+pub const SPL_TOKEN_PROGRAM_ID: Pubkey =
+    crate::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum ProgramInstruction {
+    /// Credit a SOL deposit made on L1, paid from the bridge vault
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Bridge config account
+    ///   1. `[SIGNER, WRITE]` Bridge authority, which also funds the deposit record
+    ///   2. `[WRITE]` Deposit record, at the address derived from the L1 transaction
+    ///   3. `[WRITE]` Recipient
+    ///   4. `[]` System program for CPI.
+    ///   5. `[WRITE]` Bridge vault
+    CreditDeposit {
+        l1_signature: L1Signature,
+        event_index: u8,
+        amount: u64,
+        bump_seed: u8,
+    },
+    /// Credit a token deposit made on L1 by minting the corresponding grid tokens
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Bridge config account
+    ///   1. `[SIGNER, WRITE]` Bridge authority, which also funds the deposit record
+    ///   2. `[WRITE]` Deposit record, at the address derived from the L1 transaction
+    ///   3. `[WRITE]` Recipient token account
+    ///   4. `[]` System program for CPI.
+    ///   5. `[WRITE]` Grid mint, whose mint authority is the bridge mint authority
+    ///   6. `[]` Bridge mint authority
+    ///   7. `[]` SPL Token program for CPI.
+    CreditTokenDeposit {
+        l1_signature: L1Signature,
+        event_index: u8,
+        amount: u64,
+        bump_seed: u8,
+    },
+    /// Send a message to L1, with the next id of the outbox
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Outbox account
+    ///   1. `[SIGNER, WRITE]` Sender, which funds the message account and pays withdrawals
+    ///   2. `[WRITE]` Message account, at the address derived from the message id
+    ///   3. `[]` System program for CPI.
+    ///   4. `[WRITE]` Bridge vault, for withdrawals only
+    SendMessage {
+        message_id: u64,
+        payload: OutboxPayload,
+        bump_seed: u8,
+    },
+    /// Write the receipt of a message handled by the relayer
+    ///
+    /// # Account references
+    ///   0. `[]` Bridge config account
+    ///   1. `[SIGNER]` Bridge authority
+    ///   2. `[WRITE]` Message account
+    WriteReceipt {
+        message_id: u64,
+        receipt: OutboxReceipt,
+    },
+}
+
+/// Withdrawal released by the L1 escrow program
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct EscrowRelease {
+    /// Id of the outbox message of the withdrawal.
+    pub message_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+fn credit_accounts(
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    l1_signature: &L1Signature,
+    event_index: u8,
+) -> (Vec<AccountMeta>, u8) {
+    let (record_address, bump_seed) = derive_deposit_record_address(l1_signature, event_index);
+    let accounts = vec![
+        AccountMeta::new(derive_bridge_config_address().0, false),
+        AccountMeta::new(authority_address, true),
+        AccountMeta::new(record_address, false),
+        AccountMeta::new(recipient_address, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    (accounts, bump_seed)
+}
+
+/// Constructs an instruction that credits `recipient_address` with the `amount` lamports
+/// deposited by the deposit event `event_index` of the L1 transaction `l1_signature`.
+pub fn credit_deposit(
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    l1_signature: L1Signature,
+    event_index: u8,
+    amount: u64,
+) -> Instruction {
+    let (mut accounts, bump_seed) = credit_accounts(
+        authority_address,
+        recipient_address,
+        &l1_signature,
+        event_index,
+    );
+    accounts.push(AccountMeta::new(derive_bridge_vault_address().0, false));
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::CreditDeposit {
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that mints `amount` tokens of `mint_address` to the token
+/// account `recipient_address`, for the deposit event `event_index` of the L1 transaction
+/// `l1_signature`.
+pub fn credit_token_deposit(
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    mint_address: Pubkey,
+    l1_signature: L1Signature,
+    event_index: u8,
+    amount: u64,
+) -> Instruction {
+    let (mut accounts, bump_seed) = credit_accounts(
+        authority_address,
+        recipient_address,
+        &l1_signature,
+        event_index,
+    );
+    accounts.extend([
+        AccountMeta::new(mint_address, false),
+        AccountMeta::new_readonly(derive_bridge_mint_authority_address().0, false),
+        AccountMeta::new_readonly(SPL_TOKEN_PROGRAM_ID, false),
+    ]);
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::CreditTokenDeposit {
+            l1_signature,
+            event_index,
+            amount,
+            bump_seed,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that sends a message to L1. `message_id` must be the next id of
+/// the outbox.
+pub fn send_message(
+    sender_address: Pubkey,
+    message_id: u64,
+    payload: OutboxPayload,
+) -> Instruction {
+    let (message_address, bump_seed) = derive_outbox_message_address(message_id);
+    let mut accounts = vec![
+        AccountMeta::new(derive_outbox_address().0, false),
+        AccountMeta::new(sender_address, true),
+        AccountMeta::new(message_address, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let OutboxPayload::Withdraw { .. } = payload {
+        accounts.push(AccountMeta::new(derive_bridge_vault_address().0, false));
+    }
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::SendMessage {
+            message_id,
+            payload,
+            bump_seed,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that writes the receipt of the message `message_id`.
+pub fn write_receipt(
+    authority_address: Pubkey,
+    message_id: u64,
+    receipt: OutboxReceipt,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::WriteReceipt {
+            message_id,
+            receipt,
+        },
+        vec![
+            AccountMeta::new_readonly(derive_bridge_config_address().0, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(derive_outbox_message_address(message_id).0, false),
+        ],
+    )
+}
+
+/// Constructs the L1 instruction that has `escrow_program_id` release a withdrawal from its
+/// vault, signed by the relayer `authority_address`.
+pub fn escrow_release(
+    escrow_program_id: Pubkey,
+    authority_address: Pubkey,
+    release: &EscrowRelease,
+) -> Instruction {
+    let (vault_address, _) = Pubkey::find_program_address(&[ESCROW_VAULT_SEED], &escrow_program_id);
+    let mut data = ESCROW_RELEASE_DISCRIMINATOR.to_vec();
+    data.extend(bincode::serialize(release).unwrap());
+    Instruction::new_with_bytes(
+        escrow_program_id,
+        &data,
+        vec![
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(vault_address, false),
+            AccountMeta::new(release.recipient, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
@@ -0,0 +1,16 @@
+//! The Sonic bridge program.
+//!
+//! Deposits made on L1 to the bridge escrow program are credited on the grid by the bridge
+//! authority, which records the L1 transaction of every credited deposit so that it can only
+//! be credited once.
+//!
+//! The other way around, grid programs send messages to L1 through the outbox, withdrawals or
+//! arbitrary L1 instructions. A relayer submits the rooted messages to L1, then writes the
+//! outcome back into the message as its receipt.
+
+pub mod instruction;
+pub mod state;
+
+pub mod program {
+    crate::declare_id!("SonicBridge11111111111111111111111111111111");
+}
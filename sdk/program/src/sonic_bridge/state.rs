@@ -0,0 +1,384 @@
+//! State of the Sonic bridge program.
+
+use {
+    crate::{
+        clock::Slot,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    serde::{Deserialize, Serialize},
+};
+
+/// Seed used to derive the bridge config address
+pub const BRIDGE_CONFIG_SEED: &[u8] = b"bridge_config";
+
+/// Seed used to derive the address of the vault SOL deposits are paid from
+pub const BRIDGE_VAULT_SEED: &[u8] = b"bridge_vault";
+
+/// Seed used to derive the mint authority of the tokens credited for deposits
+pub const BRIDGE_MINT_AUTHORITY_SEED: &[u8] = b"bridge_mint_authority";
+
+/// Seed used to derive the deposit record addresses
+pub const DEPOSIT_RECORD_SEED: &[u8] = b"deposit";
+
+/// Seed used to derive the outbox address
+pub const OUTBOX_SEED: &[u8] = b"outbox";
+
+/// Seed used to derive the outbox message addresses
+pub const OUTBOX_MESSAGE_SEED: &[u8] = b"outbox_message";
+
+/// Serialized size of the bridge config account
+pub const BRIDGE_CONFIG_SIZE: usize = 4 + 32 + 8 + 8;
+
+/// Serialized size of a deposit record account
+pub const DEPOSIT_RECORD_SIZE: usize = 4 + 64 + 1 + 32 + 33 + 8 + 8;
+
+/// Serialized size of the outbox account
+pub const OUTBOX_SIZE: usize = 4 + 8;
+
+/// Serialized size of an outbox message account, without its payload
+pub const OUTBOX_MESSAGE_OVERHEAD: usize = 4 + 8 + 32 + 8 + 1 + OUTBOX_RECEIPT_SIZE;
+
+/// Serialized size of an outbox receipt
+pub const OUTBOX_RECEIPT_SIZE: usize = 1 + 64 + 8 + 1;
+
+/// First field of the deposit events logged by the L1 escrow program with `sol_log_data`,
+/// the second one being the bincode serialized [`DepositEvent`]
+pub const DEPOSIT_EVENT_DISCRIMINATOR: &[u8] = b"sncdepst";
+
+/// Signature of an L1 transaction, split in halves to fit in seeds
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct L1Signature([[u8; 32]; 2]);
+
+impl L1Signature {
+    pub fn new(bytes: [u8; 64]) -> Self {
+        let mut halves = [[0; 32]; 2];
+        halves[0].copy_from_slice(&bytes[..32]);
+        halves[1].copy_from_slice(&bytes[32..]);
+        Self(halves)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.0[0]);
+        bytes[32..].copy_from_slice(&self.0[1]);
+        bytes
+    }
+
+    fn halves(&self) -> [&[u8]; 2] {
+        [&self.0[0], &self.0[1]]
+    }
+}
+
+/// Program account states
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum BridgeState {
+    /// Account is not initialized.
+    Uninitialized,
+    /// Config of the bridge.
+    Config(BridgeConfig),
+    /// A credited deposit.
+    Deposit(DepositRecord),
+    /// The outbox of the messages sent to L1.
+    Outbox(Outbox),
+    /// A message sent to L1.
+    Message(OutboxMessage),
+}
+
+/// Config of the bridge, created in genesis
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct BridgeConfig {
+    /// The only key allowed to credit deposits.
+    pub authority: Pubkey,
+    /// Number of deposits credited so far.
+    pub deposit_count: u64,
+    /// Total lamports credited for SOL deposits.
+    pub deposited_lamports: u64,
+}
+
+/// Record of a credited deposit, kept at the address derived from its L1 transaction
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct DepositRecord {
+    pub l1_signature: L1Signature,
+    /// Index of the deposit among the deposit events of the L1 transaction.
+    pub event_index: u8,
+    /// The grid account, or token account, credited.
+    pub recipient: Pubkey,
+    /// The grid mint of the tokens credited, `None` for SOL.
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    /// Slot of the grid the deposit was credited at.
+    pub slot: Slot,
+}
+
+/// Outbox of the messages sent to L1, created in genesis
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Outbox {
+    /// The id assigned to the next message.
+    pub next_message_id: u64,
+}
+
+/// An account of an L1 instruction. The relayer is the only signer of the transactions it
+/// submits to L1, so messages can't require other signatures.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct L1AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+/// An instruction to execute on L1
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct L1Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<L1AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl L1Instruction {
+    /// Whether the instruction references `pubkey`, as its program or one of its accounts.
+    pub fn references(&self, pubkey: &Pubkey) -> bool {
+        self.program_id == *pubkey || self.accounts.iter().any(|meta| meta.pubkey == *pubkey)
+    }
+
+    pub fn to_instruction(&self) -> Instruction {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: false,
+                is_writable: meta.is_writable,
+            })
+            .collect();
+        Instruction::new_with_bytes(self.program_id, &self.data, accounts)
+    }
+}
+
+/// What a message asks for on L1
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub enum OutboxPayload {
+    /// Release lamports locked in the L1 escrow program to `recipient`. The lamports are paid
+    /// into the bridge vault when the message is sent.
+    Withdraw { recipient: Pubkey, amount: u64 },
+    /// Execute an arbitrary instruction.
+    Call(L1Instruction),
+}
+
+/// A message sent to L1, kept at the address derived from its id
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct OutboxMessage {
+    pub id: u64,
+    /// The grid account that sent the message.
+    pub sender: Pubkey,
+    pub payload: OutboxPayload,
+    /// Slot of the grid the message was sent at.
+    pub slot: Slot,
+    /// Written back by the relayer once the message is handled.
+    pub receipt: Option<OutboxReceipt>,
+}
+
+impl OutboxMessage {
+    /// Serialized size of the account of a message carrying `payload`, with room for its
+    /// receipt.
+    pub fn space(payload: &OutboxPayload) -> usize {
+        OUTBOX_MESSAGE_OVERHEAD + bincode::serialized_size(payload).unwrap() as usize
+    }
+}
+
+/// What became of a message on L1
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OutboxReceipt {
+    /// The L1 transaction of the message, `None` if the relayer refused to submit it.
+    pub l1_signature: Option<L1Signature>,
+    /// Slot of the L1 the transaction was executed at.
+    pub l1_slot: Slot,
+    /// Whether the transaction succeeded.
+    pub success: bool,
+}
+
+/// Deposit logged by the L1 escrow program
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct DepositEvent {
+    /// The L1 account the deposit was made from.
+    pub depositor: Pubkey,
+    /// The grid account to credit for SOL, the grid token account for tokens.
+    pub recipient: Pubkey,
+    /// The L1 mint of the deposited tokens, `None` for SOL.
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+}
+
+impl DepositEvent {
+    /// Decode the fields of a `sol_log_data` entry, `None` if it is not a deposit event.
+    pub fn decode(fields: &[Vec<u8>]) -> Option<Self> {
+        match fields {
+            [discriminator, event] if discriminator == DEPOSIT_EVENT_DISCRIMINATOR => {
+                bincode::deserialize(event).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// The fields of the `sol_log_data` entry of the event.
+    pub fn encode(&self) -> Vec<Vec<u8>> {
+        vec![
+            DEPOSIT_EVENT_DISCRIMINATOR.to_vec(),
+            bincode::serialize(self).unwrap(),
+        ]
+    }
+}
+
+/// The address of the bridge config
+pub fn derive_bridge_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_CONFIG_SEED], &super::program::id())
+}
+
+/// The address of the vault SOL deposits are paid from
+pub fn derive_bridge_vault_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_VAULT_SEED], &super::program::id())
+}
+
+/// The mint authority of the tokens credited for deposits
+pub fn derive_bridge_mint_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_MINT_AUTHORITY_SEED], &super::program::id())
+}
+
+/// The address of the record of the deposit `event_index` of the L1 transaction
+pub fn derive_deposit_record_address(l1_signature: &L1Signature, event_index: u8) -> (Pubkey, u8) {
+    let [first_half, second_half] = l1_signature.halves();
+    Pubkey::find_program_address(
+        &[DEPOSIT_RECORD_SEED, first_half, second_half, &[event_index]],
+        &super::program::id(),
+    )
+}
+
+/// The address of the outbox
+pub fn derive_outbox_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OUTBOX_SEED], &super::program::id())
+}
+
+/// The address of the outbox message `message_id`
+pub fn derive_outbox_message_address(message_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OUTBOX_MESSAGE_SEED, &message_id.to_le_bytes()],
+        &super::program::id(),
+    )
+}
+
+/// Create the address of an outbox message from its bump seed
+pub fn create_outbox_message_address(message_id: u64, bump_seed: u8) -> Option<Pubkey> {
+    Pubkey::create_program_address(
+        &[OUTBOX_MESSAGE_SEED, &message_id.to_le_bytes(), &[bump_seed]],
+        &super::program::id(),
+    )
+    .ok()
+}
+
+/// Create the address of a deposit record from its bump seed
+pub fn create_deposit_record_address(
+    l1_signature: &L1Signature,
+    event_index: u8,
+    bump_seed: u8,
+) -> Option<Pubkey> {
+    let [first_half, second_half] = l1_signature.halves();
+    Pubkey::create_program_address(
+        &[
+            DEPOSIT_RECORD_SEED,
+            first_half,
+            second_half,
+            &[event_index],
+            &[bump_seed],
+        ],
+        &super::program::id(),
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridge_state_sizes() {
+        let config = BridgeState::Config(BridgeConfig {
+            authority: Pubkey::new_unique(),
+            deposit_count: u64::MAX,
+            deposited_lamports: u64::MAX,
+        });
+        assert_eq!(
+            bincode::serialized_size(&config).unwrap() as usize,
+            BRIDGE_CONFIG_SIZE
+        );
+
+        let record = BridgeState::Deposit(DepositRecord {
+            l1_signature: L1Signature::new([7; 64]),
+            event_index: 1,
+            recipient: Pubkey::new_unique(),
+            mint: Some(Pubkey::new_unique()),
+            amount: u64::MAX,
+            slot: Slot::MAX,
+        });
+        assert_eq!(
+            bincode::serialized_size(&record).unwrap() as usize,
+            DEPOSIT_RECORD_SIZE
+        );
+
+        let outbox = BridgeState::Outbox(Outbox {
+            next_message_id: u64::MAX,
+        });
+        assert_eq!(
+            bincode::serialized_size(&outbox).unwrap() as usize,
+            OUTBOX_SIZE
+        );
+
+        let payload = OutboxPayload::Call(L1Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![L1AccountMeta {
+                pubkey: Pubkey::new_unique(),
+                is_writable: true,
+            }],
+            data: vec![1, 2, 3],
+        });
+        let message = BridgeState::Message(OutboxMessage {
+            id: u64::MAX,
+            sender: Pubkey::new_unique(),
+            payload: payload.clone(),
+            slot: Slot::MAX,
+            receipt: Some(OutboxReceipt {
+                l1_signature: Some(L1Signature::new([7; 64])),
+                l1_slot: Slot::MAX,
+                success: true,
+            }),
+        });
+        assert_eq!(
+            bincode::serialized_size(&message).unwrap() as usize,
+            OutboxMessage::space(&payload)
+        );
+    }
+
+    #[test]
+    fn test_deposit_event() {
+        let event = DepositEvent {
+            depositor: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            mint: None,
+            amount: 42,
+        };
+        assert_eq!(DepositEvent::decode(&event.encode()), Some(event.clone()));
+        let mut fields = event.encode();
+        fields[0] = b"otherevt".to_vec();
+        assert_eq!(DepositEvent::decode(&fields), None);
+        assert_eq!(DepositEvent::decode(&fields[1..]), None);
+
+        let mut bytes = [0; 64];
+        bytes[63] = 1;
+        let l1_signature = L1Signature::new(bytes);
+        assert_eq!(l1_signature.to_bytes(), bytes);
+        let (address, bump_seed) = derive_deposit_record_address(&l1_signature, 0);
+        assert_eq!(
+            create_deposit_record_address(&l1_signature, 0, bump_seed),
+            Some(address)
+        );
+        assert_ne!(derive_deposit_record_address(&l1_signature, 1).0, address);
+    }
+}
@@ -1,7 +1,14 @@
 use {
-    super::state::SettlementAccountType, 
-    crate::pubkey::Pubkey, 
-    serde::{Deserialize, Serialize}
+    super::state::{
+        DynamicFeeMultiplier, SettlementAccountType, FEE_MULTIPLIER_SEED, SETTLEMENT_CURSOR_SEED,
+    },
+    crate::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sonic_fee_settlement::program::id,
+        system_program,
+    },
+    serde::{Deserialize, Serialize},
 };
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample, AbiEnumVisitor)]
@@ -18,14 +25,187 @@ pub enum ProgramInstruction {
         account_type: SettlementAccountType,
     },
     /// Settle fee bill
+    ///
+    /// Bill ids are settled in contiguous, non-overlapping ranges:
+    /// `from_id` must be the cursor's next bill id and `end_id` the last
+    /// (inclusive) bill id of the range.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Settlement cursor account
+    ///   1. `[SIGNER]` Settler designated in the cursor
+    ///   2..N `[WRITE]` Settlement accounts to credit
     SettleFeeBill {
         from_id: u64,
         end_id: u64,
         bills: Vec<SettlementBillParam>,
     },
-    /// Withdraw fee bill
+    /// Withdraw settled fees from a settlement account to its owner
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Settlement account
+    ///   1. `[SIGNER, WRITE]` Owner of the settlement account, which receives the lamports
     WithdrawFeeBill {
         address: Pubkey,
         amount: u64,
     },
+    /// Create the settlement cursor of the grid at its program-derived address
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized settlement cursor account
+    ///   1. `[SIGNER, WRITE]` Settler, which also funds the cursor account
+    ///   2. `[]` System program for CPI.
+    ///   3. `[]` Fee multiplier config account, which names the grid authority
+    ///   4. `[SIGNER]` Grid authority, which appoints the settler
+    InitializeSettlementCursor {
+        bump_seed: u8,
+    },
+    /// Hand the settlement authority over to a new settler
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Settlement cursor account
+    ///   1. `[SIGNER]` Current settler
+    SetSettler {
+        settler: Pubkey,
+    },
+    /// Change the fee multiplier of the grid, which applies from the next slot
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Fee multiplier config account
+    ///   1. `[SIGNER]` Fee multiplier authority
+    SetFeeMultiplier {
+        multiplier: u32,
+        dynamic: Option<DynamicFeeMultiplier>,
+    },
+}
+
+/// Constructs an instruction that initializes a settlement account, which must
+/// already be allocated with `SETTLEMENT_ACCOUNT_SIZE` bytes and assigned to the program.
+pub fn initialize_account(
+    settlement_address: Pubkey,
+    owner: Pubkey,
+    account_type: SettlementAccountType,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::InitializeAccount {
+            owner,
+            account_type,
+        },
+        vec![AccountMeta::new(settlement_address, false)],
+    )
+}
+
+/// Constructs an instruction that withdraws `amount` from the withdrawable
+/// balance of a settlement account to its owner.
+pub fn withdraw_fee_bill(settlement_address: Pubkey, owner: Pubkey, amount: u64) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::WithdrawFeeBill {
+            address: owner,
+            amount,
+        },
+        vec![
+            AccountMeta::new(settlement_address, false),
+            AccountMeta::new(owner, true),
+        ],
+    )
+}
+
+/// Derives the address of the settlement cursor account.
+pub fn derive_settlement_cursor_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SETTLEMENT_CURSOR_SEED], &id())
+}
+
+/// Derives the address of the fee multiplier config account.
+pub fn derive_fee_multiplier_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_MULTIPLIER_SEED], &id())
+}
+
+/// Constructs an instruction in which the fee multiplier authority appoints the settler
+/// of a new settlement cursor, and returns the instruction and the cursor's derived address.
+pub fn initialize_settlement_cursor(
+    settler_address: Pubkey,
+    authority_address: Pubkey,
+) -> (Instruction, Pubkey) {
+    let (cursor_address, bump_seed) = derive_settlement_cursor_address();
+    let (config_address, _) = derive_fee_multiplier_address();
+    let instruction = Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::InitializeSettlementCursor { bump_seed },
+        vec![
+            AccountMeta::new(cursor_address, false),
+            AccountMeta::new(settler_address, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(config_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    );
+
+    (instruction, cursor_address)
+}
+
+/// Constructs an instruction that settles the bills `[from_id, end_id]`
+/// into the given settlement accounts.
+pub fn settle_fee_bill(
+    settler_address: Pubkey,
+    settlement_addresses: Vec<Pubkey>,
+    from_id: u64,
+    end_id: u64,
+    bills: Vec<SettlementBillParam>,
+) -> Instruction {
+    let (cursor_address, _) = derive_settlement_cursor_address();
+    let mut accounts = vec![
+        AccountMeta::new(cursor_address, false),
+        AccountMeta::new_readonly(settler_address, true),
+    ];
+
+    for address in settlement_addresses {
+        accounts.push(AccountMeta::new(address, false));
+    }
+
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::SettleFeeBill {
+            from_id,
+            end_id,
+            bills,
+        },
+        accounts,
+    )
+}
+
+/// Constructs an instruction that hands the settlement authority over to `new_settler_address`.
+pub fn set_settler(settler_address: Pubkey, new_settler_address: Pubkey) -> Instruction {
+    let (cursor_address, _) = derive_settlement_cursor_address();
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::SetSettler {
+            settler: new_settler_address,
+        },
+        vec![
+            AccountMeta::new(cursor_address, false),
+            AccountMeta::new_readonly(settler_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that changes the fee multiplier, and optionally
+/// lets it follow the block utilization within the `dynamic` bounds.
+pub fn set_fee_multiplier(
+    authority_address: Pubkey,
+    multiplier: u32,
+    dynamic: Option<DynamicFeeMultiplier>,
+) -> Instruction {
+    let (config_address, _) = derive_fee_multiplier_address();
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::SetFeeMultiplier {
+            multiplier,
+            dynamic,
+        },
+        vec![
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
 }
@@ -1,9 +1,44 @@
 use {
     serde::{Deserialize, Serialize},
     solana_frozen_abi_macro::{AbiEnumVisitor, AbiExample},
-    solana_program::pubkey::Pubkey,
+    solana_program::{clock::Slot, pubkey::Pubkey},
 };
 
+/// Serialized size of a settlement account
+pub const SETTLEMENT_ACCOUNT_SIZE: usize = 4 + 32 + 4 + 8 + 8 + 8;
+
+/// The maximum number of settled range summaries kept in the cursor account.
+/// Older summaries are dropped first; they remain available in transaction logs.
+pub const MAX_SETTLED_RANGES: usize = 64;
+
+/// Serialized size of a settled range summary
+pub const SETTLED_RANGE_SIZE: usize = 40;
+
+/// Serialized size of a settlement cursor account holding `MAX_SETTLED_RANGES` summaries
+pub const SETTLEMENT_CURSOR_SIZE: usize =
+    4 + 32 + 8 + 8 + 8 + 8 + MAX_SETTLED_RANGES * SETTLED_RANGE_SIZE;
+
+/// Seed used to derive the settlement cursor address
+pub const SETTLEMENT_CURSOR_SEED: &[u8] = b"settlement_cursor";
+
+/// Seed used to derive the fee multiplier config address
+pub const FEE_MULTIPLIER_SEED: &[u8] = b"fee_multiplier";
+
+/// Fee multipliers are expressed in units of `1 / FEE_MULTIPLIER_DENOMINATOR`
+pub const FEE_MULTIPLIER_DENOMINATOR: u32 = 10_000;
+
+/// The lowest fee multiplier allowed (0.1x)
+pub const MIN_FEE_MULTIPLIER: u32 = 1_000;
+
+/// The highest fee multiplier allowed (10x)
+pub const MAX_FEE_MULTIPLIER: u32 = 100_000;
+
+/// Serialized size of a fee multiplier config account
+pub const FEE_MULTIPLIER_CONFIG_SIZE: usize = 4 + 32 + 4 + 4 + 1 + 4 + 4 + 1;
+
+/// The dynamic fee multiplier moves by at most `1 / FEE_MULTIPLIER_MAX_CHANGE_DENOMINATOR` per slot
+pub const FEE_MULTIPLIER_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
 /// Program account states
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample, AbiEnumVisitor)]
 #[allow(clippy::large_enum_variant)]
@@ -12,12 +47,16 @@ pub enum SettlementState {
     Uninitialized,
     /// Initialized `LookupTable` account.
     FeeBillSettled(SettlementAccount),
+    /// Initialized settlement cursor of the grid.
+    SettlementCursor(SettlementCursor),
+    /// Fee multiplier applied by the grid's banks.
+    FeeMultiplier(FeeMultiplierConfig),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample, AbiEnumVisitor)]
 pub struct SettlementAccount {
     pub owner: Pubkey,
-    pub account_type: SettlementAccountType, 
+    pub account_type: SettlementAccountType,
     pub amount: u64,
     pub withdrawable: u64,
     pub withdrawed: u64,
@@ -29,4 +68,267 @@ pub enum SettlementAccountType {
     HSSNAccount,
     SonicGridAccount,
     GridAccount,
-}
\ No newline at end of file
+}
+
+/// Tracks which fee bills of the grid have been settled, so that the same
+/// bill range can never be settled twice.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct SettlementCursor {
+    /// The only key allowed to settle fee bills (the HSSN relayer).
+    pub settler: Pubkey,
+    /// The id of the next bill expected to be settled.
+    pub next_bill_id: u64,
+    /// Total number of bills settled through this cursor.
+    pub settled_bills: u64,
+    /// Total amount settled through this cursor.
+    pub settled_amount: u64,
+    /// Most recent settled range summaries, oldest first.
+    pub ranges: Vec<SettledRange>,
+}
+
+impl SettlementCursor {
+    pub fn new(settler: Pubkey) -> Self {
+        Self {
+            settler,
+            next_bill_id: 0,
+            settled_bills: 0,
+            settled_amount: 0,
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Check that `[from_id, end_id]` directly follows the last settled range.
+    pub fn is_next_range(&self, from_id: u64, end_id: u64) -> bool {
+        from_id == self.next_bill_id && end_id >= from_id
+    }
+
+    /// Record a settled range and advance the cursor past it. Returns `None`, leaving the
+    /// cursor unchanged, if no bill id follows the range.
+    pub fn record(&mut self, range: SettledRange) -> Option<()> {
+        self.next_bill_id = range.end_id.checked_add(1)?;
+        self.settled_bills = self.settled_bills.saturating_add(range.bill_count);
+        self.settled_amount = self.settled_amount.saturating_add(range.amount);
+        if self.ranges.len() >= MAX_SETTLED_RANGES {
+            self.ranges.remove(0);
+        }
+        self.ranges.push(range);
+        Some(())
+    }
+}
+
+/// Summary of a settled bill range, kept for reconciliation against HSSN.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct SettledRange {
+    pub from_id: u64,
+    pub end_id: u64,
+    pub bill_count: u64,
+    pub amount: u64,
+    pub slot: Slot,
+}
+
+/// Fee multiplier of the grid, read by every bank when it is created.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, AbiExample)]
+pub struct FeeMultiplierConfig {
+    /// The key allowed to change the multiplier.
+    pub authority: Pubkey,
+    /// The multiplier set by the authority; the starting point of the dynamic mode.
+    pub multiplier: u32,
+    /// The multiplier applied to the fees of the next bank.
+    pub active_multiplier: u32,
+    /// If set, `active_multiplier` follows the block utilization.
+    pub dynamic: Option<DynamicFeeMultiplier>,
+}
+
+/// Bounds of a fee multiplier that follows the block utilization.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, AbiExample)]
+pub struct DynamicFeeMultiplier {
+    pub min_multiplier: u32,
+    pub max_multiplier: u32,
+    /// Block utilization, in percent of the block cost limit, at which the multiplier is stable.
+    pub target_utilization_percent: u8,
+}
+
+impl DynamicFeeMultiplier {
+    pub fn is_valid(&self) -> bool {
+        MIN_FEE_MULTIPLIER <= self.min_multiplier
+            && self.min_multiplier <= self.max_multiplier
+            && self.max_multiplier <= MAX_FEE_MULTIPLIER
+            && (1..=100).contains(&self.target_utilization_percent)
+    }
+}
+
+impl FeeMultiplierConfig {
+    pub fn new(authority: Pubkey, multiplier: u32, dynamic: Option<DynamicFeeMultiplier>) -> Self {
+        Self {
+            authority,
+            multiplier,
+            active_multiplier: multiplier,
+            dynamic,
+        }
+    }
+
+    /// Check the multiplier and the dynamic bounds are in range.
+    pub fn is_valid(&self) -> bool {
+        is_valid_fee_multiplier(self.multiplier)
+            && is_valid_fee_multiplier(self.active_multiplier)
+            && self
+                .dynamic
+                .map(|dynamic| dynamic.is_valid())
+                .unwrap_or(true)
+    }
+
+    /// The multiplier that follows a block which used `block_units` of
+    /// `block_limit`: it rises when the block was above the target
+    /// utilization and falls when it was below, by at most 1/8 per slot.
+    pub fn next_active_multiplier(&self, block_units: u64, block_limit: u64) -> u32 {
+        let Some(dynamic) = self.dynamic else {
+            return self.multiplier;
+        };
+        let target_units = block_limit
+            .saturating_mul(u64::from(dynamic.target_utilization_percent))
+            .saturating_div(100)
+            .max(1);
+        let active = u64::from(self.active_multiplier);
+        let delta = active
+            .saturating_mul(block_units.abs_diff(target_units))
+            .saturating_div(target_units)
+            .saturating_div(FEE_MULTIPLIER_MAX_CHANGE_DENOMINATOR)
+            .min(active.saturating_div(FEE_MULTIPLIER_MAX_CHANGE_DENOMINATOR))
+            .max(1);
+        let next = if block_units > target_units {
+            active.saturating_add(delta)
+        } else if block_units < target_units {
+            active.saturating_sub(delta)
+        } else {
+            active
+        };
+        next.clamp(
+            u64::from(dynamic.min_multiplier),
+            u64::from(dynamic.max_multiplier),
+        ) as u32
+    }
+}
+
+pub fn is_valid_fee_multiplier(multiplier: u32) -> bool {
+    (MIN_FEE_MULTIPLIER..=MAX_FEE_MULTIPLIER).contains(&multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settlement_account_size() {
+        let state = SettlementState::FeeBillSettled(SettlementAccount {
+            owner: Pubkey::new_unique(),
+            account_type: SettlementAccountType::GridAccount,
+            amount: u64::MAX,
+            withdrawable: u64::MAX,
+            withdrawed: u64::MAX,
+        });
+        assert_eq!(
+            bincode::serialized_size(&state).unwrap() as usize,
+            SETTLEMENT_ACCOUNT_SIZE
+        );
+    }
+
+    #[test]
+    fn test_settlement_cursor_size() {
+        let mut cursor = SettlementCursor::new(Pubkey::new_unique());
+        for i in 0..MAX_SETTLED_RANGES as u64 + 2 {
+            cursor
+                .record(SettledRange {
+                    from_id: i,
+                    end_id: i,
+                    bill_count: 1,
+                    amount: 10,
+                    slot: i,
+                })
+                .unwrap();
+        }
+        assert_eq!(cursor.ranges.len(), MAX_SETTLED_RANGES);
+        assert_eq!(cursor.ranges[0].from_id, 2);
+        assert_eq!(cursor.next_bill_id, MAX_SETTLED_RANGES as u64 + 2);
+
+        let state = SettlementState::SettlementCursor(cursor);
+        assert_eq!(
+            bincode::serialized_size(&state).unwrap() as usize,
+            SETTLEMENT_CURSOR_SIZE
+        );
+    }
+
+    #[test]
+    fn test_settlement_cursor_next_range() {
+        let mut cursor = SettlementCursor::new(Pubkey::new_unique());
+        assert!(cursor.is_next_range(0, 9));
+        assert!(!cursor.is_next_range(1, 9));
+        cursor
+            .record(SettledRange {
+                from_id: 0,
+                end_id: 9,
+                bill_count: 10,
+                amount: 100,
+                slot: 1,
+            })
+            .unwrap();
+        assert!(!cursor.is_next_range(0, 9));
+        assert!(!cursor.is_next_range(5, 12));
+        assert!(!cursor.is_next_range(10, 9));
+        assert!(cursor.is_next_range(10, 10));
+        assert_eq!(cursor.settled_amount, 100);
+
+        // The last bill id can't be settled, the cursor could not move past it
+        let mut last = cursor.clone();
+        assert_eq!(
+            last.record(SettledRange {
+                from_id: 10,
+                end_id: u64::MAX,
+                bill_count: u64::MAX - 9,
+                amount: 100,
+                slot: 2,
+            }),
+            None
+        );
+        assert_eq!(last, cursor);
+    }
+
+    #[test]
+    fn test_fee_multiplier_config_size() {
+        let state = SettlementState::FeeMultiplier(FeeMultiplierConfig::new(
+            Pubkey::new_unique(),
+            FEE_MULTIPLIER_DENOMINATOR,
+            Some(DynamicFeeMultiplier {
+                min_multiplier: MIN_FEE_MULTIPLIER,
+                max_multiplier: MAX_FEE_MULTIPLIER,
+                target_utilization_percent: 50,
+            }),
+        ));
+        assert_eq!(
+            bincode::serialized_size(&state).unwrap() as usize,
+            FEE_MULTIPLIER_CONFIG_SIZE
+        );
+    }
+
+    #[test]
+    fn test_next_active_multiplier() {
+        let mut config = FeeMultiplierConfig::new(Pubkey::new_unique(), 10_000, None);
+        assert_eq!(config.next_active_multiplier(1_000, 1_000), 10_000);
+
+        config.dynamic = Some(DynamicFeeMultiplier {
+            min_multiplier: 5_000,
+            max_multiplier: 20_000,
+            target_utilization_percent: 50,
+        });
+        assert!(config.is_valid());
+        // At the target the multiplier is stable
+        assert_eq!(config.next_active_multiplier(500, 1_000), 10_000);
+        // Full blocks raise it by 1/8, empty blocks lower it by 1/8
+        assert_eq!(config.next_active_multiplier(1_000, 1_000), 11_250);
+        assert_eq!(config.next_active_multiplier(0, 1_000), 8_750);
+        // It never leaves the bounds
+        config.active_multiplier = 19_999;
+        assert_eq!(config.next_active_multiplier(1_000, 1_000), 20_000);
+        config.active_multiplier = 5_001;
+        assert_eq!(config.next_active_multiplier(0, 1_000), 5_000);
+    }
+}
@@ -0,0 +1,102 @@
+//! Sonic: where the accounts given to a program come from.
+//!
+//! A grid serves some accounts from other chains rather than from its own state: read-only
+//! mirrors of the base layer, of other L1s or of other grids. [`get_remote_account_meta`] tells
+//! a program whether one of its instruction accounts is such a mirror, and at which slot of its
+//! chain it was read, so that e.g. an oracle can reject stale mirrored data.
+
+use crate::{clock::Slot, pubkey::Pubkey};
+
+/// The chain an account comes from.
+#[repr(u8)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteNamespace {
+    /// The state of this chain.
+    #[default]
+    Local,
+    /// The base layer of this chain.
+    BaseLayer,
+    /// Another Solana L1, served by a hypergrid node.
+    L1,
+    /// Another grid, served by a hypergrid node.
+    Grid,
+}
+
+/// Where an instruction account comes from, as returned by the
+/// `sol_get_remote_account_meta` syscall.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteAccountMeta {
+    /// Whether the account is a read-only mirror rather than the state of this chain. Accounts
+    /// cloned into genesis are the state of this chain, but keep their origin.
+    pub remote: bool,
+    /// The chain the account was loaded from.
+    pub namespace: RemoteNamespace,
+    /// The hypergrid node serving `namespace`, the default pubkey for the local state and the
+    /// base layer.
+    pub source: Pubkey,
+    /// The slot of its chain the account was read at, 0 if unknown.
+    pub source_slot: Slot,
+}
+
+impl RemoteAccountMeta {
+    /// The slot of its chain the account was read at, if known.
+    pub fn source_slot(&self) -> Option<Slot> {
+        (self.source_slot != 0).then_some(self.source_slot)
+    }
+
+    /// Whether the account was read before `min_source_slot` of its chain, or at an unknown
+    /// slot. Accounts of the local state are never stale.
+    pub fn is_stale(&self, min_source_slot: Slot) -> bool {
+        self.namespace != RemoteNamespace::Local
+            && self
+                .source_slot()
+                .map_or(true, |source_slot| source_slot < min_source_slot)
+    }
+}
+
+/// Get where the instruction account at `account_index` comes from.
+///
+/// `account_index` is the index of the account in the instruction, the same as in the
+/// `accounts` given to the entrypoint. The program fails if there is no such account.
+pub fn get_remote_account_meta(account_index: usize) -> RemoteAccountMeta {
+    #[cfg(target_os = "solana")]
+    {
+        let mut meta = RemoteAccountMeta::default();
+        unsafe {
+            crate::syscalls::sol_get_remote_account_meta(account_index as u64, &mut meta);
+        }
+        meta
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    crate::program_stubs::sol_get_remote_account_meta(account_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_account_meta_is_stale() {
+        let local = RemoteAccountMeta::default();
+        assert!(!local.is_stale(u64::MAX));
+
+        let mirror = RemoteAccountMeta {
+            remote: true,
+            namespace: RemoteNamespace::BaseLayer,
+            source: Pubkey::default(),
+            source_slot: 42,
+        };
+        assert_eq!(mirror.source_slot(), Some(42));
+        assert!(!mirror.is_stale(42));
+        assert!(mirror.is_stale(43));
+
+        let unknown_slot = RemoteAccountMeta {
+            source_slot: 0,
+            ..mirror
+        };
+        assert_eq!(unknown_slot.source_slot(), None);
+        assert!(unknown_slot.is_stale(0));
+    }
+}
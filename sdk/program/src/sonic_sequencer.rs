@@ -0,0 +1,42 @@
+//! Sonic: the sequencer cluster mode.
+//!
+//! A grid created with a [`SequencerConfig`] in genesis has a single leader, the sequencer,
+//! for every slot. Its blocks are rooted as soon as they are replayed rather than through
+//! Tower BFT votes, so the vote program and vote costs are disabled, and followers only
+//! accept blocks signed by the sequencer.
+
+use {
+    crate::pubkey::Pubkey,
+    serde::{Deserialize, Serialize},
+};
+
+/// The address genesis keeps the sequencer config at.
+pub mod config {
+    crate::declare_id!("SonicSequencerConfig11111111111111111111111");
+}
+
+/// Size of a serialized [`SequencerConfig`]
+pub const SEQUENCER_CONFIG_SIZE: usize = 32;
+
+/// The sequencer of a grid, owned by the config program so that it can't be modified
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SequencerConfig {
+    /// Identity of the only leader of the grid
+    pub sequencer: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequencer_config_size() {
+        let config = SequencerConfig {
+            sequencer: Pubkey::new_unique(),
+        };
+        assert_eq!(
+            bincode::serialized_size(&config).unwrap() as usize,
+            SEQUENCER_CONFIG_SIZE
+        );
+    }
+}
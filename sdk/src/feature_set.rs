@@ -780,6 +780,14 @@ pub mod deprecate_unused_legacy_vote_plumbing {
     solana_sdk::declare_id!("6Uf8S75PVh91MYgPQSHnjRAPQq6an5BDv9vomrCwDqLe");
 }
 
+pub mod sonic_fee_multiplier_governance {
+    solana_sdk::declare_id!("B7hf5aX7AgyqmJ7EpLuLEEMg1eVqWdHh8MaL2DoMPme4");
+}
+
+pub mod remote_account_meta_syscall_enabled {
+    solana_sdk::declare_id!("9ZmAZtY98fmZNLJmtVJJ1vs7jXisE5SY7F6rrau9v4Ep");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -970,6 +978,8 @@ lazy_static! {
         (enable_gossip_duplicate_proof_ingestion::id(), "enable gossip duplicate proof ingestion #32963"),
         (enable_chained_merkle_shreds::id(), "Enable chained Merkle shreds #34916"),
         (deprecate_unused_legacy_vote_plumbing::id(), "Deprecate unused legacy vote tx plumbing"),
+        (sonic_fee_multiplier_governance::id(), "Sonic: fee multiplier governance"),
+        (remote_account_meta_syscall_enabled::id(), "Sonic: syscall reading where remote accounts were loaded from"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -1,8 +1,13 @@
 //! Fee structures.
 
-use crate::native_token::sol_to_lamports;
 #[cfg(not(target_os = "solana"))]
 use solana_program::message::SanitizedMessage;
+use {
+    crate::native_token::sol_to_lamports,
+    solana_program::sonic_fee_settlement::state::{
+        FEE_MULTIPLIER_DENOMINATOR, MAX_FEE_MULTIPLIER, MIN_FEE_MULTIPLIER,
+    },
+};
 
 /// A fee and its associated compute unit limit
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -29,8 +34,9 @@ pub struct FeeStructure {
     pub lamports_per_write_lock: u64,
     /// Compute unit fee bins
     pub compute_fee_bins: Vec<FeeBin>,
-    ///Sonic: congestion multiplier
-    fee_multiplier: u32,
+    ///Sonic: congestion multiplier, in units of `1 / FEE_MULTIPLIER_DENOMINATOR`.
+    /// Banks override it with the multiplier configured on chain.
+    pub fee_multiplier: u32,
 }
 
 pub const ACCOUNT_DATA_COST_PAGE_SIZE: u64 = 32_u64.saturating_mul(1024);
@@ -49,24 +55,29 @@ impl FeeStructure {
             })
             .collect::<Vec<_>>();
 
-        //Sonic: get fee multiplier from environment variable
-        let fee_multiplier: Option<&'static str> = option_env!("SONIC_FEE_MULTIPLIER");
-        let fee_multiplier = match fee_multiplier.unwrap_or("10000").parse() {
-            Ok(f) => f,
-            Err(_) => 10000,
-        };
-        if fee_multiplier < 1000 || fee_multiplier > 100000 {
-            panic!("environment parameter \"SONIC_FEE_MULTIPLIER\" should be between 1000 and 100000.");
-        }
-        
         FeeStructure {
             lamports_per_signature: sol_to_lamports(sol_per_signature),
             lamports_per_write_lock: sol_to_lamports(sol_per_write_lock),
             compute_fee_bins,
-            fee_multiplier
+            fee_multiplier: Self::default_fee_multiplier(),
         }
     }
 
+    /// Sonic: the multiplier used until a bank applies the on-chain one.
+    /// `SONIC_FEE_MULTIPLIER` is only a build-time fallback and is clamped
+    /// into the allowed range.
+    pub fn default_fee_multiplier() -> u32 {
+        option_env!("SONIC_FEE_MULTIPLIER")
+            .and_then(|fee_multiplier| fee_multiplier.parse::<u32>().ok())
+            .unwrap_or(FEE_MULTIPLIER_DENOMINATOR)
+            .clamp(MIN_FEE_MULTIPLIER, MAX_FEE_MULTIPLIER)
+    }
+
+    /// Sonic: set the fee multiplier, clamped into the allowed range.
+    pub fn set_fee_multiplier(&mut self, fee_multiplier: u32) {
+        self.fee_multiplier = fee_multiplier.clamp(MIN_FEE_MULTIPLIER, MAX_FEE_MULTIPLIER);
+    }
+
     pub fn get_max_fee(&self, num_signatures: u64, num_write_locks: u64) -> u64 {
         num_signatures
             .saturating_mul(self.lamports_per_signature)
@@ -104,7 +115,7 @@ impl FeeStructure {
         } else {
             // 1.0 // multiplier that has no effect
             //Sonic: custom congestion multiplier
-            self.fee_multiplier as f64 / 10000 as f64
+            self.fee_multiplier as f64 / FEE_MULTIPLIER_DENOMINATOR as f64
         };
         
         let signature_fee = message
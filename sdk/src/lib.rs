@@ -57,7 +57,10 @@ pub use solana_program::{
     syscalls, system_instruction, system_program, sysvar, unchecked_div_by_const, vote,
     wasm_bindgen,
     sonic_account_migrater,
+    sonic_bridge,
     sonic_fee_settlement,
+    sonic_remote_account,
+    sonic_sequencer,
 };
 
 pub mod account;
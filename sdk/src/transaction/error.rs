@@ -169,6 +169,43 @@ pub enum TransactionError {
     /// The total balance before the transaction does not equal the total balance after the transaction
     #[error("Sum of account balances before and after transaction do not match")]
     UnbalancedTransaction,
+
+    /// Sonic: A remote account the transaction migrates could not be loaded from its source
+    #[error("Remote account at index {account_index} is unavailable: {reason}")]
+    RemoteAccountUnavailable {
+        account_index: u8,
+        reason: RemoteAccountUnavailableReason,
+    },
+}
+
+/// Sonic: Reasons a remote account could not be loaded from its source.
+#[derive(
+    Error, Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, AbiExample, AbiEnumVisitor,
+)]
+pub enum RemoteAccountUnavailableReason {
+    /// The account does not exist on its source
+    #[error("the account does not exist on its source")]
+    NotFound,
+
+    /// The source of the account could not be reached
+    #[error("the source of the account could not be reached")]
+    SourceUnreachable,
+
+    /// The account was requested from a node that serves no accounts
+    #[error("the source node serves no accounts")]
+    InvalidSource,
+
+    /// The source returned account data that could not be decoded
+    #[error("the source returned invalid account data")]
+    InvalidData,
+
+    /// The copies of the account fetched from independent sources disagree
+    #[error("the account was rejected by the quorum of sources")]
+    QuorumRejected,
+
+    /// The account is already loaded from a source chain that takes precedence
+    #[error("the account is already loaded from a source chain that takes precedence")]
+    NamespaceConflict,
 }
 
 impl From<SanitizeError> for TransactionError {
@@ -8,12 +8,13 @@ use {
     crate::{
         account::WritableAccount,
         rent::Rent,
+        sonic_remote_account::RemoteAccountMeta,
         system_instruction::{
             MAX_PERMITTED_ACCOUNTS_DATA_ALLOCATIONS_PER_TRANSACTION, MAX_PERMITTED_DATA_LENGTH,
         },
     },
     solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE,
-    std::mem::MaybeUninit,
+    std::{collections::HashMap, mem::MaybeUninit},
 };
 use {
     crate::{
@@ -144,6 +145,9 @@ pub struct TransactionContext {
     accounts_resize_delta: RefCell<i64>,
     #[cfg(not(target_os = "solana"))]
     rent: Rent,
+    /// Sonic: where the accounts loaded from a remote, or cloned from one into genesis, come from
+    #[cfg(not(target_os = "solana"))]
+    remote_account_metas: HashMap<IndexOfAccount, RemoteAccountMeta>,
     /// Useful for debugging to filter by or to look it up on the explorer
     #[cfg(all(not(target_os = "solana"), debug_assertions))]
     signature: Signature,
@@ -172,6 +176,7 @@ impl TransactionContext {
             return_data: TransactionReturnData::default(),
             accounts_resize_delta: RefCell::new(0),
             rent,
+            remote_account_metas: HashMap::new(),
             #[cfg(all(not(target_os = "solana"), debug_assertions))]
             signature: Signature::default(),
         }
@@ -206,6 +211,36 @@ impl TransactionContext {
         &self.signature
     }
 
+    /// Sonic: records where the account at `index_in_transaction` comes from
+    #[cfg(not(target_os = "solana"))]
+    pub fn set_remote_account_meta(
+        &mut self,
+        index_in_transaction: IndexOfAccount,
+        meta: RemoteAccountMeta,
+    ) {
+        self.remote_account_metas.insert(index_in_transaction, meta);
+    }
+
+    /// Sonic: returns where the account at `index_in_transaction` comes from, the local state
+    /// unless recorded otherwise
+    #[cfg(not(target_os = "solana"))]
+    pub fn get_remote_account_meta(
+        &self,
+        index_in_transaction: IndexOfAccount,
+    ) -> Result<RemoteAccountMeta, InstructionError> {
+        let remote = self
+            .get_account_at_index(index_in_transaction)?
+            .try_borrow()
+            .map_err(|_| InstructionError::AccountBorrowFailed)?
+            .remote;
+        let meta = self
+            .remote_account_metas
+            .get(&index_in_transaction)
+            .copied()
+            .unwrap_or_default();
+        Ok(RemoteAccountMeta { remote, ..meta })
+    }
+
     /// Returns the total number of accounts loaded in this Transaction
     pub fn get_number_of_accounts(&self) -> IndexOfAccount {
         self.accounts.len() as IndexOfAccount
@@ -979,6 +979,7 @@ mod tests {
                 loaded_addresses: LoadedAddresses::default(),
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: Some(1234),
+                remote_accounts: None,
             },
         });
         let expected_block = ConfirmedBlock {
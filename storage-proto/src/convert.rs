@@ -11,13 +11,16 @@ use {
         },
         pubkey::Pubkey,
         signature::Signature,
-        transaction::{Transaction, TransactionError, VersionedTransaction},
+        transaction::{
+            RemoteAccountUnavailableReason, Transaction, TransactionError, VersionedTransaction,
+        },
         transaction_context::TransactionReturnData,
     },
     solana_transaction_status::{
         ConfirmedBlock, EntrySummary, InnerInstruction, InnerInstructions, Reward, RewardType,
-        TransactionByAddrInfo, TransactionStatusMeta, TransactionTokenBalance,
-        TransactionWithStatusMeta, VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
+        TransactionByAddrInfo, TransactionRemoteAccount, TransactionStatusMeta,
+        TransactionTokenBalance, TransactionWithStatusMeta, VersionedConfirmedBlock,
+        VersionedTransactionWithStatusMeta,
     },
     std::{
         convert::{TryFrom, TryInto},
@@ -374,6 +377,7 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         } = value;
         let err = match status {
             Ok(()) => None,
@@ -416,6 +420,11 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             .collect();
         let return_data_none = return_data.is_none();
         let return_data = return_data.map(|return_data| return_data.into());
+        let remote_accounts = remote_accounts
+            .unwrap_or_default()
+            .into_iter()
+            .map(|remote_account| remote_account.into())
+            .collect();
 
         Self {
             err,
@@ -434,6 +443,7 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            remote_accounts,
         }
     }
 }
@@ -466,6 +476,7 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            remote_accounts,
         } = value;
         let status = match &err {
             None => Ok(()),
@@ -522,6 +533,12 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
         } else {
             return_data.map(|return_data| return_data.into())
         };
+        let remote_accounts = (!remote_accounts.is_empty()).then(|| {
+            remote_accounts
+                .into_iter()
+                .map(|remote_account| remote_account.into())
+                .collect()
+        });
         Ok(Self {
             status,
             fee,
@@ -535,6 +552,7 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         })
     }
 }
@@ -641,6 +659,28 @@ impl From<generated::ReturnData> for TransactionReturnData {
     }
 }
 
+impl From<TransactionRemoteAccount> for generated::RemoteAccount {
+    fn from(value: TransactionRemoteAccount) -> Self {
+        Self {
+            pubkey: <Pubkey as AsRef<[u8]>>::as_ref(&value.pubkey).into(),
+            source: value
+                .source
+                .map(|source| <Pubkey as AsRef<[u8]>>::as_ref(&source).into()),
+            source_slot: value.source_slot,
+        }
+    }
+}
+
+impl From<generated::RemoteAccount> for TransactionRemoteAccount {
+    fn from(value: generated::RemoteAccount) -> Self {
+        Self {
+            pubkey: Pubkey::try_from(value.pubkey).unwrap(),
+            source: value.source.map(|source| Pubkey::try_from(source).unwrap()),
+            source_slot: value.source_slot,
+        }
+    }
+}
+
 impl From<CompiledInstruction> for generated::CompiledInstruction {
     fn from(value: CompiledInstruction) -> Self {
         Self {
@@ -780,6 +820,21 @@ impl TryFrom<tx_by_addr::TransactionError> for TransactionError {
                         account_index: transaction_details.index as u8,
                     });
                 }
+                37 => {
+                    let reason = match transaction_details.remote_account_unavailable_reason {
+                        0 => RemoteAccountUnavailableReason::NotFound,
+                        1 => RemoteAccountUnavailableReason::SourceUnreachable,
+                        2 => RemoteAccountUnavailableReason::InvalidSource,
+                        3 => RemoteAccountUnavailableReason::InvalidData,
+                        4 => RemoteAccountUnavailableReason::QuorumRejected,
+                        5 => RemoteAccountUnavailableReason::NamespaceConflict,
+                        _ => return Err("Invalid RemoteAccountUnavailableReason"),
+                    };
+                    return Ok(TransactionError::RemoteAccountUnavailable {
+                        account_index: transaction_details.index as u8,
+                        reason,
+                    });
+                }
                 _ => {}
             }
         }
@@ -936,6 +991,9 @@ impl From<TransactionError> for tx_by_addr::TransactionError {
                 TransactionError::UnbalancedTransaction => {
                     tx_by_addr::TransactionErrorType::UnbalancedTransaction
                 }
+                TransactionError::RemoteAccountUnavailable { .. } => {
+                    tx_by_addr::TransactionErrorType::RemoteAccountUnavailable
+                }
             } as i32,
             instruction_error: match transaction_error {
                 TransactionError::InstructionError(index, ref instruction_error) => {
@@ -1117,18 +1175,47 @@ impl From<TransactionError> for tx_by_addr::TransactionError {
                 TransactionError::DuplicateInstruction(index) => {
                     Some(tx_by_addr::TransactionDetails {
                         index: index as u32,
+                        ..tx_by_addr::TransactionDetails::default()
                     })
                 }
                 TransactionError::InsufficientFundsForRent { account_index } => {
                     Some(tx_by_addr::TransactionDetails {
                         index: account_index as u32,
+                        ..tx_by_addr::TransactionDetails::default()
                     })
                 }
                 TransactionError::ProgramExecutionTemporarilyRestricted { account_index } => {
                     Some(tx_by_addr::TransactionDetails {
                         index: account_index as u32,
+                        ..tx_by_addr::TransactionDetails::default()
                     })
                 }
+                TransactionError::RemoteAccountUnavailable {
+                    account_index,
+                    reason,
+                } => Some(tx_by_addr::TransactionDetails {
+                    index: account_index as u32,
+                    remote_account_unavailable_reason: match reason {
+                        RemoteAccountUnavailableReason::NotFound => {
+                            tx_by_addr::RemoteAccountUnavailableReason::NotFound
+                        }
+                        RemoteAccountUnavailableReason::SourceUnreachable => {
+                            tx_by_addr::RemoteAccountUnavailableReason::SourceUnreachable
+                        }
+                        RemoteAccountUnavailableReason::InvalidSource => {
+                            tx_by_addr::RemoteAccountUnavailableReason::InvalidSource
+                        }
+                        RemoteAccountUnavailableReason::InvalidData => {
+                            tx_by_addr::RemoteAccountUnavailableReason::InvalidData
+                        }
+                        RemoteAccountUnavailableReason::QuorumRejected => {
+                            tx_by_addr::RemoteAccountUnavailableReason::QuorumRejected
+                        }
+                        RemoteAccountUnavailableReason::NamespaceConflict => {
+                            tx_by_addr::RemoteAccountUnavailableReason::NamespaceConflict
+                        }
+                    } as i32,
+                }),
 
                 _ => None,
             },
@@ -1250,6 +1337,36 @@ mod test {
         assert_eq!(reward, gen_reward.into());
     }
 
+    #[test]
+    fn test_transaction_status_meta_remote_accounts_encode() {
+        let mut meta = TransactionStatusMeta {
+            remote_accounts: Some(vec![
+                TransactionRemoteAccount {
+                    pubkey: Pubkey::new_unique(),
+                    source: None,
+                    source_slot: Some(42),
+                },
+                TransactionRemoteAccount {
+                    pubkey: Pubkey::new_unique(),
+                    source: Some(Pubkey::new_unique()),
+                    source_slot: None,
+                },
+            ]),
+            // Always decoded as present
+            pre_token_balances: Some(vec![]),
+            post_token_balances: Some(vec![]),
+            rewards: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        };
+        let gen_meta: generated::TransactionStatusMeta = meta.clone().into();
+        assert_eq!(meta, gen_meta.try_into().unwrap());
+
+        meta.remote_accounts = None;
+        let gen_meta: generated::TransactionStatusMeta = meta.clone().into();
+        assert!(gen_meta.remote_accounts.is_empty());
+        assert_eq!(meta, gen_meta.try_into().unwrap());
+    }
+
     #[test]
     fn test_transaction_by_addr_encode() {
         let info = TransactionByAddrInfo {
@@ -1851,6 +1968,17 @@ mod test {
             transaction_error,
             tx_by_addr_transaction_error.try_into().unwrap()
         );
+
+        let transaction_error = TransactionError::RemoteAccountUnavailable {
+            account_index: 10,
+            reason: RemoteAccountUnavailableReason::QuorumRejected,
+        };
+        let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
+            transaction_error.clone().into();
+        assert_eq!(
+            transaction_error,
+            tx_by_addr_transaction_error.try_into().unwrap()
+        );
     }
 
     #[test]
@@ -1861,12 +1989,14 @@ mod test {
             match error {
                 tx_by_addr::TransactionErrorType::DuplicateInstruction
                 | tx_by_addr::TransactionErrorType::InsufficientFundsForRent
-                | tx_by_addr::TransactionErrorType::ProgramExecutionTemporarilyRestricted => {
+                | tx_by_addr::TransactionErrorType::ProgramExecutionTemporarilyRestricted
+                | tx_by_addr::TransactionErrorType::RemoteAccountUnavailable => {
                     let tx_by_addr_error = tx_by_addr::TransactionError {
                         transaction_error: error as i32,
                         instruction_error: None,
                         transaction_details: Some(tx_by_addr::TransactionDetails {
                             index: ix_index,
+                            ..tx_by_addr::TransactionDetails::default()
                         }),
                     };
                     let transaction_error: TransactionError = tx_by_addr_error
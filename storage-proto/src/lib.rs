@@ -9,7 +9,8 @@ use {
         transaction_context::TransactionReturnData,
     },
     solana_transaction_status::{
-        InnerInstructions, Reward, RewardType, TransactionStatusMeta, TransactionTokenBalance,
+        InnerInstructions, Reward, RewardType, TransactionRemoteAccount, TransactionStatusMeta,
+        TransactionTokenBalance,
     },
     std::str::FromStr,
 };
@@ -178,6 +179,8 @@ pub struct StoredTransactionStatusMeta {
     pub return_data: Option<TransactionReturnData>,
     #[serde(deserialize_with = "default_on_eof")]
     pub compute_units_consumed: Option<u64>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub remote_accounts: Option<Vec<TransactionRemoteAccount>>,
 }
 
 impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
@@ -194,6 +197,7 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             rewards,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         } = value;
         Self {
             status,
@@ -211,6 +215,7 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data,
             compute_units_consumed,
+            remote_accounts,
         }
     }
 }
@@ -231,6 +236,7 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            remote_accounts,
         } = value;
 
         if !loaded_addresses.is_empty() {
@@ -256,6 +262,7 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
                 .map(|rewards| rewards.into_iter().map(|reward| reward.into()).collect()),
             return_data,
             compute_units_consumed,
+            remote_accounts,
         })
     }
 }
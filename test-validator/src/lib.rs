@@ -57,6 +57,7 @@ use {
     solana_tpu_client::tpu_client::{
         DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_TPU_ENABLE_UDP, DEFAULT_TPU_USE_QUIC,
     },
+    sonic_hypergrid::remote_source::{RemoteAccountSource, StaticRemoteSource},
     std::{
         collections::{HashMap, HashSet},
         ffi::OsStr,
@@ -147,6 +148,8 @@ pub struct TestValidatorGenesis {
     pub tpu_enable_udp: bool,
     pub geyser_plugin_manager: Arc<RwLock<GeyserPluginManager>>,
     admin_rpc_service_post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
+    remote_accounts: Vec<(Pubkey, AccountSharedData, Option<Pubkey>)>,
+    remote_source: Option<Arc<dyn RemoteAccountSource>>,
 }
 
 impl Default for TestValidatorGenesis {
@@ -182,6 +185,8 @@ impl Default for TestValidatorGenesis {
             geyser_plugin_manager: Arc::new(RwLock::new(GeyserPluginManager::new())),
             admin_rpc_service_post_init:
                 Arc::<RwLock<Option<AdminRpcRequestMetadataPostInit>>>::default(),
+            remote_accounts: Vec::<(Pubkey, AccountSharedData, Option<Pubkey>)>::default(),
+            remote_source: Option::<Arc<dyn RemoteAccountSource>>::default(),
         }
     }
 }
@@ -295,6 +300,26 @@ impl TestValidatorGenesis {
         self
     }
 
+    /// Add an account held by the remote `source` node, or by the base layer if `source` is
+    /// `None`. The account is only loaded once migrated by a `MigrateRemoteAccounts` or
+    /// `MigrateSourceAccounts` instruction.
+    pub fn add_remote_account(
+        &mut self,
+        address: Pubkey,
+        account: AccountSharedData,
+        source: Option<Pubkey>,
+    ) -> &mut Self {
+        self.remote_accounts.push((address, account, source));
+        self
+    }
+
+    /// Migrate remote accounts from `remote_source` instead of the base layer and HSSN.
+    /// Accounts added with `add_remote_account` take precedence over the ones of `remote_source`.
+    pub fn remote_source(&mut self, remote_source: Arc<dyn RemoteAccountSource>) -> &mut Self {
+        self.remote_source = Some(remote_source);
+        self
+    }
+
     pub fn add_accounts<T>(&mut self, accounts: T) -> &mut Self
     where
         T: IntoIterator<Item = (Pubkey, AccountSharedData)>,
@@ -1037,6 +1062,16 @@ impl TestValidator {
         if let Some(ref tower_storage) = config.tower_storage {
             validator_config.tower_storage = tower_storage.clone();
         }
+        if !config.remote_accounts.is_empty() || config.remote_source.is_some() {
+            let remote_source = match &config.remote_source {
+                Some(remote_source) => StaticRemoteSource::new_with_fallback(remote_source.clone()),
+                None => StaticRemoteSource::new(),
+            };
+            for (address, account, source) in &config.remote_accounts {
+                remote_source.add_account(*address, account.clone(), *source);
+            }
+            validator_config.remote_source = Some(Arc::new(remote_source));
+        }
 
         let validator = Some(Validator::new(
             node,
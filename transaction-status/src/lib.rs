@@ -43,6 +43,8 @@ pub mod parse_address_lookup_table;
 pub mod parse_associated_token;
 pub mod parse_bpf_loader;
 pub mod parse_instruction;
+pub mod parse_sonic_account_migrater;
+pub mod parse_sonic_fee_settlement;
 pub mod parse_stake;
 pub mod parse_system;
 pub mod parse_token;
@@ -348,6 +350,35 @@ impl From<TransactionTokenBalance> for UiTransactionTokenBalance {
     }
 }
 
+/// Sonic: an account of a transaction served from the remote cache, and where it was read
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionRemoteAccount {
+    pub pubkey: Pubkey,
+    /// The hypergrid node the account was loaded from, `None` for the base layer
+    pub source: Option<Pubkey>,
+    /// The slot of its chain the account was read at, if known
+    pub source_slot: Option<Slot>,
+}
+
+/// A duplicate representation of TransactionRemoteAccount for pretty JSON serialization
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTransactionRemoteAccount {
+    pub pubkey: String,
+    pub source: Option<String>,
+    pub source_slot: Option<Slot>,
+}
+
+impl From<TransactionRemoteAccount> for UiTransactionRemoteAccount {
+    fn from(remote_account: TransactionRemoteAccount) -> Self {
+        Self {
+            pubkey: remote_account.pubkey.to_string(),
+            source: remote_account.source.as_ref().map(ToString::to_string),
+            source_slot: remote_account.source_slot,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct TransactionStatusMeta {
     pub status: TransactionResult<()>,
@@ -362,6 +393,8 @@ pub struct TransactionStatusMeta {
     pub loaded_addresses: LoadedAddresses,
     pub return_data: Option<TransactionReturnData>,
     pub compute_units_consumed: Option<u64>,
+    /// Sonic: the accounts served from the remote cache, `None` if there were none
+    pub remote_accounts: Option<Vec<TransactionRemoteAccount>>,
 }
 
 impl Default for TransactionStatusMeta {
@@ -379,6 +412,7 @@ impl Default for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data: None,
             compute_units_consumed: None,
+            remote_accounts: None,
         }
     }
 }
@@ -432,6 +466,11 @@ pub struct UiTransactionStatusMeta {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub compute_units_consumed: OptionSerializer<u64>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub remote_accounts: OptionSerializer<Vec<UiTransactionRemoteAccount>>,
 }
 
 /// A duplicate representation of LoadedAddresses
@@ -491,6 +530,10 @@ impl UiTransactionStatusMeta {
                 meta.return_data.map(|return_data| return_data.into()),
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+            remote_accounts: OptionSerializer::or_skip(
+                meta.remote_accounts
+                    .map(|remote_accounts| remote_accounts.into_iter().map(Into::into).collect()),
+            ),
         }
     }
 
@@ -519,6 +562,7 @@ impl UiTransactionStatusMeta {
             loaded_addresses: OptionSerializer::Skip,
             return_data: OptionSerializer::Skip,
             compute_units_consumed: OptionSerializer::Skip,
+            remote_accounts: OptionSerializer::Skip,
         }
     }
 }
@@ -550,6 +594,10 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
                 meta.return_data.map(|return_data| return_data.into()),
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+            remote_accounts: OptionSerializer::or_skip(
+                meta.remote_accounts
+                    .map(|remote_accounts| remote_accounts.into_iter().map(Into::into).collect()),
+            ),
         }
     }
 }
@@ -1531,6 +1579,7 @@ mod test {
             },
             return_data: None,
             compute_units_consumed: None,
+            remote_accounts: None,
         };
         let expected_json_output_value: serde_json::Value = serde_json::from_str(
             "{\
@@ -1584,4 +1633,56 @@ mod test {
             expected_json_output_value
         );
     }
+
+    #[test]
+    fn test_ui_transaction_status_meta_remote_accounts_serialization() {
+        let base_layer_account = Pubkey::new_unique();
+        let grid_account = Pubkey::new_unique();
+        let grid_node = Pubkey::new_unique();
+        let meta = TransactionStatusMeta {
+            remote_accounts: Some(vec![
+                TransactionRemoteAccount {
+                    pubkey: base_layer_account,
+                    source: None,
+                    source_slot: Some(42),
+                },
+                TransactionRemoteAccount {
+                    pubkey: grid_account,
+                    source: Some(grid_node),
+                    source_slot: None,
+                },
+            ]),
+            ..TransactionStatusMeta::default()
+        };
+        let expected_remote_accounts = serde_json::json!([
+            {
+                "pubkey": base_layer_account.to_string(),
+                "source": null,
+                "sourceSlot": 42,
+            },
+            {
+                "pubkey": grid_account.to_string(),
+                "source": grid_node.to_string(),
+                "sourceSlot": null,
+            },
+        ]);
+
+        let ui_meta_from: UiTransactionStatusMeta = meta.clone().into();
+        assert_eq!(
+            serde_json::to_value(ui_meta_from).unwrap()["remoteAccounts"],
+            expected_remote_accounts
+        );
+        let ui_meta_parse = UiTransactionStatusMeta::parse(meta, &[], false);
+        assert_eq!(
+            serde_json::to_value(ui_meta_parse).unwrap()["remoteAccounts"],
+            expected_remote_accounts
+        );
+
+        let ui_meta_simple =
+            UiTransactionStatusMeta::build_simple(TransactionStatusMeta::default(), false);
+        assert!(serde_json::to_value(ui_meta_simple)
+            .unwrap()
+            .get("remoteAccounts")
+            .is_none());
+    }
 }
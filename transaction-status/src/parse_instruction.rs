@@ -4,6 +4,8 @@ use {
         parse_address_lookup_table::parse_address_lookup_table,
         parse_associated_token::{parse_associated_token, spl_associated_token_id},
         parse_bpf_loader::{parse_bpf_loader, parse_bpf_upgradeable_loader},
+        parse_sonic_account_migrater::parse_sonic_account_migrater,
+        parse_sonic_fee_settlement::parse_sonic_fee_settlement,
         parse_stake::parse_stake,
         parse_system::parse_system,
         parse_token::parse_token,
@@ -14,7 +16,7 @@ use {
     solana_account_decoder::parse_token::spl_token_ids,
     solana_sdk::{
         address_lookup_table, instruction::CompiledInstruction, message::AccountKeys,
-        pubkey::Pubkey, stake, system_program, vote,
+        pubkey::Pubkey, sonic_account_migrater, sonic_fee_settlement, stake, system_program, vote,
     },
     std::{
         collections::HashMap,
@@ -30,6 +32,8 @@ lazy_static! {
     static ref BPF_UPGRADEABLE_LOADER_PROGRAM_ID: Pubkey = solana_sdk::bpf_loader_upgradeable::id();
     static ref MEMO_V1_PROGRAM_ID: Pubkey = spl_memo_id_v1();
     static ref MEMO_V3_PROGRAM_ID: Pubkey = spl_memo_id_v3();
+    static ref SONIC_ACCOUNT_MIGRATER_PROGRAM_ID: Pubkey = sonic_account_migrater::program::id();
+    static ref SONIC_FEE_SETTLEMENT_PROGRAM_ID: Pubkey = sonic_fee_settlement::program::id();
     static ref STAKE_PROGRAM_ID: Pubkey = stake::program::id();
     static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
     static ref VOTE_PROGRAM_ID: Pubkey = vote::program::id();
@@ -53,6 +57,14 @@ lazy_static! {
             *BPF_UPGRADEABLE_LOADER_PROGRAM_ID,
             ParsableProgram::BpfUpgradeableLoader,
         );
+        m.insert(
+            *SONIC_ACCOUNT_MIGRATER_PROGRAM_ID,
+            ParsableProgram::SonicAccountMigrater,
+        );
+        m.insert(
+            *SONIC_FEE_SETTLEMENT_PROGRAM_ID,
+            ParsableProgram::SonicFeeSettlement,
+        );
         m.insert(*STAKE_PROGRAM_ID, ParsableProgram::Stake);
         m.insert(*SYSTEM_PROGRAM_ID, ParsableProgram::System);
         m.insert(*VOTE_PROGRAM_ID, ParsableProgram::Vote);
@@ -102,6 +114,8 @@ pub enum ParsableProgram {
     SplToken,
     BpfLoader,
     BpfUpgradeableLoader,
+    SonicAccountMigrater,
+    SonicFeeSettlement,
     Stake,
     System,
     Vote,
@@ -131,6 +145,12 @@ pub fn parse(
         ParsableProgram::BpfUpgradeableLoader => {
             serde_json::to_value(parse_bpf_upgradeable_loader(instruction, account_keys)?)?
         }
+        ParsableProgram::SonicAccountMigrater => {
+            serde_json::to_value(parse_sonic_account_migrater(instruction, account_keys)?)?
+        }
+        ParsableProgram::SonicFeeSettlement => {
+            serde_json::to_value(parse_sonic_fee_settlement(instruction, account_keys)?)?
+        }
         ParsableProgram::Stake => serde_json::to_value(parse_stake(instruction, account_keys)?)?,
         ParsableProgram::System => serde_json::to_value(parse_system(instruction, account_keys)?)?,
         ParsableProgram::Vote => serde_json::to_value(parse_vote(instruction, account_keys)?)?,
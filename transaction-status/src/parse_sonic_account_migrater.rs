@@ -0,0 +1,158 @@
+use {
+    crate::parse_instruction::{
+        check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
+    },
+    bincode::deserialize,
+    serde_json::json,
+    solana_sdk::{
+        instruction::CompiledInstruction, message::AccountKeys,
+        sonic_account_migrater::instruction::ProgramInstruction,
+    },
+};
+
+pub fn parse_sonic_account_migrater(
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+) -> Result<ParsedInstructionEnum, ParseInstructionError> {
+    let migrater_instruction: ProgramInstruction =
+        deserialize(&instruction.data).map_err(|_| {
+            ParseInstructionError::InstructionNotParsable(ParsableProgram::SonicAccountMigrater)
+        })?;
+    match instruction.accounts.iter().max() {
+        Some(index) if (*index as usize) < account_keys.len() => {}
+        _ => {
+            // Runtime should prevent this from ever happening
+            return Err(ParseInstructionError::InstructionKeyMismatch(
+                ParsableProgram::SonicAccountMigrater,
+            ));
+        }
+    }
+    check_num_sonic_account_migrater_accounts(&instruction.accounts, 2)?;
+    let addresses: Vec<String> = instruction.accounts[2..]
+        .iter()
+        .map(|index| account_keys[*index as usize].to_string())
+        .collect();
+    let mut value = json!({
+        "payer": account_keys[instruction.accounts[0] as usize].to_string(),
+        "systemProgram": account_keys[instruction.accounts[1] as usize].to_string(),
+        "addresses": addresses,
+    });
+    let map = value.as_object_mut().unwrap();
+    let instruction_type = match migrater_instruction {
+        ProgramInstruction::MigrateRemoteAccounts => "migrateRemoteAccounts",
+        ProgramInstruction::DeactivateRemoteAccounts => "deactivateRemoteAccounts",
+        ProgramInstruction::MigrateSourceAccounts { node_id, refresh } => {
+            map.insert("sourceNode".to_string(), json!(node_id.to_string()));
+            map.insert("refresh".to_string(), json!(refresh));
+            "migrateSourceAccounts"
+        }
+    };
+    Ok(ParsedInstructionEnum {
+        instruction_type: instruction_type.to_string(),
+        info: value,
+    })
+}
+
+fn check_num_sonic_account_migrater_accounts(
+    accounts: &[u8],
+    num: usize,
+) -> Result<(), ParseInstructionError> {
+    check_num_accounts(accounts, num, ParsableProgram::SonicAccountMigrater)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            message::Message, pubkey::Pubkey, sonic_account_migrater::instruction, system_program,
+        },
+    };
+
+    #[test]
+    fn test_parse_migrate_remote_accounts_ix() {
+        let payer = Pubkey::new_unique();
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let instruction = instruction::migrate_remote_accounts(payer, addresses.clone());
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_account_migrater(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "migrateRemoteAccounts".to_string(),
+                info: json!({
+                    "payer": payer.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "addresses": [addresses[0].to_string(), addresses[1].to_string()],
+                }),
+            }
+        );
+        assert!(parse_sonic_account_migrater(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..3], None)
+        )
+        .is_err());
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.truncate(1);
+        assert!(parse_sonic_account_migrater(
+            &message.instructions[0],
+            &AccountKeys::new(&keys, None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_deactivate_remote_accounts_ix() {
+        let payer = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+
+        let instruction = instruction::deactivate_remote_accounts(payer, vec![address]);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_account_migrater(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "deactivateRemoteAccounts".to_string(),
+                info: json!({
+                    "payer": payer.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "addresses": [address.to_string()],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_migrate_source_accounts_ix() {
+        let payer = Pubkey::new_unique();
+        let node_id = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+
+        let instruction = instruction::migrate_source_accounts(payer, node_id, true, vec![address]);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_account_migrater(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "migrateSourceAccounts".to_string(),
+                info: json!({
+                    "payer": payer.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "addresses": [address.to_string()],
+                    "sourceNode": node_id.to_string(),
+                    "refresh": true,
+                }),
+            }
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use {
+    crate::parse_instruction::{
+        check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
+    },
+    bincode::deserialize,
+    serde_json::json,
+    solana_sdk::{
+        instruction::CompiledInstruction,
+        message::AccountKeys,
+        sonic_fee_settlement::{instruction::ProgramInstruction, state::SettlementAccountType},
+    },
+};
+
+pub fn parse_sonic_fee_settlement(
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+) -> Result<ParsedInstructionEnum, ParseInstructionError> {
+    let settlement_instruction: ProgramInstruction =
+        deserialize(&instruction.data).map_err(|_| {
+            ParseInstructionError::InstructionNotParsable(ParsableProgram::SonicFeeSettlement)
+        })?;
+    match instruction.accounts.iter().max() {
+        Some(index) if (*index as usize) < account_keys.len() => {}
+        _ => {
+            // Runtime should prevent this from ever happening
+            return Err(ParseInstructionError::InstructionKeyMismatch(
+                ParsableProgram::SonicFeeSettlement,
+            ));
+        }
+    }
+    match settlement_instruction {
+        ProgramInstruction::InitializeAccount {
+            owner,
+            account_type,
+        } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 1)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "initializeAccount".to_string(),
+                info: json!({
+                    "settlementAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "owner": owner.to_string(),
+                    "accountType": account_type_name(&account_type),
+                }),
+            })
+        }
+        ProgramInstruction::SettleFeeBill {
+            from_id,
+            end_id,
+            bills,
+        } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 2)?;
+            let bills: Vec<_> = bills
+                .into_iter()
+                .map(|bill| {
+                    json!({
+                        "account": bill.key.to_string(),
+                        "amount": bill.amount,
+                    })
+                })
+                .collect();
+            Ok(ParsedInstructionEnum {
+                instruction_type: "settleFeeBill".to_string(),
+                info: json!({
+                    "settlementCursor": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "settler": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "fromId": from_id,
+                    "endId": end_id,
+                    "bills": bills,
+                }),
+            })
+        }
+        ProgramInstruction::WithdrawFeeBill { address, amount } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 1)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "withdrawFeeBill".to_string(),
+                info: json!({
+                    "settlementAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "owner": address.to_string(),
+                    "amount": amount,
+                }),
+            })
+        }
+        ProgramInstruction::InitializeSettlementCursor { bump_seed } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 5)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "initializeSettlementCursor".to_string(),
+                info: json!({
+                    "settlementCursor": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "settler": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "systemProgram": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "feeMultiplierConfig": account_keys[instruction.accounts[3] as usize].to_string(),
+                    "authority": account_keys[instruction.accounts[4] as usize].to_string(),
+                    "bumpSeed": bump_seed,
+                }),
+            })
+        }
+        ProgramInstruction::SetSettler { settler } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 2)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "setSettler".to_string(),
+                info: json!({
+                    "settlementCursor": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "settler": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "newSettler": settler.to_string(),
+                }),
+            })
+        }
+        ProgramInstruction::SetFeeMultiplier {
+            multiplier,
+            dynamic,
+        } => {
+            check_num_sonic_fee_settlement_accounts(&instruction.accounts, 2)?;
+            let mut value = json!({
+                "feeMultiplierConfig": account_keys[instruction.accounts[0] as usize].to_string(),
+                "authority": account_keys[instruction.accounts[1] as usize].to_string(),
+                "multiplier": multiplier,
+            });
+            let map = value.as_object_mut().unwrap();
+            if let Some(dynamic) = dynamic {
+                map.insert(
+                    "dynamic".to_string(),
+                    json!({
+                        "minMultiplier": dynamic.min_multiplier,
+                        "maxMultiplier": dynamic.max_multiplier,
+                        "targetUtilizationPercent": dynamic.target_utilization_percent,
+                    }),
+                );
+            }
+            Ok(ParsedInstructionEnum {
+                instruction_type: "setFeeMultiplier".to_string(),
+                info: value,
+            })
+        }
+    }
+}
+
+fn account_type_name(account_type: &SettlementAccountType) -> &'static str {
+    match account_type {
+        SettlementAccountType::BurnAccount => "burn",
+        SettlementAccountType::HSSNAccount => "hssn",
+        SettlementAccountType::SonicGridAccount => "sonicGrid",
+        SettlementAccountType::GridAccount => "grid",
+    }
+}
+
+fn check_num_sonic_fee_settlement_accounts(
+    accounts: &[u8],
+    num: usize,
+) -> Result<(), ParseInstructionError> {
+    check_num_accounts(accounts, num, ParsableProgram::SonicFeeSettlement)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_sdk::{
+            message::Message,
+            pubkey::Pubkey,
+            sonic_fee_settlement::{
+                instruction::{self, SettlementBillParam},
+                state::DynamicFeeMultiplier,
+            },
+            system_program,
+        },
+    };
+
+    #[test]
+    fn test_parse_initialize_account_ix() {
+        let settlement_address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let instruction = instruction::initialize_account(
+            settlement_address,
+            owner,
+            SettlementAccountType::GridAccount,
+        );
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "initializeAccount".to_string(),
+                info: json!({
+                    "settlementAccount": settlement_address.to_string(),
+                    "owner": owner.to_string(),
+                    "accountType": "grid",
+                }),
+            }
+        );
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.pop();
+        assert!(parse_sonic_fee_settlement(
+            &message.instructions[0],
+            &AccountKeys::new(&keys, None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_settle_fee_bill_ix() {
+        let settler = Pubkey::new_unique();
+        let grid_account = Pubkey::new_unique();
+        let bills = vec![SettlementBillParam {
+            key: grid_account,
+            amount: 42,
+        }];
+
+        let instruction = instruction::settle_fee_bill(settler, vec![grid_account], 3, 5, bills);
+        let mut message = Message::new(&[instruction], None);
+        let (cursor_address, _) = instruction::derive_settlement_cursor_address();
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "settleFeeBill".to_string(),
+                info: json!({
+                    "settlementCursor": cursor_address.to_string(),
+                    "settler": settler.to_string(),
+                    "fromId": 3,
+                    "endId": 5,
+                    "bills": [{
+                        "account": grid_account.to_string(),
+                        "amount": 42,
+                    }],
+                }),
+            }
+        );
+        assert!(parse_sonic_fee_settlement(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys[0..2], None)
+        )
+        .is_err());
+        let keys = message.account_keys.clone();
+        message.instructions[0].accounts.truncate(1);
+        assert!(parse_sonic_fee_settlement(
+            &message.instructions[0],
+            &AccountKeys::new(&keys, None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_withdraw_fee_bill_ix() {
+        let settlement_address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let instruction = instruction::withdraw_fee_bill(settlement_address, owner, 7);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "withdrawFeeBill".to_string(),
+                info: json!({
+                    "settlementAccount": settlement_address.to_string(),
+                    "owner": owner.to_string(),
+                    "amount": 7,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_settlement_cursor_ixs() {
+        let settler = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (instruction, cursor_address) =
+            instruction::initialize_settlement_cursor(settler, authority);
+        let (_, bump_seed) = instruction::derive_settlement_cursor_address();
+        let (config_address, _) = instruction::derive_fee_multiplier_address();
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "initializeSettlementCursor".to_string(),
+                info: json!({
+                    "settlementCursor": cursor_address.to_string(),
+                    "settler": settler.to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                    "feeMultiplierConfig": config_address.to_string(),
+                    "authority": authority.to_string(),
+                    "bumpSeed": bump_seed,
+                }),
+            }
+        );
+
+        let new_settler = Pubkey::new_unique();
+        let instruction = instruction::set_settler(settler, new_settler);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "setSettler".to_string(),
+                info: json!({
+                    "settlementCursor": cursor_address.to_string(),
+                    "settler": settler.to_string(),
+                    "newSettler": new_settler.to_string(),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_set_fee_multiplier_ix() {
+        let authority = Pubkey::new_unique();
+        let (config_address, _) = instruction::derive_fee_multiplier_address();
+
+        let instruction = instruction::set_fee_multiplier(authority, 20_000, None);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "setFeeMultiplier".to_string(),
+                info: json!({
+                    "feeMultiplierConfig": config_address.to_string(),
+                    "authority": authority.to_string(),
+                    "multiplier": 20_000,
+                }),
+            }
+        );
+
+        let dynamic = DynamicFeeMultiplier {
+            min_multiplier: 10_000,
+            max_multiplier: 30_000,
+            target_utilization_percent: 50,
+        };
+        let instruction = instruction::set_fee_multiplier(authority, 20_000, Some(dynamic));
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_sonic_fee_settlement(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "setFeeMultiplier".to_string(),
+                info: json!({
+                    "feeMultiplierConfig": config_address.to_string(),
+                    "authority": authority.to_string(),
+                    "multiplier": 20_000,
+                    "dynamic": {
+                        "minMultiplier": 10_000,
+                        "maxMultiplier": 30_000,
+                        "targetUtilizationPercent": 50,
+                    },
+                }),
+            }
+        );
+    }
+}
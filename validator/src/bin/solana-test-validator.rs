@@ -32,6 +32,7 @@ use {
         admin_rpc_service, cli, dashboard::Dashboard, ledger_lockfile, lock_ledger,
         println_name_value, redirect_stderr_to_file,
     },
+    sonic_hypergrid::remote_source::remote_source_from_arg,
     std::{
         collections::HashSet,
         fs, io,
@@ -464,6 +465,13 @@ fn main() {
         })
         .deactivate_features(&features_to_deactivate);
 
+    if let Some(remote_source) = matches.value_of("remote_source") {
+        genesis.remote_source(remote_source_from_arg(remote_source).unwrap_or_else(|err| {
+            println!("Error: invalid --remote-source: {err}");
+            exit(1);
+        }));
+    }
+
     genesis.rpc_config(JsonRpcConfig {
         enable_rpc_transaction_history: true,
         enable_extended_tx_metadata_storage: true,
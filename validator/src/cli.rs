@@ -1425,6 +1425,15 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                     further debugging.
                 ")
         )
+        .arg(
+            Arg::with_name("generate_fee_bills")
+                .long("generate-fee-bills")
+                .takes_value(false)
+                .help(
+                    "Aggregate the transaction fees of rooted slots into fee bills and settle \
+                     them with the settings in the `fee_bill` section of the Hypergrid config",
+                ),
+        )
         .args(&get_deprecated_arguments())
         .after_help("The default subcommand is run")
         .subcommand(
@@ -2467,6 +2476,22 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                      If the ledger already exists then this parameter is silently ignored",
                 ),
         )
+        .arg(
+            Arg::with_name("remote_source")
+                .long("remote-source")
+                .value_name("DIRECTORY_OR_URL")
+                .takes_value(true)
+                .validator(|value| {
+                    sonic_hypergrid::remote_source::remote_source_from_arg(&value).map(|_| ())
+                })
+                .help(
+                    "Migrate remote accounts from this source instead of the base layer and HSSN \
+                     of the hypergrid config. A URL is used as the RPC of every source node. \
+                     A directory holds base layer accounts as <ADDRESS>.json files, in the format \
+                     of `solana account --output json`, and the accounts of a source node in a \
+                     <NODE_ADDRESS> sub-directory",
+                ),
+        )
         .arg(
             Arg::with_name("warp_slot")
                 .required(false)
@@ -69,6 +69,7 @@ use {
         ledger_lockfile, lock_ledger, new_spinner_progress_bar, println_name_value,
         redirect_stderr_to_file,
     },
+    sonic_hypergrid::config::Config as HypergridConfig,
     std::{
         collections::{HashSet, VecDeque},
         env,
@@ -1440,6 +1441,15 @@ pub fn main() {
             use_snapshot_archives_at_startup::cli::NAME,
             UseSnapshotArchivesAtStartup
         ),
+        fee_bill_config: matches.is_present("generate_fee_bills").then(|| {
+            let config_path = HypergridConfig::default_path();
+            HypergridConfig::load(&config_path)
+                .unwrap_or_else(|err| {
+                    eprintln!("Failed to load Hypergrid config {config_path}: {err}");
+                    exit(1);
+                })
+                .fee_bill
+        }),
         ..ValidatorConfig::default()
     };
 
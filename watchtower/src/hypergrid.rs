@@ -0,0 +1,207 @@
+//! Sonic: checks of the services a grid depends on.
+//!
+//! A grid keeps producing blocks while HSSN or the base layer RPC are down, but it can no
+//! longer load the accounts it migrates, and the copies it serves go stale. These checks probe
+//! both endpoints of the hypergrid config, and compare the slots the base layer accounts used
+//! by recent grid transactions were read at with the current base layer slot.
+
+use {
+    log::*,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::{client_error, config::RpcBlockConfig},
+    solana_sdk::{clock::Slot, commitment_config::CommitmentConfig},
+    solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
+    sonic_hypergrid::{config::Config as HypergridConfig, remote_loader::RemoteAccountLoader},
+    std::time::Duration,
+};
+
+/// Number of recent grid slots the sampled blocks are looked for in
+const SAMPLE_SLOT_RANGE: Slot = 32;
+
+pub struct HypergridMonitor {
+    hssn_rpc_url: String,
+    hssn_loader: RemoteAccountLoader,
+    baselayer_rpc_client: RpcClient,
+    /// Alert when a base layer account was read more than this many slots ago
+    max_remote_slot_lag: Slot,
+    /// Number of recent grid blocks the remote accounts are sampled from
+    sample_blocks: usize,
+}
+
+impl HypergridMonitor {
+    pub fn new(
+        hypergrid_config: HypergridConfig,
+        rpc_timeout: Duration,
+        max_remote_slot_lag: Slot,
+        sample_blocks: usize,
+    ) -> Self {
+        let baselayer_rpc_client = RpcClient::new_with_timeout_and_commitment(
+            hypergrid_config.baselayer_rpc_url.clone(),
+            rpc_timeout,
+            CommitmentConfig::confirmed(),
+        );
+        info!("HSSN URL: {}", hypergrid_config.hssn_rpc_url);
+        info!("Base layer RPC URL: {}", hypergrid_config.baselayer_rpc_url);
+        Self {
+            hssn_rpc_url: hypergrid_config.hssn_rpc_url.clone(),
+            hssn_loader: RemoteAccountLoader::new_with_config(hypergrid_config),
+            baselayer_rpc_client,
+            max_remote_slot_lag,
+            sample_blocks,
+        }
+    }
+
+    /// Run the checks against the grid at `rpc_client`, returning their failures
+    pub fn check(&self, rpc_client: &RpcClient) -> Vec<(&'static str, String)> {
+        let mut failures = vec![];
+
+        match self.hssn_loader.get_hypergrid_nodes() {
+            Ok(nodes) => info!("Hypergrid nodes registered in HSSN: {}", nodes.len()),
+            Err(err) => failures.push((
+                "hssn",
+                format!("HSSN {} is unreachable: {err}", self.hssn_rpc_url),
+            )),
+        }
+
+        let baselayer_slot = match self.baselayer_rpc_client.get_slot() {
+            Ok(slot) => {
+                info!("Base layer slot: {}", slot);
+                slot
+            }
+            Err(err) => {
+                failures.push((
+                    "baselayer-rpc",
+                    format!(
+                        "Base layer RPC {} failed: {err}",
+                        self.baselayer_rpc_client.url()
+                    ),
+                ));
+                return failures;
+            }
+        };
+
+        match self.sample_remote_source_slots(rpc_client) {
+            Ok(source_slots) => {
+                info!("Sampled base layer accounts: {}", source_slots.len());
+                failures.extend(remote_slot_lag_failure(
+                    &source_slots,
+                    baselayer_slot,
+                    self.max_remote_slot_lag,
+                ));
+            }
+            Err(err) => warn!("Unable to sample remote accounts: {}", err),
+        }
+
+        failures
+    }
+
+    /// The base layer accounts used by the transactions of the latest grid blocks, with the
+    /// slot they were read at
+    fn sample_remote_source_slots(
+        &self,
+        rpc_client: &RpcClient,
+    ) -> client_error::Result<Vec<(String, Slot)>> {
+        let commitment = CommitmentConfig::confirmed();
+        let slot = rpc_client.get_slot_with_commitment(commitment)?;
+        let blocks = rpc_client.get_blocks_with_commitment(
+            slot.saturating_sub(SAMPLE_SLOT_RANGE),
+            Some(slot),
+            commitment,
+        )?;
+
+        let mut source_slots = vec![];
+        for block_slot in blocks.into_iter().rev().take(self.sample_blocks) {
+            let block = rpc_client.get_block_with_config(
+                block_slot,
+                RpcBlockConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    transaction_details: Some(TransactionDetails::Full),
+                    rewards: Some(false),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+            let remote_accounts = block
+                .transactions
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|transaction| transaction.meta)
+                .filter_map(|meta| Option::<Vec<_>>::from(meta.remote_accounts))
+                .flatten();
+            for remote_account in remote_accounts {
+                // Accounts served by hypergrid nodes are read at the slots of their own chain
+                if remote_account.source.is_some() {
+                    continue;
+                }
+                if let Some(source_slot) = remote_account.source_slot {
+                    source_slots.push((remote_account.pubkey, source_slot));
+                }
+            }
+        }
+        Ok(source_slots)
+    }
+}
+
+/// The account read the longest before `baselayer_slot`, and how many slots before
+fn most_lagging_account(
+    source_slots: &[(String, Slot)],
+    baselayer_slot: Slot,
+) -> Option<(&str, Slot)> {
+    source_slots
+        .iter()
+        .map(|(pubkey, source_slot)| (pubkey.as_str(), baselayer_slot.saturating_sub(*source_slot)))
+        .max_by_key(|(_, lag)| *lag)
+}
+
+/// The failure to report if an account was read more than `max_remote_slot_lag` slots before
+/// `baselayer_slot`
+fn remote_slot_lag_failure(
+    source_slots: &[(String, Slot)],
+    baselayer_slot: Slot,
+    max_remote_slot_lag: Slot,
+) -> Option<(&'static str, String)> {
+    let (pubkey, lag) = most_lagging_account(source_slots, baselayer_slot)
+        .filter(|(_, lag)| *lag > max_remote_slot_lag)?;
+    Some((
+        "remote-slot-lag",
+        format!("{pubkey} is {lag} slots behind the base layer"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_slots(slots: &[(&str, Slot)]) -> Vec<(String, Slot)> {
+        slots
+            .iter()
+            .map(|(pubkey, slot)| (pubkey.to_string(), *slot))
+            .collect()
+    }
+
+    #[test]
+    fn test_most_lagging_account() {
+        assert_eq!(most_lagging_account(&[], 100), None);
+
+        let slots = source_slots(&[("a", 90), ("b", 70), ("c", 95)]);
+        assert_eq!(most_lagging_account(&slots, 100), Some(("b", 30)));
+
+        // Accounts read after the sampled base layer slot don't lag
+        let slots = source_slots(&[("a", 105)]);
+        assert_eq!(most_lagging_account(&slots, 100), Some(("a", 0)));
+    }
+
+    #[test]
+    fn test_remote_slot_lag_failure() {
+        let slots = source_slots(&[("a", 90), ("b", 70)]);
+        assert_eq!(remote_slot_lag_failure(&slots, 100, 30), None);
+        assert_eq!(
+            remote_slot_lag_failure(&slots, 100, 29),
+            Some((
+                "remote-slot-lag",
+                "b is 30 slots behind the base layer".to_string()
+            ))
+        );
+        assert_eq!(remote_slot_lag_failure(&[], 100, 0), None);
+    }
+}
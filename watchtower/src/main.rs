@@ -1,7 +1,10 @@
 //! A command-line executable for monitoring the health of a cluster
 #![allow(clippy::arithmetic_side_effects)]
 
+mod hypergrid;
+
 use {
+    crate::hypergrid::HypergridMonitor,
     clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg},
     log::*,
     solana_clap_utils::{
@@ -19,6 +22,7 @@ use {
         native_token::{sol_to_lamports, Sol},
         pubkey::Pubkey,
     },
+    sonic_hypergrid::config::Config as HypergridConfig,
     std::{
         collections::HashMap,
         error,
@@ -39,6 +43,7 @@ struct Config {
     unhealthy_threshold: usize,
     validator_identity_pubkeys: Vec<Pubkey>,
     name_suffix: String,
+    hypergrid_monitor: Option<HypergridMonitor>,
 }
 
 fn get_config() -> Config {
@@ -160,6 +165,51 @@ fn get_config() -> Config {
                     no alerting should a Bad Gateway error be a side effect of \
                     the real problem")
         )
+        .arg(
+            Arg::with_name("monitor_hypergrid")
+                .long("monitor-hypergrid")
+                .takes_value(false)
+                .help("Sonic: alert when HSSN or the base layer RPC of the hypergrid config \
+                    are unreachable, or when the base layer accounts used by the grid lag \
+                    behind the base layer by more than --max-remote-slot-lag slots"),
+        )
+        .arg(
+            Arg::with_name("hssn_url")
+                .long("hssn-url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .requires("monitor_hypergrid")
+                .help("Sonic: HSSN REST endpoint [default: hssn_rpc_url of the hypergrid config file]"),
+        )
+        .arg(
+            Arg::with_name("baselayer_url")
+                .long("baselayer-url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .requires("monitor_hypergrid")
+                .help("Sonic: base layer JSON RPC URL [default: baselayer_rpc_url of the hypergrid config file]"),
+        )
+        .arg(
+            Arg::with_name("max_remote_slot_lag")
+                .long("max-remote-slot-lag")
+                .value_name("SLOTS")
+                .takes_value(true)
+                .default_value("1500")
+                .validator(is_parsable::<u64>)
+                .help("Sonic: alert when a base layer account used by the grid was read more \
+                    than this many slots before the current base layer slot"),
+        )
+        .arg(
+            Arg::with_name("remote_account_sample_blocks")
+                .long("remote-account-sample-blocks")
+                .value_name("COUNT")
+                .takes_value(true)
+                .default_value("4")
+                .validator(is_parsable::<usize>)
+                .help("Sonic: number of recent grid blocks the base layer accounts are sampled from"),
+        )
         .arg(
             Arg::with_name("name_suffix")
                 .long("name-suffix")
@@ -199,6 +249,29 @@ fn get_config() -> Config {
 
     let name_suffix = value_t_or_exit!(matches, "name_suffix", String);
 
+    let hypergrid_monitor = matches.is_present("monitor_hypergrid").then(|| {
+        let config_path = HypergridConfig::default_path();
+        let mut hypergrid_config = HypergridConfig::load(&config_path).unwrap_or_else(|err| {
+            warn!(
+                "Unable to load the hypergrid config {}: {}",
+                config_path, err
+            );
+            HypergridConfig::default()
+        });
+        if let Some(hssn_url) = matches.value_of("hssn_url") {
+            hypergrid_config.hssn_rpc_url = hssn_url.to_string();
+        }
+        if let Some(baselayer_url) = matches.value_of("baselayer_url") {
+            hypergrid_config.baselayer_rpc_url = baselayer_url.to_string();
+        }
+        HypergridMonitor::new(
+            hypergrid_config,
+            rpc_timeout,
+            value_t_or_exit!(matches, "max_remote_slot_lag", u64),
+            value_t_or_exit!(matches, "remote_account_sample_blocks", usize),
+        )
+    });
+
     let config = Config {
         address_labels: config.address_labels,
         ignore_http_bad_gateway,
@@ -211,6 +284,7 @@ fn get_config() -> Config {
         unhealthy_threshold,
         validator_identity_pubkeys,
         name_suffix,
+        hypergrid_monitor,
     };
 
     info!("RPC URL: {}", config.json_rpc_url);
@@ -357,6 +431,10 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                     failures.push(("delinquent", validator_errors.join(",")));
                 }
 
+                if let Some(hypergrid_monitor) = &config.hypergrid_monitor {
+                    failures.extend(hypergrid_monitor.check(&rpc_client));
+                }
+
                 for failure in failures.iter() {
                     error!("{} sanity failure: {}", failure.0, failure.1);
                 }